@@ -0,0 +1,106 @@
+use ic_cdk::{api::msg_caller, update};
+
+use crate::events::event_log::record_event;
+use crate::events::evnts_type::Events;
+use crate::house_settings::is_paused;
+use crate::market::functions::open_position_in_market::{
+    FailureReason, OpenPositioninMarketResult,
+};
+use crate::stable_memory::MARKETS_LIST;
+use crate::user::balance_utils::{get_user_balance, set_user_balance};
+use crate::user::position_util::_put_user_position_detail;
+use crate::user::user_query::try_get_user_position_details;
+
+/// Adds collateral from the caller's balance to one of their open positions.
+///
+/// The added collateral repays the position's debt first, so the position's size and pnl are unchanged
+/// while its leverage is reduced. Repaying debt does not wait for a price update,
+/// so it can be used to rescue positions close to liquidation.
+/// The amount left once the debt is repaid is added to the position at 1x leverage at the current price
+/// (after its price impact), settling the funding and borrowing fees accrued by the position.
+///
+/// # Parameters
+///
+/// * `market_index` (u64): The unique identifier of the market containing the position
+/// * `position_id` (u64): The unique identifier of the position
+/// * `amount` (u128): The collateral to add in quote asset (20-decimal precision)
+///
+/// # Returns
+///
+/// Returns [`OpenPositioninMarketResult`] which can be:
+/// - `Settled { position_id, position }`: Collateral was added, returns the updated position
+/// - `Waiting { id: None }`: The amount exceeds the position's debt and the market has no current price,
+///   nothing is added
+/// - `Failed { reason }`: Insufficient balance, the position does not exist, the amount left after the debt
+///   can not be added to the position (e.g fees accrued exceed it or max leverage exceeded)
+///   or the protocol is paused or the market does not accept closing positions, or opening positions
+///   when the amount exceeds the position's debt (`MarketNotActive`)
+#[update(name = "addCollateral")]
+pub fn add_collateral(
+    market_index: u64,
+    position_id: u64,
+    amount: u128,
+) -> OpenPositioninMarketResult {
+    let owner = msg_caller();
+    let owner_balance = get_user_balance(owner);
+
+    if owner_balance < amount {
+        return OpenPositioninMarketResult::Failed {
+            reason: FailureReason::InsufficientBalance,
+        };
+    }
+
+    let Some((position_market_index, position)) = try_get_user_position_details(owner, position_id)
+    else {
+        return OpenPositioninMarketResult::Failed {
+            reason: FailureReason::Other,
+        };
+    };
+
+    if position_market_index != market_index {
+        return OpenPositioninMarketResult::Failed {
+            reason: FailureReason::Other,
+        };
+    }
+
+    MARKETS_LIST.with_borrow_mut(|reference| {
+        let mut market = reference.get(market_index).expect("Market does not exist");
+
+        // the amount left after the debt is repaid increases the position
+        let increases_position = amount > position.debt;
+
+        if is_paused()
+            || !market.status.allows_closing()
+            || (increases_position && !market.status.allows_opening())
+        {
+            return OpenPositioninMarketResult::Failed {
+                reason: FailureReason::MarketNotActive,
+            };
+        }
+
+        let result = market.add_collateral_to_position(position, amount);
+
+        if let OpenPositioninMarketResult::Settled { position, .. } = result {
+            set_user_balance(owner, owner_balance - amount);
+
+            _put_user_position_detail(owner, market_index, position_id, position);
+
+            reference.set(market_index, &market);
+
+            record_event(Events::AddCollateral {
+                owner,
+                market_index,
+                position_id,
+                amount,
+            });
+
+            return OpenPositioninMarketResult::Settled {
+                position_id: Some(position_id),
+                position,
+                position_fee: 0,
+            };
+        }
+
+        result
+    })
+}
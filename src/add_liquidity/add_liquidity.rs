@@ -2,7 +2,9 @@ use ic_cdk::{api::msg_caller, update};
 
 use crate::add_liquidity::add_liquidity_params::AddLiquidityParams;
 use crate::constants::ADD_LIQUIDITY_PRIORITY_INDEX;
-use crate::house_settings::{get_execution_fee, update_execution_fees_accumulated};
+use crate::events::event_log::record_event;
+use crate::events::evnts_type::Events;
+use crate::house_settings::{get_execution_fee, is_paused, update_execution_fees_accumulated};
 use crate::market::market_details::LiquidityOperationResult;
 
 use crate::pricing_update_management::price_waiting_operation_trait::PriceWaitingOperation;
@@ -30,7 +32,8 @@ use crate::user::balance_utils::{
 ///
 /// Returns [`LiquidityOperationResult`] which can be:
 /// - `Settled { amount_out }`: Successfully added liquidity, returns actual shares received
-/// - `Waiting`: Operation queued due to stale price data, will execute when price updates
+/// - `Waiting { id }`: Operation queued due to stale price data, will execute when price updates,
+///   `id` is the ticket for querying the operation status
 /// - `Failed`: Operation failed due to insufficient balance or invalid parameters
 ///
 /// # Security Notes
@@ -61,14 +64,16 @@ pub fn add_liquidity(params: AddLiquidityParams) -> LiquidityOperationResult {
     let result = _add_liquidity(&params);
 
     if let LiquidityOperationResult::Waiting { id: _ } = result {
-        put_price_waiting_operation(
+        let ticket = put_price_waiting_operation(
             params.market_index,
             ADD_LIQUIDITY_PRIORITY_INDEX,
             PriceWaitingOperation::from(params),
         );
+
+        return LiquidityOperationResult::Waiting { id: Some(ticket) };
     }
 
-    return result;
+    result
 }
 
 /// Internal implementation of the add liquidity functionality.
@@ -117,6 +122,10 @@ pub fn _add_liquidity(params: &AddLiquidityParams) -> LiquidityOperationResult {
     MARKETS_LIST.with_borrow_mut(|reference| {
         let mut market = reference.get(market_index).expect("Market does not exist");
 
+        if is_paused() || !market.status.allows_opening() {
+            return LiquidityOperationResult::Failed("Market is not active".to_string());
+        }
+
         let result = market.add_liquidity_to_market((*params).into());
 
         if let LiquidityOperationResult::Settled { amount_out } = result {
@@ -128,8 +137,16 @@ pub fn _add_liquidity(params: &AddLiquidityParams) -> LiquidityOperationResult {
             update_user_market_liquidity_shares(depositor, market_index, amount_out, true);
 
             reference.set(market_index, &market);
+
+            record_event(Events::AddedLiquidity {
+                depositor,
+                market_index,
+                amount: params.amount,
+                price: market.pricing_manager.price,
+                shares: amount_out,
+            });
         }
 
-        return result;
+        result
     })
 }
@@ -1,18 +1,20 @@
 use candid::{CandidType, Principal};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::add_liquidity::add_liquidity::_add_liquidity;
 use crate::market::functions::add_liquidity_to_market::AddLiquidityToMarketParams;
+use crate::pricing_update_management::operation_status::OperationStatus;
 use crate::pricing_update_management::price_waiting_operation_trait::{
     PriceWaitingOperation, PriceWaitingOperationTrait,
 };
+use crate::user::balance_utils::update_user_balance;
 
 /// Parameters for adding liquidity to a market.
 ///
 /// This struct contains all the necessary information to add liquidity to a specific
 /// market in the clearing house. The depositor must be the message caller to ensure
 /// security and prevent unauthorized operations.
-#[derive(CandidType, Deserialize, Copy, Clone)]
+#[derive(CandidType, Deserialize, Serialize, Copy, Clone)]
 pub struct AddLiquidityParams {
     /// The unique identifier of the target market to add liquidity to.
     /// Must correspond to an existing market in the clearing house.
@@ -34,21 +36,44 @@ pub struct AddLiquidityParams {
     /// Acts as slippage protection: if actual shares would be lower, the transaction fails.
     #[serde(rename = "minAmountOut")]
     pub min_amount_out: u128,
+
+    /// Optional deadline for the operation, in nanoseconds since the UNIX epoch.
+    /// If the operation is queued waiting for a price update and is not executed by this time,
+    /// it is failed and its reserved amounts are released.
+    /// Without a deadline, a queued operation fails if the next price update fails.
+    pub deadline: Option<u64>,
 }
 
 impl PriceWaitingOperationTrait for AddLiquidityParams {
-    fn execute(&self) {
-        _add_liquidity(&self);
+    fn execute(&self) -> OperationStatus {
+        _add_liquidity(self).into()
+    }
+
+    fn deadline(&self) -> Option<u64> {
+        self.deadline
+    }
+
+    fn executor(&self) -> Option<Principal> {
+        Some(self.depositor)
+    }
+
+    /// reserves the amount, the execution fee is taken on execution
+    fn reserve(&self) {
+        update_user_balance(self.depositor, self.amount, false);
+    }
+
+    fn release(&self) {
+        update_user_balance(self.depositor, self.amount, true);
     }
 }
 
-impl Into<AddLiquidityToMarketParams> for AddLiquidityParams {
-    fn into(self) -> AddLiquidityToMarketParams {
-        let Self {
+impl From<AddLiquidityParams> for AddLiquidityToMarketParams {
+    fn from(val: AddLiquidityParams) -> Self {
+        let AddLiquidityParams {
             min_amount_out,
             amount,
             ..
-        } = self;
+        } = val;
         AddLiquidityToMarketParams {
             min_amount_out,
             amount,
@@ -1,3 +1,4 @@
+#[allow(clippy::module_inception)]
 pub mod add_liquidity;
 pub mod add_liquidity_params;
 pub mod add_liquidity_result;
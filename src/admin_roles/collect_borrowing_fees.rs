@@ -1,27 +1,35 @@
-use candid::CandidType;
+use candid::{CandidType, Principal};
 use ic_cdk::update;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-use crate::admin_roles::admin_guard;
+use crate::admin_roles::keeper_guard;
 use crate::{
     constants::COLLECT_BORROW_FEES_PRIORITY_INDEX,
+    events::{event_log::record_event, evnts_type::Events},
     market::market_details::MarketDetails,
     pricing_update_management::{
+        operation_status::{OperationOutcome, OperationStatus},
         price_waiting_operation_trait::{PriceWaitingOperation, PriceWaitingOperationTrait},
         price_waiting_operation_utils::put_price_waiting_operation,
     },
     stable_memory::MARKETS_LIST,
 };
 
-#[derive(CandidType, Deserialize)]
+#[derive(CandidType, Deserialize, Serialize, Clone)]
 pub struct CollectBorrowFeesParams {
     #[serde(rename = "marketIndex")]
     pub market_index: u64,
 }
 
 impl PriceWaitingOperationTrait for CollectBorrowFeesParams {
-    fn execute(&self) {
+    fn execute(&self) -> OperationStatus {
         _collect_borrow_fees(self.market_index);
+
+        OperationStatus::Settled(OperationOutcome::Executed)
+    }
+
+    fn executor(&self) -> Option<Principal> {
+        None
     }
 }
 
@@ -31,11 +39,11 @@ impl From<CollectBorrowFeesParams> for PriceWaitingOperation {
     }
 }
 
-#[update(name = "collectBorrowFees", guard = "admin_guard")]
+#[update(name = "collectBorrowFees", guard = "keeper_guard")]
 pub fn collect_borrow_fees(market_index: u64) {
     let outcome = _collect_borrow_fees(market_index);
 
-    if outcome == false {
+    if !outcome {
         put_price_waiting_operation(
             market_index,
             COLLECT_BORROW_FEES_PRIORITY_INDEX,
@@ -53,7 +61,12 @@ fn _collect_borrow_fees(market_index: u64) -> bool {
 
         reference.set(market_index, &market);
 
-        return true;
+        record_event(Events::CollectBorrowFees {
+            market_index,
+            price: market.pricing_manager.price,
+        });
+
+        true
     })
 }
 
@@ -1,9 +1,11 @@
 use ic_cdk::update;
 
-use crate::admin_roles::admin_guard;
+use crate::admin_roles::keeper_guard;
+use crate::events::event_log::record_event;
+use crate::events::evnts_type::Events;
 use crate::stable_memory::MARKETS_LIST;
 
-#[update(name = "settleFundingFees", guard = "admin_guard")]
+#[update(name = "settleFundingFees", guard = "keeper_guard")]
 fn settle_funding_fees(market_index: u64) {
     MARKETS_LIST.with_borrow_mut(|reference| {
         let mut market = reference.get(market_index).expect("Market does not exist");
@@ -11,5 +13,10 @@ fn settle_funding_fees(market_index: u64) {
         market.settle_funding_payment();
 
         reference.set(market_index, &market);
+
+        record_event(Events::SettleFundingFees {
+            market_index,
+            price: market.pricing_manager.price,
+        });
     });
 }
@@ -2,15 +2,13 @@ use candid::CandidType;
 use ic_cdk::update;
 use serde::Deserialize;
 
-use crate::admin_roles::admin_guard;
+use crate::admin_roles::owner_guard;
+use crate::constants::DEFAULT_MARKET_SETTLEMENT_INTERVAL;
+use crate::settlement_management::settlement_timer_utils::start_market_settlement_timer;
 
 use crate::{
-    market::{
-        components::{
-            bias::Bias, funding_state::FundingState, liquidity_state::HouseLiquidityState,
-        },
-        market_details::{MarketDetails, MarketState},
-    },
+    events::{event_log::record_event, evnts_type::Events},
+    market::{market_config::MarketConfig, market_details::MarketDetails},
     pricing_update_management::price_fetch::AssetPricingDetails,
     stable_memory::MARKETS_LIST,
 };
@@ -20,54 +18,34 @@ use crate::{
 pub struct CreateMarketParams {
     #[serde(rename = "assetPricingDetails")]
     pub asset_pricing_details: AssetPricingDetails,
-    #[serde(rename = "initState")]
-    pub init_state: MarketState,
-    #[serde(rename = "fundingFactor")]
-    pub funding_factor: u128,
-    #[serde(rename = "fundingExponentFactor")]
-    pub funding_exponent_factor: u128,
-    #[serde(rename = "longsMaxReserveFactor")]
-    pub longs_max_reserve_factor: u128,
-    #[serde(rename = "longsBorrowingExponentFactor")]
-    pub longs_borrowing_exponent_factor: u128,
-    #[serde(rename = "longsBaseBorrowingFactor")]
-    pub longs_base_borrowing_factor: u128,
-    #[serde(rename = "shortsMaxReserveFactor")]
-    pub shorts_max_reserve_factor: u128,
-    #[serde(rename = "shortsBorrowingExponentFactor")]
-    pub shorts_borrowing_exponent_factor: u128,
-    #[serde(rename = "shortsBaseBorrowingFactor")]
-    pub shorts_base_borrowing_factor: u128,
+    /// every tunable parameter of the market, see [`MarketConfig`] for the bounds
+    pub config: MarketConfig,
 }
 
-// #[update(name = "addMarket", guard = "admin_guard")]
-#[update(name = "createNewMarket", guard = "admin_guard")]
+// #[update(name = "addMarket", guard = "owner_guard")]
+#[update(name = "createNewMarket", guard = "owner_guard")]
 pub fn create_new_market(params: CreateMarketParams) -> u64 {
-    let mut liquidity_state = HouseLiquidityState::default();
-    liquidity_state.longs_max_reserve_factor = params.longs_max_reserve_factor;
-    liquidity_state.shorts_max_reserve_factor = params.shorts_max_reserve_factor;
+    if let Err(reason) = params.config.validate() {
+        panic!("{}", reason);
+    }
 
-    let mut funding_state = FundingState::default();
-    funding_state.funding_factor = params.funding_factor;
-    funding_state.funding_exponent_factor = params.funding_exponent_factor;
+    let mut market_details = MarketDetails {
+        index_asset_pricing_details: params.asset_pricing_details,
+        ..Default::default()
+    };
+    market_details.apply_config(params.config);
 
-    let mut bias = Bias::default();
-    bias.longs.borrowing_exponent_factor_ = params.longs_borrowing_exponent_factor;
-    bias.longs.base_borrowing_factor = params.longs_base_borrowing_factor;
-    bias.shorts.borrowing_exponent_factor_ = params.shorts_borrowing_exponent_factor;
-    bias.shorts.base_borrowing_factor = params.shorts_base_borrowing_factor;
-    // bias.longs_reserve_factor = params.longs_reserve_factor;
-    // bias.shorts_max_reserve_factor = params.shorts_max_reserve_factor;
+    let market_index = MARKETS_LIST.with_borrow_mut(|reference| {
+        reference.push(&market_details);
+        reference.len() - 1
+    });
 
-    let mut market_details = MarketDetails::default();
-    market_details.index_asset_pricing_details = params.asset_pricing_details;
-    market_details.state = params.init_state;
-    market_details.funding_state = funding_state;
-    market_details.bias_tracker = bias;
-    market_details.liquidity_state = liquidity_state;
+    start_market_settlement_timer(market_index, DEFAULT_MARKET_SETTLEMENT_INTERVAL);
 
-    MARKETS_LIST.with_borrow_mut(|reference| {
-        reference.push(&market_details);
-        return reference.len() - 1;
-    })
+    record_event(Events::CreateMarket {
+        market_index,
+        config: params.config,
+    });
+
+    market_index
 }
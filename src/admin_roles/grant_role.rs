@@ -0,0 +1,32 @@
+use candid::Principal;
+use ic_cdk::update;
+
+use crate::admin_roles::owner_guard;
+use crate::admin_roles::roles::{self, Role};
+use crate::events::event_log::record_event;
+use crate::events::evnts_type::Events;
+
+/// Grants a role to a principal.
+///
+/// # Parameters
+///
+/// * `principal` (Principal): The principal receiving the role
+/// * `role` ([`Role`]): The role granted, the owner role can only be transferred with `proposeOwner`
+///
+/// # Returns
+///
+/// Returns `bool` indicating success:
+/// - `true`: Role was granted
+/// - `false`: Principal already holds the role
+#[update(name = "grantRole", guard = "owner_guard")]
+pub fn grant_role(principal: Principal, role: Role) -> bool {
+    assert!(role != Role::Owner, "Owner role can not be granted");
+
+    let granted = roles::grant_role(principal, role);
+
+    if granted {
+        record_event(Events::GrantRole { principal, role });
+    }
+
+    granted
+}
@@ -1,15 +1,43 @@
 pub mod collect_borrowing_fees;
 pub mod collect_funding_fees;
 pub mod create_market;
+pub mod grant_role;
+pub mod pause_protocol;
+pub mod revoke_role;
+pub mod roles;
+pub mod set_execution_fee;
+pub mod set_market_status;
+pub mod set_settlement_interval;
+pub mod set_timelock_delay;
+pub mod transfer_ownership;
+pub mod update_market_config;
 
-use crate::stable_memory::ADMIN;
+use crate::admin_roles::roles::{Role, has_role};
 
-pub fn admin_guard() -> Result<(), String> {
-    let caller = ic_cdk::api::msg_caller();
-    let admin = ADMIN.with_borrow(|reference| *reference.get());
-    if caller == admin {
+pub fn owner_guard() -> Result<(), String> {
+    _role_guard(Role::Owner, "Caller is not owner")
+}
+
+pub fn risk_manager_guard() -> Result<(), String> {
+    _role_guard(Role::RiskManager, "Caller is not risk manager")
+}
+
+pub fn keeper_guard() -> Result<(), String> {
+    _role_guard(Role::Keeper, "Caller is not keeper")
+}
+
+pub fn pauser_guard() -> Result<(), String> {
+    _role_guard(Role::Pauser, "Caller is not pauser")
+}
+
+pub fn treasurer_guard() -> Result<(), String> {
+    _role_guard(Role::Treasurer, "Caller is not treasurer")
+}
+
+fn _role_guard(role: Role, error: &str) -> Result<(), String> {
+    if has_role(ic_cdk::api::msg_caller(), role) {
         Ok(())
     } else {
-        Err("Caller is not admin".to_string())
+        Err(error.to_string())
     }
 }
@@ -0,0 +1,28 @@
+use ic_cdk::update;
+
+use crate::admin_roles::{pauser_guard, risk_manager_guard};
+use crate::events::event_log::record_event;
+use crate::events::evnts_type::Events;
+use crate::house_settings::set_paused;
+
+/// Pauses the protocol, rejecting opening and closing positions, adding and removing liquidity
+/// and withdrawals in every market until it is unpaused.
+///
+/// # Security Notes
+///
+/// - **Emergency**: Callable by the pauser so activity can be halted without the timelock,
+///   unpausing requires the risk manager
+#[update(name = "pauseProtocol", guard = "pauser_guard")]
+pub fn pause_protocol() {
+    set_paused(true);
+
+    record_event(Events::SetProtocolPaused { paused: true });
+}
+
+/// Unpauses the protocol, markets accept the activity allowed by their own status again.
+#[update(name = "unpauseProtocol", guard = "risk_manager_guard")]
+pub fn unpause_protocol() {
+    set_paused(false);
+
+    record_event(Events::SetProtocolPaused { paused: false });
+}
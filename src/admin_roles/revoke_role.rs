@@ -0,0 +1,30 @@
+use candid::Principal;
+use ic_cdk::update;
+
+use crate::admin_roles::owner_guard;
+use crate::admin_roles::roles::{self, Role};
+use crate::events::event_log::record_event;
+use crate::events::evnts_type::Events;
+
+/// Revokes a role from a principal.
+///
+/// # Parameters
+///
+/// * `principal` (Principal): The principal losing the role
+/// * `role` ([`Role`]): The role revoked
+///
+/// # Returns
+///
+/// Returns `bool` indicating success:
+/// - `true`: Role was revoked
+/// - `false`: Principal does not hold the role
+#[update(name = "revokeRole", guard = "owner_guard")]
+pub fn revoke_role(principal: Principal, role: Role) -> bool {
+    let revoked = roles::revoke_role(principal, role);
+
+    if revoked {
+        record_event(Events::RevokeRole { principal, role });
+    }
+
+    revoked
+}
@@ -0,0 +1,121 @@
+use std::borrow::Cow;
+
+use candid::{CandidType, Principal};
+use ic_stable_structures::{Storable, storable::Bound};
+use serde::{Deserialize, Serialize};
+
+use crate::stable_memory::{OWNER, PENDING_OWNER, ROLES};
+
+/// Role
+///
+/// a set of permissions held by a principal
+#[derive(CandidType, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Role {
+    /// Controls the protocol, grants and revokes the other roles and holds every permission
+    Owner,
+    /// Queues and executes risk parameter changes
+    RiskManager,
+    /// Runs the periodic fee settlement of markets
+    Keeper,
+    /// Halts activity in an emergency
+    Pauser,
+    /// Sweeps the accumulated fees to the treasury account
+    Treasurer,
+}
+
+/// Granted Roles
+///
+/// the roles granted to a principal by the owner
+#[derive(CandidType, Deserialize, Serialize, Clone, Default)]
+pub struct GrantedRoles {
+    pub roles: Vec<Role>,
+}
+
+pub fn get_owner() -> Principal {
+    OWNER.with_borrow(|reference| *reference.get())
+}
+
+pub fn get_pending_owner() -> Option<Principal> {
+    PENDING_OWNER.with_borrow(|reference| *reference.get())
+}
+
+/// Has Role
+///
+/// checks if a principal holds a role, the owner holds every role
+pub fn has_role(principal: Principal, role: Role) -> bool {
+    if principal == get_owner() {
+        return true;
+    }
+
+    role != Role::Owner && get_granted_roles(principal).roles.contains(&role)
+}
+
+pub fn get_granted_roles(principal: Principal) -> GrantedRoles {
+    ROLES.with_borrow(|reference| reference.get(&principal).unwrap_or_default())
+}
+
+/// Grant Role
+///
+/// Returns false if the principal already holds the role
+pub fn grant_role(principal: Principal, role: Role) -> bool {
+    let mut granted_roles = get_granted_roles(principal);
+
+    if granted_roles.roles.contains(&role) {
+        return false;
+    }
+
+    granted_roles.roles.push(role);
+
+    ROLES.with_borrow_mut(|reference| {
+        reference.insert(principal, granted_roles);
+    });
+
+    true
+}
+
+/// Revoke Role
+///
+/// Returns false if the principal does not hold the role
+pub fn revoke_role(principal: Principal, role: Role) -> bool {
+    let mut granted_roles = get_granted_roles(principal);
+
+    if !granted_roles.roles.contains(&role) {
+        return false;
+    }
+
+    granted_roles
+        .roles
+        .retain(|granted_role| *granted_role != role);
+
+    ROLES.with_borrow_mut(|reference| {
+        if granted_roles.roles.is_empty() {
+            reference.remove(&principal);
+        } else {
+            reference.insert(principal, granted_roles);
+        }
+    });
+
+    true
+}
+
+impl Storable for GrantedRoles {
+    fn to_bytes(&self) -> Cow<'_, [u8]> {
+        let serialized = bincode::serialize(self).expect("failed to serialize");
+        Cow::Owned(serialized)
+    }
+
+    /// Converts the element into an owned byte vector.
+    ///
+    /// This method consumes `self` and avoids cloning when possible.
+    fn into_bytes(self) -> Vec<u8> {
+        bincode::serialize(&self).expect("failed to serialize")
+    }
+
+    /// Converts bytes into an element.
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        bincode::deserialize(bytes.as_ref()).expect("failed to desearalize")
+    }
+
+    /// The size bounds of the type.
+    const BOUND: Bound = Bound::Unbounded;
+}
@@ -0,0 +1,22 @@
+use ic_cdk::{api::msg_caller, update};
+
+use crate::admin_roles::risk_manager_guard;
+use crate::timelock::timelock_utils::queue_change;
+use crate::timelock::timelocked_change::TimelockedChange;
+
+/// Queues a change of the execution fee charged on every operation.
+///
+/// # Parameters
+///
+/// * `execution_fee` (u128): The new execution fee in quote asset (20-decimal precision)
+///
+/// # Returns
+///
+/// Returns the change id, used for executing or cancelling the change after the timelock delay
+#[update(name = "setExecutionFee", guard = "risk_manager_guard")]
+pub fn set_execution_fee(execution_fee: u128) -> u64 {
+    queue_change(
+        TimelockedChange::SetExecutionFee(execution_fee),
+        msg_caller(),
+    )
+}
@@ -0,0 +1,58 @@
+use candid::Principal;
+use ic_cdk::{api::msg_caller, update};
+
+use crate::admin_roles::pauser_guard;
+use crate::admin_roles::roles::{Role, has_role};
+use crate::delisting::delisting_details::get_delisting_details;
+use crate::events::event_log::record_event;
+use crate::events::evnts_type::Events;
+use crate::market::market_status::MarketStatus;
+use crate::stable_memory::MARKETS_LIST;
+
+/// Sets the activity accepted by a market.
+///
+/// # Parameters
+///
+/// * `market_index` (u64): The unique identifier of the market
+/// * `status` ([`MarketStatus`]): The new status, `Active`, `CloseOnly` or `Paused`
+///
+/// # Security Notes
+///
+/// - **Emergency**: The pauser can make a market more restrictive (e.g `Active` to `CloseOnly` or `Paused`)
+///   without the timelock
+/// - **Unpausing**: Making a market less restrictive (e.g `Paused` to `Active`) requires the risk manager
+/// - **Delisting**: A market being delisted can not be made active and the `Delisted` status is only set
+///   by the final settlement
+#[update(name = "setMarketStatus", guard = "pauser_guard")]
+pub fn set_market_status(market_index: u64, status: MarketStatus) {
+    _set_market_status(market_index, status, msg_caller());
+}
+
+pub fn _set_market_status(market_index: u64, status: MarketStatus, caller: Principal) {
+    MARKETS_LIST.with_borrow_mut(|reference| {
+        let mut market = reference.get(market_index).expect("Market does not exist");
+
+        assert!(
+            status != MarketStatus::Delisted && market.status != MarketStatus::Delisted,
+            "Market is delisted"
+        );
+        assert!(
+            status != MarketStatus::Active || get_delisting_details(market_index).is_none(),
+            "Market is being delisted"
+        );
+
+        assert!(
+            status >= market.status || has_role(caller, Role::RiskManager),
+            "Caller is not risk manager"
+        );
+
+        market.status = status;
+
+        reference.set(market_index, &market);
+    });
+
+    record_event(Events::SetMarketStatus {
+        market_index,
+        status,
+    });
+}
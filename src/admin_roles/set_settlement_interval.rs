@@ -0,0 +1,32 @@
+use ic_cdk::update;
+
+use crate::admin_roles::risk_manager_guard;
+use crate::constants::MIN_MARKET_SETTLEMENT_INTERVAL;
+use crate::events::event_log::record_event;
+use crate::events::evnts_type::Events;
+use crate::settlement_management::settlement_timer_utils::start_market_settlement_timer;
+use crate::stable_memory::MARKETS_LIST;
+
+/// Sets the interval of the recurring funding and borrowing settlement of a market.
+///
+/// # Parameters
+///
+/// * `market_index` (u64): The unique identifier of the market
+/// * `interval_in_secs` (u64): Seconds between settlements, zero stops the recurring settlement
+#[update(name = "setMarketSettlementInterval", guard = "risk_manager_guard")]
+pub fn set_market_settlement_interval(market_index: u64, interval_in_secs: u64) {
+    let market_exists = MARKETS_LIST.with_borrow(|reference| market_index < reference.len());
+    assert!(market_exists, "Market does not exist");
+
+    assert!(
+        interval_in_secs == 0 || interval_in_secs >= MIN_MARKET_SETTLEMENT_INTERVAL,
+        "Settlement interval too short"
+    );
+
+    start_market_settlement_timer(market_index, interval_in_secs);
+
+    record_event(Events::SetSettlementInterval {
+        market_index,
+        interval_in_secs,
+    });
+}
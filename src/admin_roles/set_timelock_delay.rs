@@ -0,0 +1,23 @@
+use ic_cdk::{api::msg_caller, update};
+
+use crate::admin_roles::owner_guard;
+use crate::timelock::timelock_utils::queue_change;
+use crate::timelock::timelocked_change::TimelockedChange;
+
+/// Queues a change of the timelock delay, the change itself waits for the current delay.
+///
+/// # Parameters
+///
+/// * `delay_in_secs` (u64): The new delay between queueing and executing changes,
+///   between `MIN_TIMELOCK_DELAY` and `MAX_TIMELOCK_DELAY` seconds
+///
+/// # Returns
+///
+/// Returns the change id, used for executing or cancelling the change after the timelock delay
+#[update(name = "setTimelockDelay", guard = "owner_guard")]
+pub fn set_timelock_delay(delay_in_secs: u64) -> u64 {
+    queue_change(
+        TimelockedChange::SetTimelockDelay(delay_in_secs),
+        msg_caller(),
+    )
+}
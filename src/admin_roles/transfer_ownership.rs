@@ -0,0 +1,50 @@
+use candid::Principal;
+use ic_cdk::{api::msg_caller, update};
+
+use crate::admin_roles::owner_guard;
+use crate::admin_roles::roles::get_pending_owner;
+use crate::events::event_log::record_event;
+use crate::events::evnts_type::Events;
+use crate::stable_memory::{OWNER, PENDING_OWNER};
+
+/// Proposes a new owner, the ownership is transferred once the new owner accepts it.
+///
+/// # Parameters
+///
+/// * `new_owner` (Principal): The proposed owner, replaces any previously proposed owner
+#[update(name = "proposeOwner", guard = "owner_guard")]
+pub fn propose_owner(new_owner: Principal) {
+    PENDING_OWNER.with_borrow_mut(|reference| {
+        reference.set(Some(new_owner));
+    });
+
+    record_event(Events::ProposeOwner { new_owner });
+}
+
+/// Accepts the ownership proposed to the caller.
+///
+/// # Security Notes
+///
+/// - **Caller Verification**: The caller must be the proposed owner, so the ownership
+///   can not be transferred to a principal that can not sign
+#[update(name = "acceptOwnership")]
+pub fn accept_ownership() {
+    _accept_ownership(msg_caller());
+}
+
+pub fn _accept_ownership(caller: Principal) {
+    assert!(
+        get_pending_owner() == Some(caller),
+        "Caller is not the pending owner"
+    );
+
+    OWNER.with_borrow_mut(|reference| {
+        reference.set(caller);
+    });
+
+    PENDING_OWNER.with_borrow_mut(|reference| {
+        reference.set(None);
+    });
+
+    record_event(Events::AcceptOwnership { owner: caller });
+}
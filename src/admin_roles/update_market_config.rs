@@ -0,0 +1,31 @@
+use ic_cdk::{api::msg_caller, update};
+
+use crate::admin_roles::risk_manager_guard;
+use crate::market::market_config::MarketConfig;
+use crate::timelock::timelock_utils::queue_change;
+use crate::timelock::timelocked_change::TimelockedChange;
+
+/// Queues an update of every tunable parameter of a market.
+///
+/// The update can be executed with `executeTimelockedChange` after the timelock delay.
+/// Funding and borrowing fees are accrued with the current parameters on execution,
+/// so the new parameters only apply from the time of execution.
+///
+/// # Parameters
+///
+/// * `market_index` (u64): The unique identifier of the market
+/// * `config` ([`MarketConfig`]): The new parameters, validated with the same bounds as for creating a market
+///
+/// # Returns
+///
+/// Returns the change id, used for executing or cancelling the change
+#[update(name = "updateMarketConfig", guard = "risk_manager_guard")]
+pub fn update_market_config(market_index: u64, config: MarketConfig) -> u64 {
+    queue_change(
+        TimelockedChange::UpdateMarketConfig {
+            market_index,
+            config: Box::new(config),
+        },
+        msg_caller(),
+    )
+}
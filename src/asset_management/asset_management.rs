@@ -0,0 +1,164 @@
+use candid::{CandidType, Principal};
+use icrc_ledger_types::icrc1::account::Account;
+use serde::{Deserialize, Serialize};
+
+use crate::asset_management::functions::{canister_account, user_deposit_subaccount};
+use crate::asset_management::icp_send_asset_out::send_asset_out_icp;
+use crate::asset_management::icrc2_deposit_in::send_asset_in_asset_icrc;
+use crate::asset_management::icrc2_send_out::send_asset_out_icrc;
+use crate::math::math::{apply_precision, to_precision};
+
+/// Asset Ledger Type
+///
+/// the standard implemented by the ledger of the house asset
+#[cfg_attr(test, derive(Debug, PartialEq, Eq))]
+#[derive(Serialize, Deserialize, CandidType, Clone, Copy)]
+pub enum AssetLedgerType {
+    /// ICRC-2 ledger, deposits are pulled from the user's account with an approved `icrc2_transfer_from`
+    ICRC,
+    /// ICRC-1 only ledger, deposits are first sent by the user into their deposit subaccount of the canister
+    ICRC1,
+    /// Legacy ICP ledger, deposits are first sent by the user into their deposit subaccount of the canister
+    ICP,
+    /// ckBTC ledger (ICRC-2)
+    CKBTC,
+}
+
+/// Asset Ledger
+///
+/// details of the ledger of the house asset
+#[cfg_attr(test, derive(Debug, PartialEq, Eq))]
+#[derive(Serialize, Deserialize, CandidType, Clone, Copy)]
+pub struct AssetLedger {
+    /// Principal ID of the ledger canister
+    pub ledger_id: Principal,
+    pub ledger_type: AssetLedgerType,
+    /// Number of decimals of the asset on the ledger (e.g 8 for ICP and ckBTC)
+    pub asset_decimals: u32,
+    /// Transfer fee of the ledger in ledger units (e.g 10_000 for ICP)
+    pub ledger_fee: u128,
+}
+
+impl AssetLedger {
+    /// Sends In
+    ///
+    /// moves `amount` (20-decimal precision) of the house asset from `user` into the canister's account
+    ///
+    /// Returns true if the transfer succeeded
+    pub async fn _send_in(&self, amount: u128, user: Principal) -> bool {
+        let ledger_amount = self.to_ledger_amount(amount);
+
+        if ledger_amount == 0 {
+            return false;
+        }
+
+        let Self {
+            ledger_id,
+            ledger_type,
+            ledger_fee,
+            ..
+        } = *self;
+
+        match ledger_type {
+            AssetLedgerType::ICRC | AssetLedgerType::CKBTC => {
+                let from_account = Account {
+                    owner: user,
+                    subaccount: None,
+                };
+                send_asset_in_asset_icrc(ledger_amount, ledger_id, from_account, canister_account())
+                    .await
+            }
+            AssetLedgerType::ICRC1 => {
+                send_asset_out_icrc(
+                    ledger_amount,
+                    ledger_id,
+                    Some(user_deposit_subaccount(&user)),
+                    canister_account(),
+                )
+                .await
+            }
+            AssetLedgerType::ICP => {
+                send_asset_out_icp(
+                    ledger_amount,
+                    ledger_fee,
+                    ledger_id,
+                    Some(user_deposit_subaccount(&user)),
+                    canister_account(),
+                )
+                .await
+            }
+        }
+    }
+
+    /// Send Out
+    ///
+    /// sends `amount` (20-decimal precision) of the house asset from the canister's account to `user`
+    ///
+    /// Returns true if the transfer succeeded
+    pub async fn _send_out(&self, amount: u128, user: Principal) -> bool {
+        let to_account = Account {
+            owner: user,
+            subaccount: None,
+        };
+
+        self._send_out_to_account(amount, to_account).await
+    }
+
+    /// Send Out To Account
+    ///
+    /// sends `amount` (20-decimal precision) of the house asset from the canister's account to `to_account`
+    /// @dev the ledger fee is taken out of the amount sent so the canister's balance reduces by exactly `amount`
+    pub async fn _send_out_to_account(&self, amount: u128, to_account: Account) -> bool {
+        let Some(amount_after_fee) = self.ledger_amount_after_fee(amount) else {
+            return false;
+        };
+
+        let Self {
+            ledger_id,
+            ledger_type,
+            ledger_fee,
+            ..
+        } = *self;
+
+        match ledger_type {
+            AssetLedgerType::ICRC | AssetLedgerType::ICRC1 | AssetLedgerType::CKBTC => {
+                send_asset_out_icrc(amount_after_fee, ledger_id, None, to_account).await
+            }
+            AssetLedgerType::ICP => {
+                send_asset_out_icp(amount_after_fee, ledger_fee, ledger_id, None, to_account).await
+            }
+        }
+    }
+
+    /// Ledger Amount After Fee
+    ///
+    /// the ledger units received for sending out `amount` (20-decimal precision)
+    ///
+    /// Returns None if the amount does not cover the ledger fee
+    pub fn ledger_amount_after_fee(&self, amount: u128) -> Option<u128> {
+        self.to_ledger_amount(amount)
+            .checked_sub(self.ledger_fee)
+            .filter(|amount_after_fee| *amount_after_fee > 0)
+    }
+
+    /// converts an amount with 20-decimal precision to ledger units
+    pub fn to_ledger_amount(&self, amount: u128) -> u128 {
+        apply_precision(amount, 10u128.pow(self.asset_decimals))
+    }
+
+    /// converts an amount in ledger units to 20-decimal precision
+    pub fn from_ledger_amount(&self, ledger_amount: u128) -> u128 {
+        to_precision(ledger_amount, 10u128.pow(self.asset_decimals))
+    }
+}
+
+impl Default for AssetLedger {
+    fn default() -> Self {
+        Self {
+            ledger_id: Principal::anonymous(),
+            ledger_type: AssetLedgerType::ICRC,
+            asset_decimals: 0,
+            ledger_fee: 0,
+        }
+    }
+}
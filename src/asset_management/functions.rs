@@ -0,0 +1,33 @@
+use candid::Principal;
+use ic_cdk::api::canister_self;
+use icrc_ledger_types::icrc1::account::{Account, Subaccount};
+
+use crate::asset_management::icp_send_asset_in::_to_subaccount;
+
+/// Canister Account
+///
+/// the default account of the canister where all deposited house assets are held
+pub fn canister_account() -> Account {
+    Account {
+        owner: canister_self(),
+        subaccount: None,
+    }
+}
+
+/// User Deposit Subaccount
+///
+/// the subaccount of the canister derived from the user's principal
+pub fn user_deposit_subaccount(user: &Principal) -> Subaccount {
+    _to_subaccount(user).0
+}
+
+/// User Deposit Account
+///
+/// for ledgers without ICRC-2 approvals (ICRC-1 only and legacy ICP),
+/// users send assets to this account before calling deposit
+pub fn user_deposit_account(user: &Principal) -> Account {
+    Account {
+        owner: canister_self(),
+        subaccount: Some(user_deposit_subaccount(user)),
+    }
+}
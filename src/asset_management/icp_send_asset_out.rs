@@ -1,6 +1,6 @@
 use candid::Principal;
 use ic_ledger_types::{
-    AccountIdentifier, DEFAULT_FEE, DEFAULT_SUBACCOUNT, Memo, Subaccount as ICSubaccount, Tokens,
+    AccountIdentifier, DEFAULT_SUBACCOUNT, Memo, Subaccount as ICSubaccount, Tokens,
     TransferArgs as ICRCTransferArgs, transfer,
 };
 use icrc_ledger_types::icrc1::account::{Account, Subaccount};
@@ -9,6 +9,7 @@ use icrc_ledger_types::icrc1::account::{Account, Subaccount};
 ///
 /// # Arguments
 /// * `amount` - Amount of ICP tokens to transfer (in e8s)
+/// * `fee` - Transfer fee of the ledger (in e8s)
 /// * `ledger_id` - Principal ID of the ICP ledger canister
 /// * `from_sub` - Optional subaccount to transfer from
 /// * `to_account` - Destination account details including owner and subaccount
@@ -18,43 +19,39 @@ use icrc_ledger_types::icrc1::account::{Account, Subaccount};
 ///
 /// # Notes
 /// - Returns early with true if amount is 0
-/// - Uses default fee and memo(0) for all transfers
+/// - Uses the given ledger fee and memo(0) for all transfers
 /// - Handles nested Result types from IC ledger response
 ///
 pub async fn send_asset_out_icp(
     amount: u128,
+    fee: u128,
     ledger_id: Principal,
     from_sub: Option<Subaccount>,
     to_account: Account,
 ) -> bool {
     let tokens = Tokens::from_e8s(amount as u64);
-    if tokens < DEFAULT_FEE {
+    let fee = Tokens::from_e8s(fee as u64);
+    if tokens < fee {
         return false;
     }
     let args = ICRCTransferArgs {
-        amount: Tokens::from_e8s(amount as u64),
+        amount: tokens,
         memo: Memo(0),
-        fee: DEFAULT_FEE,
+        fee,
         from_subaccount: Some(_to_ic_subaccount(from_sub)),
         to: AccountIdentifier::new(&to_account.owner, &_to_ic_subaccount(to_account.subaccount)),
         created_at_time: None,
     };
 
     match transfer(ledger_id, &args).await {
-        Ok(res) => {
-            if let Ok(_) = res {
-                return true;
-            } else {
-                return false;
-            }
-        }
-        Err(_) => return false,
-    };
+        Ok(res) => res.is_ok(),
+        Err(_) => false,
+    }
 }
 
 fn _to_ic_subaccount(sub: Option<Subaccount>) -> ICSubaccount {
     match sub {
-        Some(res) => return ICSubaccount(res),
-        None => return DEFAULT_SUBACCOUNT,
+        Some(res) => ICSubaccount(res),
+        None => DEFAULT_SUBACCOUNT,
     }
 }
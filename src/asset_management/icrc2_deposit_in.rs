@@ -44,8 +44,8 @@ pub async fn send_asset_in_asset_icrc(
 
     if let Ok(result) = call.await {
         tx_result = result.candid().unwrap();
-        return tx_result.is_ok();
+        tx_result.is_ok()
     } else {
-        return false;
+        false
     }
 }
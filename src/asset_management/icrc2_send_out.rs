@@ -20,7 +20,6 @@ use icrc_ledger_types::{
 /// - Does not specify fee, memo or timestamp (all None)
 /// - Returns false on any error in the transfer
 /// - Handles nested Result types from IC ledger response
-
 pub async fn send_asset_out_icrc(
     amount: u128,
     ledger_id: Principal,
@@ -43,8 +42,8 @@ pub async fn send_asset_out_icrc(
 
     if let Ok(result) = call.await {
         tx_result = result.candid().unwrap();
-        return tx_result.is_ok();
+        tx_result.is_ok()
     } else {
-        return false;
+        false
     }
 }
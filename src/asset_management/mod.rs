@@ -1,3 +1,5 @@
+#[allow(clippy::module_inception)]
+pub mod asset_management;
 pub mod ckbtc_minter_interface;
 pub mod functions;
 pub mod icp_send_asset_in;
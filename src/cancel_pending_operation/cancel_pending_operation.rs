@@ -0,0 +1,69 @@
+use candid::Principal;
+use ic_cdk::{api::msg_caller, update};
+
+use crate::events::event_log::record_event;
+use crate::events::evnts_type::Events;
+use crate::pricing_update_management::operation_status::{OperationStatus, set_operation_status};
+use crate::pricing_update_management::price_waiting_operation_trait::PriceWaitingOperationTrait;
+use crate::pricing_update_management::price_waiting_operation_utils::{
+    get_price_waiting_operation, remove_price_waiting_operation,
+};
+
+/// Cancels a queued price waiting operation.
+///
+/// Operations queued because of stale price data can be cancelled by their executor
+/// before the next price update executes them.
+///
+/// # Parameters
+///
+/// * `ticket` (u64): The ticket returned in the `Waiting { id }` result when the operation was queued
+///
+/// # Returns
+///
+/// Returns `bool` indicating success:
+/// - `true`: Operation was removed from the queue and its reserved amounts were released
+/// - `false`: No operation is queued with the ticket (it was already executed or cancelled)
+///
+/// # Security Notes
+///
+/// - **Caller Verification**: The caller must be the owner of the operation
+///   (position owner, liquidity depositor/owner or liquidator), operations queued by the house can not be cancelled
+///
+/// # Process Flow
+///
+/// 1. Finds the queued operation with the ticket
+/// 2. Verifies the caller is the executor of the operation
+/// 3. Removes the operation from the market's queue
+/// 4. Releases the amounts reserved for the operation (collateral, liquidity amount or liquidity shares)
+/// 5. Sets the operation status to `Cancelled`
+#[update(name = "cancelPendingOperation")]
+pub fn cancel_pending_operation(ticket: u64) -> bool {
+    _cancel_pending_operation(ticket, msg_caller())
+}
+
+pub fn _cancel_pending_operation(ticket: u64, caller: Principal) -> bool {
+    let Some((key, operation)) = get_price_waiting_operation(ticket) else {
+        return false;
+    };
+
+    assert!(
+        operation.executor() == Some(caller),
+        "Caller is not the owner of the operation"
+    );
+
+    remove_price_waiting_operation(key);
+
+    operation.release();
+
+    set_operation_status(ticket, OperationStatus::Cancelled);
+
+    let (market_index, ..) = key;
+
+    record_event(Events::CancelPendingOperation {
+        id: ticket,
+        owner: caller,
+        market_index,
+    });
+
+    true
+}
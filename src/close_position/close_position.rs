@@ -1,12 +1,16 @@
 use crate::close_position::close_position_result::ClosePositionResult;
+use crate::events::event_log::record_event;
+use crate::events::evnts_type::Events;
 
 use crate::close_position::close_position_params::ClosePositionParams;
 use crate::constants::CLOSE_POSITION_PRIORITY_INDEX;
+use crate::house_settings::{is_paused, update_position_fees_acccumulated};
 use crate::pricing_update_management::price_waiting_operation_trait::PriceWaitingOperation;
 use crate::pricing_update_management::price_waiting_operation_utils::put_price_waiting_operation;
 use crate::stable_memory::MARKETS_LIST;
 use crate::user::balance_utils::update_user_balance;
-use crate::user::user_query::get_user_position_details;
+use crate::user::position_util::{_put_user_position_detail, remove_user_position_detail};
+use crate::user::user_query::try_get_user_position_details;
 use ic_cdk::{api::msg_caller, update};
 
 /// Closes an existing trading position in a specific market.
@@ -22,12 +26,15 @@ use ic_cdk::{api::msg_caller, update};
 ///   - `owner` (Principal): The principal ID of the position owner
 ///   - `position_id` (u64): The unique identifier of the position to close
 ///   - `acceptable_price_limit` (u128): Maximum acceptable price for closing (with 20 decimal places precision)
+///   - `size_delta` (Option<u128>): Units of the position to close, `None` closes the whole position
 ///
 /// # Returns
 ///
 /// Returns [`ClosePositionResult`] which can be:
-/// - `Settled { returns }`: Successfully closed position, returns settlement amount
-/// - `Waiting`: Operation queued due to stale price data, will execute when price updates
+/// - `Settled { returns, position_fee }`: Successfully closed position (or the `size_delta` part of it), returns settlement amount
+///   after the position fee (close fee factor of the closed open interest) is taken
+/// - `Waiting { id }`: Operation queued due to stale price data, will execute when price updates,
+///   `id` is the ticket for querying the operation status
 /// - `Failed`: Operation failed due to invalid position or other errors
 ///
 /// # Security Notes
@@ -51,14 +58,16 @@ use ic_cdk::{api::msg_caller, update};
 ///     owner: msg_caller(),
 ///     position_id: 12345,
 ///     acceptable_price_limit: 2000000000000000000000, // 2.0 units max price with 20 decimal places
+///     size_delta: None, // Some(units) to close only part of the position
+///     deadline: None, // fail if still waiting for a price update at this time
 /// };
 ///
 /// let result = close_position(params);
 /// match result {
-///     ClosePositionResult::Settled { returns } => {
+///     ClosePositionResult::Settled { returns, position_fee } => {
 ///         // Successfully closed position, received settlement amount
 ///     },
-///     ClosePositionResult::Waiting => {
+///     ClosePositionResult::Waiting { id } => {
 ///         // Operation queued, will execute when price updates
 ///     },
 ///     ClosePositionResult::Failed => {
@@ -76,15 +85,17 @@ pub fn close_position(params: ClosePositionParams) -> ClosePositionResult {
     );
 
     let result = _close_position(&params);
-    if let ClosePositionResult::Waiting = result {
-        put_price_waiting_operation(
+    if let ClosePositionResult::Waiting { id: _ } = result {
+        let ticket = put_price_waiting_operation(
             params.market_index,
             CLOSE_POSITION_PRIORITY_INDEX,
             PriceWaitingOperation::from(params),
         );
+
+        return ClosePositionResult::Waiting { id: Some(ticket) };
     };
 
-    return result;
+    result
 }
 
 /// Internal implementation of the close position functionality.
@@ -111,26 +122,81 @@ pub fn close_position(params: ClosePositionParams) -> ClosePositionResult {
 /// 2. Retrieves the position details from user records
 /// 3. Checks if market price data is current
 /// 4. If price is stale, returns `Waiting` to queue the operation
-/// 5. If price is current, executes the position closure
-/// 6. Updates user balance with settlement amount on success
+/// 5. If price is current, executes the position closure (of the `size_delta` part for partial closes)
+/// 6. Updates user balance with settlement amount and removes the position on success,
+///    or keeps the remaining part under the same position ID for partial closes
 ///
 /// # Note
 ///
 /// This is an internal function. External callers should use the public `close_position` function
 /// which includes proper caller verification and price waiting operation handling.
 pub fn _close_position(params: &ClosePositionParams) -> ClosePositionResult {
-    let (market_index, position) = get_user_position_details(params.owner, params.position_id);
+    let Some((market_index, position)) =
+        try_get_user_position_details(params.owner, params.position_id)
+    else {
+        return ClosePositionResult::Failed;
+    };
+
+    let (closed_position, remaining_position) = match params.size_delta {
+        Some(size_delta) if size_delta == 0 || size_delta > position.units => {
+            return ClosePositionResult::Failed;
+        }
+        Some(size_delta) if size_delta < position.units => {
+            let (closed_position, remaining_position) = position.split(size_delta);
+            (closed_position, Some(remaining_position))
+        }
+        _ => (position, None),
+    };
+
     MARKETS_LIST.with_borrow_mut(|reference| {
         let mut market = reference.get(market_index).expect("Market does not exist");
 
-        let result = market.close_position_in_market(position, params.acceptable_price_limit);
+        if is_paused() || !market.status.allows_closing() {
+            return ClosePositionResult::Failed;
+        }
+
+        let result =
+            market.close_position_in_market(closed_position, params.acceptable_price_limit);
+
+        if let ClosePositionResult::Settled { returns, .. } = result {
+            // position fee is taken from the returns
+            let position_fee = market
+                .get_position_fee(closed_position.open_interest(), false)
+                .min(returns);
+
+            let treasury_fee = market.collect_position_fee(position_fee);
+            update_position_fees_acccumulated(treasury_fee, true);
 
-        if let ClosePositionResult::Settled { returns } = result {
-            update_user_balance(position.owner, returns, true);
+            update_user_balance(position.owner, returns - position_fee, true);
+
+            match remaining_position {
+                Some(remaining_position) => _put_user_position_detail(
+                    params.owner,
+                    market_index,
+                    params.position_id,
+                    remaining_position,
+                ),
+                None => remove_user_position_detail(params.owner, params.position_id),
+            }
 
             reference.set(market_index, &market);
+
+            record_event(Events::ClosePosition {
+                owner: params.owner,
+                market_index,
+                position_id: params.position_id,
+                units: closed_position.units,
+                price: market.pricing_manager.price,
+                returns: returns - position_fee,
+                position_fee,
+            });
+
+            return ClosePositionResult::Settled {
+                returns: returns - position_fee,
+                position_fee,
+            };
         }
 
-        return result;
+        result
     })
 }
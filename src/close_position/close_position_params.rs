@@ -1,7 +1,8 @@
 use candid::{CandidType, Principal};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::close_position::close_position::_close_position;
+use crate::pricing_update_management::operation_status::OperationStatus;
 use crate::pricing_update_management::price_waiting_operation_trait::{
     PriceWaitingOperation, PriceWaitingOperationTrait,
 };
@@ -10,7 +11,7 @@ use crate::pricing_update_management::price_waiting_operation_trait::{
 ///
 /// This struct contains all the necessary information to close a trading position
 /// in a specific market. The owner must be the message caller to ensure security.
-#[derive(CandidType, Deserialize, Copy, Clone)]
+#[derive(CandidType, Deserialize, Serialize, Copy, Clone)]
 pub struct ClosePositionParams {
     /// The unique identifier of the market containing the position to close.
     /// Must correspond to an existing market in the clearing house.
@@ -34,11 +35,32 @@ pub struct ClosePositionParams {
     /// This provides protection against unfavorable price movements during closure.
     #[serde(rename = "acceptablePriceLimit")]
     pub acceptable_price_limit: u128,
+
+    /// The units of the position to close, for a partial close.
+    /// Collateral, debt and max reserve are reduced proportionally and the rest of the position
+    /// stays open under the same position ID.
+    /// `None` (or the position's full units) closes the whole position.
+    #[serde(rename = "sizeDelta")]
+    pub size_delta: Option<u128>,
+
+    /// Optional deadline for the operation, in nanoseconds since the UNIX epoch.
+    /// If the operation is queued waiting for a price update and is not executed by this time,
+    /// it is failed and its reserved amounts are released.
+    /// Without a deadline, a queued operation fails if the next price update fails.
+    pub deadline: Option<u64>,
 }
 
 impl PriceWaitingOperationTrait for ClosePositionParams {
-    fn execute(&self) {
-        _close_position(&self);
+    fn execute(&self) -> OperationStatus {
+        _close_position(self).into()
+    }
+
+    fn deadline(&self) -> Option<u64> {
+        self.deadline
+    }
+
+    fn executor(&self) -> Option<Principal> {
+        Some(self.owner)
     }
 }
 
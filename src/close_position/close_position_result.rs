@@ -1,9 +1,10 @@
 use candid::CandidType;
 use serde::Deserialize;
 
+#[cfg_attr(test, derive(Debug, PartialEq, Eq))]
 #[derive(CandidType, Deserialize)]
 pub enum ClosePositionResult {
-    Settled { returns: u128 },
-    Waiting,
+    Settled { returns: u128, position_fee: u128 },
+    Waiting { id: Option<u64> }, // operation ticket
     Failed,
 }
@@ -7,6 +7,7 @@
 /// The function is called by the system when a position is closed by the system
 ///
 /// The function is called by the system when a position is closed by the system
+#[allow(clippy::module_inception)]
 pub mod close_position;
 pub mod close_position_params;
 pub mod close_position_result;
@@ -1,25 +1,69 @@
 use ic_stable_structures::memory_manager::MemoryId;
 
 /// Memeory locarions
-pub const _ADMIN_MEMORY_ID: MemoryId = MemoryId::new(1);
+pub const _OWNER_MEMORY_ID: MemoryId = MemoryId::new(1);
 pub const _HOUSE_DETAILS_MEMORY_ID: MemoryId = MemoryId::new(2);
-pub const _MARKETS_MEMORY_ID: MemoryId = MemoryId::new(3);
+pub const _LEGACY_MARKETS_MEMORY_ID: MemoryId = MemoryId::new(3);
 pub const _BALANCES_MEMORY_ID: MemoryId = MemoryId::new(4);
 pub const _MARKET_SHARE_USER_BALANCES_MEMORY_ID: MemoryId = MemoryId::new(5);
 pub const _POSITIONS_MEMORY_ID: MemoryId = MemoryId::new(6);
 pub const _MARKET_LIQUIDTY_SHARES_MEMORY_ID: MemoryId = MemoryId::new(7);
+pub const _PRICE_WAITING_OPERATIONS_MEMORY_ID: MemoryId = MemoryId::new(8);
+pub const _PRICE_WAITING_OPERATIONS_COUNTER_MEMORY_ID: MemoryId = MemoryId::new(9);
+pub const _PRICE_WAITING_OPERATIONS_STATUS_MEMORY_ID: MemoryId = MemoryId::new(10);
+pub const _MARKETS_SETTLEMENT_DETAILS_MEMORY_ID: MemoryId = MemoryId::new(11);
+pub const _MARKET_ORDERS_MEMORY_ID: MemoryId = MemoryId::new(12);
+pub const _ORDERS_COUNTER_MEMORY_ID: MemoryId = MemoryId::new(13);
+pub const _ORDERS_STATUS_MEMORY_ID: MemoryId = MemoryId::new(14);
+pub const _POSITIONS_TRIGGERS_MEMORY_ID: MemoryId = MemoryId::new(15);
+pub const _TWAP_ORDERS_MEMORY_ID: MemoryId = MemoryId::new(16);
+pub const _TWAP_ORDERS_COUNTER_MEMORY_ID: MemoryId = MemoryId::new(17);
+pub const _TREASURY_SWEEPS_MEMORY_ID: MemoryId = MemoryId::new(18);
+pub const _TIMELOCK_QUEUE_MEMORY_ID: MemoryId = MemoryId::new(19);
+pub const _TIMELOCK_COUNTER_MEMORY_ID: MemoryId = MemoryId::new(20);
+pub const _TIMELOCK_DELAY_MEMORY_ID: MemoryId = MemoryId::new(21);
+pub const _PENDING_OWNER_MEMORY_ID: MemoryId = MemoryId::new(22);
+pub const _ROLES_MEMORY_ID: MemoryId = MemoryId::new(23);
+pub const _MARKETS_DELISTINGS_MEMORY_ID: MemoryId = MemoryId::new(24);
+pub const _EVENTS_LOG_INDEX_MEMORY_ID: MemoryId = MemoryId::new(25);
+pub const _EVENTS_LOG_DATA_MEMORY_ID: MemoryId = MemoryId::new(26);
+pub const _PRICE_WAITING_OPERATIONS_KEYS_MEMORY_ID: MemoryId = MemoryId::new(27);
+pub const _ORDERS_MARKETS_MEMORY_ID: MemoryId = MemoryId::new(28);
+pub const _MARKETS_MEMORY_ID: MemoryId = MemoryId::new(29);
+pub const _USERS_ORDERS_MEMORY_ID: MemoryId = MemoryId::new(30);
 
 pub const COLLECT_BORROW_FEES_PRIORITY_INDEX: u8 = 0;
-pub const ADD_LIQUIDITY_PRIORITY_INDEX: u8 = 1;
-pub const CLOSE_POSITION_PRIORITY_INDEX: u8 = 2;
-pub const OPEN_POSITION_PRIORITY_INDEX: u8 = 3;
-pub const REMOVE_LIQUIDITY_PRIORITY_INDEX: u8 = 4;
+pub const LIQUIDATE_POSITION_PRIORITY_INDEX: u8 = 1;
+pub const ADD_LIQUIDITY_PRIORITY_INDEX: u8 = 2;
+pub const CLOSE_POSITION_PRIORITY_INDEX: u8 = 3;
+pub const OPEN_POSITION_PRIORITY_INDEX: u8 = 4;
+pub const REMOVE_LIQUIDITY_PRIORITY_INDEX: u8 = 5;
 
 pub const ONE_HOUR_NANOSECONDS: u64 = 60 * 60 * 1_000_000_000;
 pub const _ONE_SECOND: u64 = 1_000_000_000;
 pub const MAX_ALLOWED_PRICE_CHANGE_INTERVAL: u64 = 600_000_000_000; // 10 minutes 
+pub const PRICE_WAITING_OPERATIONS_EXECUTION_DELAY: u64 = 500_000_000; // 500 milliseconds
+pub const PRICE_WAITING_OPERATIONS_RETRY_INTERVAL: u64 = 30 * _ONE_SECOND;
+pub const DEFAULT_MARKET_SETTLEMENT_INTERVAL: u64 = 60 * 60; // 1 hour in seconds
+pub const MIN_MARKET_SETTLEMENT_INTERVAL: u64 = 60; // 1 minute in seconds
+pub const MAX_TWAP_ORDER_CHILDREN: u64 = 100;
+pub const MIN_TWAP_ORDER_INTERVAL: u64 = 10; // 10 seconds
+pub const DEFAULT_TIMELOCK_DELAY: u64 = 2 * 24 * 60 * 60; // 2 days in seconds
+pub const MIN_TIMELOCK_DELAY: u64 = 60 * 60; // 1 hour in seconds
+pub const MAX_TIMELOCK_DELAY: u64 = 30 * 24 * 60 * 60; // 30 days in seconds
+pub const TIMELOCK_GRACE_PERIOD: u64 = 14 * 24 * 60 * 60; // 14 days in seconds
+pub const MIN_DELISTING_DELAY: u64 = 24 * 60 * 60; // 1 day in seconds
+pub const DELISTING_SETTLEMENT_RETRY_INTERVAL: u64 = 60; // 1 minute in seconds
+pub const DELISTING_SETTLEMENT_BATCH: usize = 100; // entries processed per message of the final settlement
+pub const MAX_EVENTS_QUERY_LIMIT: u64 = 500;
+pub const MAX_RETAINED_STATUSES: u64 = 100_000; // statuses of the latest operations (or orders) kept
+pub const STATUS_PRUNING_BATCH: usize = 10; // statuses checked for pruning every time a status is set
+pub const MAX_ORDERS_PER_USER: usize = 50;
+pub const MAX_ORDERS_PER_POSITION: usize = 5; // limit close orders on the same position
+pub const TRIGGERS_EXECUTION_BATCH: usize = 50; // orders (or position triggers) checked per message after a price update
 
 // collect borow fees
+// liquidate position
 // close positon
 // add liquidity
 // open position
@@ -0,0 +1,62 @@
+use ic_cdk::{api::time, update};
+
+use crate::admin_roles::owner_guard;
+use crate::constants::{_ONE_SECOND, MIN_DELISTING_DELAY};
+use crate::delisting::delisting_details::{
+    DelistingDetails, get_delisting_details, set_delisting_details,
+};
+use crate::delisting::delisting_utils::schedule_final_settlement;
+use crate::events::event_log::record_event;
+use crate::events::evnts_type::Events;
+use crate::market::market_status::MarketStatus;
+use crate::stable_memory::MARKETS_LIST;
+
+/// Delists a market.
+///
+/// The market is moved to close-only until the settlement time, when every open position
+/// is settled at the oracle price (the final settlement price) and the owners' balances are credited,
+/// and the market's orders and active TWAP orders are cancelled releasing their reserved amounts.
+/// The final settlement is run in batches of `DELISTING_SETTLEMENT_BATCH` entries, one per message.
+/// After the final settlement the market rejects all activity and liquidity providers redeem
+/// their shares for the remaining pool value with `redeemDelistedLiquidity`.
+///
+/// # Parameters
+///
+/// * `market_index` (u64): The unique identifier of the market
+/// * `settlement_time` (u64): Time of the final settlement in nanoseconds,
+///   at least `MIN_DELISTING_DELAY` seconds from now to give traders notice
+#[update(name = "delistMarket", guard = "owner_guard")]
+pub fn delist_market(market_index: u64, settlement_time: u64) {
+    assert!(
+        get_delisting_details(market_index).is_none(),
+        "Market is already delisted"
+    );
+    assert!(
+        settlement_time >= time() + MIN_DELISTING_DELAY * _ONE_SECOND,
+        "Settlement time is too early"
+    );
+
+    MARKETS_LIST.with_borrow_mut(|reference| {
+        let mut market = reference.get(market_index).expect("Market does not exist");
+
+        // a paused market stays paused until the final settlement
+        market.status = market.status.max(MarketStatus::CloseOnly);
+
+        reference.set(market_index, &market);
+    });
+
+    set_delisting_details(
+        market_index,
+        DelistingDetails {
+            settlement_time,
+            ..Default::default()
+        },
+    );
+
+    schedule_final_settlement(market_index, settlement_time);
+
+    record_event(Events::DelistMarket {
+        market_index,
+        settlement_time,
+    });
+}
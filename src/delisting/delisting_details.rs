@@ -0,0 +1,85 @@
+use std::borrow::Cow;
+
+use candid::{CandidType, Principal};
+use ic_stable_structures::{Storable, storable::Bound};
+use serde::{Deserialize, Serialize};
+
+use crate::stable_memory::MARKETS_DELISTINGS;
+
+/// Delisting Details
+///
+/// the retirement of a market, close-only until its settlement time
+/// when every open position is settled at the final settlement price
+#[derive(CandidType, Deserialize, Serialize, Clone, Default)]
+pub struct DelistingDetails {
+    /// Time of the final settlement in nanoseconds
+    #[serde(rename = "settlementTime")]
+    pub settlement_time: u64,
+    /// Price every open position is settled at, None until the final settlement starts
+    #[serde(rename = "finalSettlementPrice")]
+    pub final_settlement_price: Option<u128>,
+    /// Number of positions settled at the final settlement
+    #[serde(rename = "settledPositions")]
+    pub settled_positions: u64,
+    /// Number of positions not settled in the current pass over the positions,
+    /// they are settled again in the next pass before the orders are cancelled
+    #[serde(rename = "unsettledPositions")]
+    pub unsettled_positions: u64,
+    /// Time the final settlement was completed, None until then
+    #[serde(rename = "settledAt")]
+    pub settled_at: Option<u64>,
+    /// Progress of the final settlement, which is run in batches over several messages
+    #[serde(rename = "settlementStage")]
+    pub settlement_stage: SettlementStage,
+}
+
+/// Settlement Stage
+///
+/// the step of the final settlement to run next, each with the key to resume from
+#[derive(CandidType, Deserialize, Serialize, Clone, Copy)]
+pub enum SettlementStage {
+    /// Settling the open positions of the market, scanning the users' positions
+    Positions { next: Option<(Principal, u64)> },
+    /// Cancelling the orders in the market's order book
+    Orders,
+    /// Cancelling the active TWAP orders in the market, scanning all TWAP orders
+    TwapOrders { next: Option<u64> },
+}
+
+impl Default for SettlementStage {
+    fn default() -> Self {
+        SettlementStage::Positions { next: None }
+    }
+}
+
+pub fn get_delisting_details(market_index: u64) -> Option<DelistingDetails> {
+    MARKETS_DELISTINGS.with_borrow(|reference| reference.get(&market_index))
+}
+
+pub fn set_delisting_details(market_index: u64, details: DelistingDetails) {
+    MARKETS_DELISTINGS.with_borrow_mut(|reference| {
+        reference.insert(market_index, details);
+    });
+}
+
+impl Storable for DelistingDetails {
+    fn to_bytes(&self) -> Cow<'_, [u8]> {
+        let serialized = bincode::serialize(self).expect("failed to serialize");
+        Cow::Owned(serialized)
+    }
+
+    /// Converts the element into an owned byte vector.
+    ///
+    /// This method consumes `self` and avoids cloning when possible.
+    fn into_bytes(self) -> Vec<u8> {
+        bincode::serialize(&self).expect("failed to serialize")
+    }
+
+    /// Converts bytes into an element.
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        bincode::deserialize(bytes.as_ref()).expect("failed to desearalize")
+    }
+
+    /// The size bounds of the type.
+    const BOUND: Bound = Bound::Unbounded;
+}
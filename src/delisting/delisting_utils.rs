@@ -0,0 +1,415 @@
+use std::time::Duration;
+
+use candid::Principal;
+use ic_cdk::api::time;
+
+use crate::close_position::close_position_result::ClosePositionResult;
+use crate::constants::{
+    _ONE_SECOND, DELISTING_SETTLEMENT_BATCH, DELISTING_SETTLEMENT_RETRY_INTERVAL,
+};
+use crate::delisting::delisting_details::{
+    SettlementStage, get_delisting_details, set_delisting_details,
+};
+use crate::events::event_log::record_event;
+use crate::events::evnts_type::Events;
+use crate::house_settings::is_paused;
+use crate::market::market_status::MarketStatus;
+use crate::order_management::order::Order;
+use crate::order_management::order_utils::{remove_order, set_order_status};
+use crate::order_management::twap_order::{TwapOrder, TwapOrderStatus};
+use crate::order_management::twap_order_utils::{clear_twap_order_timer, set_twap_order};
+use crate::position::position_details::PositionDetails;
+use crate::pricing_update_management::operation_status::OperationStatus;
+use crate::pricing_update_management::price_fetch::update_price;
+use crate::pricing_update_management::price_waiting_operation_trait::PriceWaitingOperationTrait;
+use crate::settlement_management::settlement_timer_utils::start_market_settlement_timer;
+use crate::stable_memory::{
+    MARKET_DELISTING_TIMERS, MARKET_ORDERS, MARKETS_DELISTINGS, MARKETS_LIST, TWAP_ORDERS,
+    USERS_POSITIONS,
+};
+use crate::user::balance_utils::update_user_balance;
+use crate::user::position_util::remove_user_position_detail;
+
+/// Rearm Delisting Timers
+///
+/// schedules the final settlement of every market being delisted,
+/// settlements whose time passed during the upgrade are run immediately
+/// @dev called after an upgrade since timers do not persist
+pub fn rearm_delisting_timers() {
+    let current_time = time();
+
+    let delistings: Vec<(u64, u64)> = MARKETS_DELISTINGS.with_borrow(|reference| {
+        reference
+            .iter()
+            .map(|entry| entry.into_pair())
+            .filter(|(_, details)| details.settled_at.is_none())
+            .map(|(market_index, details)| (market_index, details.settlement_time))
+            .collect()
+    });
+
+    for (market_index, settlement_time) in delistings {
+        _set_delisting_timer(market_index, settlement_time.saturating_sub(current_time));
+    }
+}
+
+/// Schedule Final Settlement
+///
+/// runs the final settlement of a market at its settlement time
+pub fn schedule_final_settlement(market_index: u64, settlement_time: u64) {
+    _set_delisting_timer(market_index, settlement_time.saturating_sub(time()));
+}
+
+/// Run Final Settlement
+///
+/// updates the price of a market being delisted, fixes it as the final settlement price
+/// and runs the next batch of the final settlement, re-armed immediately until it is completed,
+/// retried later if the price can not be updated or the protocol is paused
+pub async fn run_final_settlement(market_index: u64) {
+    MARKET_DELISTING_TIMERS.with_borrow_mut(|reference| {
+        reference.remove(&market_index);
+    });
+
+    // retried later if the price update or a batch fails
+    _set_delisting_timer(
+        market_index,
+        DELISTING_SETTLEMENT_RETRY_INTERVAL * _ONE_SECOND,
+    );
+
+    if is_paused() {
+        return;
+    }
+
+    let Some(details) = get_delisting_details(market_index) else {
+        clear_delisting_timer(market_index);
+        return;
+    };
+
+    if details.settled_at.is_some() {
+        clear_delisting_timer(market_index);
+        return;
+    }
+
+    if details.final_settlement_price.is_none() {
+        update_price(market_index).await;
+
+        if !_fix_final_settlement_price(market_index) {
+            return;
+        }
+    }
+
+    match _settle_delisted_market_batch(market_index) {
+        SettlementProgress::Completed => clear_delisting_timer(market_index),
+        // the next batch is run in its own message
+        SettlementProgress::Pending => _set_delisting_timer(market_index, 0),
+        // the positions not settled are retried at the retry interval
+        SettlementProgress::Retry => {}
+    }
+}
+
+/// Settlement Progress
+///
+/// the progress of the final settlement after a batch
+#[cfg_attr(test, derive(Debug, PartialEq, Eq))]
+pub enum SettlementProgress {
+    Completed,
+    /// The next batch can be run
+    Pending,
+    /// Some positions could not be settled, the positions are settled again from the first one
+    Retry,
+}
+
+pub fn clear_delisting_timer(market_index: u64) {
+    MARKET_DELISTING_TIMERS.with_borrow_mut(|reference| {
+        if let Some(timer_id) = reference.remove(&market_index) {
+            ic_cdk_timers::clear_timer(timer_id);
+        }
+    });
+}
+
+/// fixes the current price of the market as its final settlement price and marks the market delisted,
+/// so no position or liquidity changes happen while the settlement is run
+///
+/// Returns false if the market price is not current
+fn _fix_final_settlement_price(market_index: u64) -> bool {
+    let Some(mut details) = get_delisting_details(market_index) else {
+        return false;
+    };
+
+    let price = MARKETS_LIST.with_borrow_mut(|reference| {
+        let mut market = reference.get(market_index).expect("Market does not exist");
+
+        let price = market.pricing_manager.get_price()?;
+
+        market.status = MarketStatus::Delisted;
+
+        reference.set(market_index, &market);
+
+        Some(price)
+    });
+
+    let Some(price) = price else {
+        return false;
+    };
+
+    details.final_settlement_price = Some(price);
+
+    set_delisting_details(market_index, details);
+
+    true
+}
+
+/// runs one batch of the final settlement of a market:
+/// settles its open positions at the final settlement price,
+/// then cancels its orders and its active TWAP orders releasing their reserved amounts,
+/// the orders are only cancelled once every position was settled
+pub fn _settle_delisted_market_batch(market_index: u64) -> SettlementProgress {
+    let Some(mut details) = get_delisting_details(market_index) else {
+        return SettlementProgress::Completed;
+    };
+
+    let Some(price) = details.final_settlement_price else {
+        return SettlementProgress::Retry;
+    };
+
+    let mut retry = false;
+
+    details.settlement_stage = match details.settlement_stage {
+        SettlementStage::Positions { next } => {
+            _accrue_delisted_market_fees(market_index);
+
+            let (settled_positions, unsettled_positions, next) =
+                _settle_delisted_positions(market_index, price, next);
+
+            details.settled_positions += settled_positions;
+            details.unsettled_positions += unsettled_positions;
+
+            match next {
+                Some(next) => SettlementStage::Positions { next: Some(next) },
+                None if details.unsettled_positions > 0 => {
+                    details.unsettled_positions = 0;
+                    retry = true;
+                    SettlementStage::Positions { next: None }
+                }
+                None => SettlementStage::Orders,
+            }
+        }
+        SettlementStage::Orders => {
+            if _cancel_delisted_orders(market_index) {
+                SettlementStage::TwapOrders { next: None }
+            } else {
+                SettlementStage::Orders
+            }
+        }
+        SettlementStage::TwapOrders { next } => {
+            match _cancel_delisted_twap_orders(market_index, next) {
+                Some(next) => SettlementStage::TwapOrders { next: Some(next) },
+                None => {
+                    details.settled_at = Some(time());
+                    SettlementStage::TwapOrders { next: None }
+                }
+            }
+        }
+    };
+
+    let completed = details.settled_at.is_some();
+
+    if completed {
+        record_event(Events::SettleDelistedMarket {
+            market_index,
+            price,
+            settled_positions: details.settled_positions,
+        });
+
+        // funding and borrowing fees are no longer settled once the market has no positions
+        start_market_settlement_timer(market_index, 0);
+    }
+
+    set_delisting_details(market_index, details);
+
+    if completed {
+        SettlementProgress::Completed
+    } else if retry {
+        SettlementProgress::Retry
+    } else {
+        SettlementProgress::Pending
+    }
+}
+
+fn _accrue_delisted_market_fees(market_index: u64) {
+    MARKETS_LIST.with_borrow_mut(|reference| {
+        let mut market = reference.get(market_index).expect("Market does not exist");
+
+        market.accrue_funding_and_borrowing_fees();
+
+        reference.set(market_index, &market);
+    });
+}
+
+/// settles the market's positions among the next `DELISTING_SETTLEMENT_BATCH` users' positions
+/// starting from `from` (all markets' positions are scanned),
+/// positions that are not settled are kept to be settled again in the next pass
+/// @dev the fees of the market are accrued before the batch
+///
+/// Returns the number of positions settled and not settled
+/// and the key to resume from, None once every position was scanned
+pub fn _settle_delisted_positions(
+    market_index: u64,
+    price: u128,
+    from: Option<(Principal, u64)>,
+) -> (u64, u64, Option<(Principal, u64)>) {
+    let start = from.unwrap_or((Principal::management_canister(), 0));
+
+    let (entries, next) = USERS_POSITIONS.with_borrow(|reference| {
+        let mut entries: Vec<((Principal, u64), (u64, PositionDetails))> = reference
+            .range(start..)
+            .take(DELISTING_SETTLEMENT_BATCH + 1)
+            .map(|entry| entry.into_pair())
+            .collect();
+
+        let next = (entries.len() > DELISTING_SETTLEMENT_BATCH)
+            .then(|| entries.pop().map(|(key, _)| key))
+            .flatten();
+
+        let entries = entries
+            .into_iter()
+            .filter(|(_, (position_market_index, _))| *position_market_index == market_index)
+            .map(|((owner, position_id), (_, position))| (owner, position_id, position))
+            .collect::<Vec<_>>();
+
+        (entries, next)
+    });
+
+    let settled_positions = MARKETS_LIST.with_borrow_mut(|reference| {
+        let mut market = reference.get(market_index).expect("Market does not exist");
+
+        let mut settled_positions = 0;
+
+        for (owner, position_id, position) in &entries {
+            // positions are settled at the final settlement price without price impact or position fee
+            let ClosePositionResult::Settled { returns, .. } =
+                market._close_position_at_execution_price(*position, price)
+            else {
+                continue;
+            };
+
+            update_user_balance(*owner, returns, true);
+
+            remove_user_position_detail(*owner, *position_id);
+
+            record_event(Events::ClosePosition {
+                owner: *owner,
+                market_index,
+                position_id: *position_id,
+                units: position.units,
+                price,
+                returns,
+                position_fee: 0,
+            });
+
+            settled_positions += 1;
+        }
+
+        reference.set(market_index, &market);
+
+        settled_positions
+    });
+
+    (
+        settled_positions,
+        entries.len() as u64 - settled_positions,
+        next,
+    )
+}
+
+/// cancels up to `DELISTING_SETTLEMENT_BATCH` orders of the market's order book
+///
+/// Returns true once the order book is empty
+fn _cancel_delisted_orders(market_index: u64) -> bool {
+    let orders: Vec<((u64, u64), Order)> = MARKET_ORDERS.with_borrow(|reference| {
+        reference
+            .range((market_index, 0)..=(market_index, u64::MAX))
+            .take(DELISTING_SETTLEMENT_BATCH + 1)
+            .map(|entry| entry.into_pair())
+            .collect()
+    });
+
+    let completed = orders.len() <= DELISTING_SETTLEMENT_BATCH;
+
+    for (key, order) in orders.into_iter().take(DELISTING_SETTLEMENT_BATCH) {
+        let (_, order_id) = key;
+
+        remove_order(key);
+
+        order.release();
+
+        set_order_status(order_id, OperationStatus::Cancelled);
+
+        record_event(Events::CancelOrder {
+            order_id,
+            owner: order.owner(),
+            market_index,
+        });
+    }
+
+    completed
+}
+
+/// cancels the market's active TWAP orders among the next `DELISTING_SETTLEMENT_BATCH` TWAP orders
+/// starting from `from` (all TWAP orders are scanned)
+///
+/// Returns the order id to resume from, None once every TWAP order was scanned
+fn _cancel_delisted_twap_orders(market_index: u64, from: Option<u64>) -> Option<u64> {
+    let (orders, next): (Vec<(u64, TwapOrder)>, Option<u64>) =
+        TWAP_ORDERS.with_borrow(|reference| {
+            let mut orders: Vec<(u64, TwapOrder)> = reference
+                .range(from.unwrap_or_default()..)
+                .take(DELISTING_SETTLEMENT_BATCH + 1)
+                .map(|entry| entry.into_pair())
+                .collect();
+
+            let next = (orders.len() > DELISTING_SETTLEMENT_BATCH)
+                .then(|| orders.pop().map(|(order_id, _)| order_id))
+                .flatten();
+
+            let orders = orders
+                .into_iter()
+                .filter(|(_, order)| {
+                    order.status == TwapOrderStatus::Active && order.market_index() == market_index
+                })
+                .collect();
+
+            (orders, next)
+        });
+
+    for (order_id, mut order) in orders {
+        clear_twap_order_timer(order_id);
+
+        update_user_balance(order.owner(), order.reserved_collateral(), true);
+
+        order.status = TwapOrderStatus::Cancelled;
+
+        record_event(Events::CancelTwapOrder {
+            order_id,
+            owner: order.owner(),
+            market_index,
+        });
+
+        set_twap_order(order_id, order);
+    }
+
+    next
+}
+
+fn _set_delisting_timer(market_index: u64, delay: u64) {
+    let new_timer = ic_cdk_timers::set_timer(Duration::from_nanos(delay), move || {
+        ic_cdk::futures::spawn(async move {
+            run_final_settlement(market_index).await;
+        });
+    });
+
+    MARKET_DELISTING_TIMERS.with_borrow_mut(|reference| {
+        if let Some(timer_id) = reference.insert(market_index, new_timer) {
+            ic_cdk_timers::clear_timer(timer_id);
+        }
+    });
+}
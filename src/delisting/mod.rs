@@ -0,0 +1,4 @@
+pub mod delist_market;
+pub mod delisting_details;
+pub mod delisting_utils;
+pub mod redeem_delisted_liquidity;
@@ -0,0 +1,77 @@
+use ic_cdk::{api::msg_caller, update};
+
+use crate::delisting::delisting_details::get_delisting_details;
+use crate::events::event_log::record_event;
+use crate::events::evnts_type::Events;
+use crate::house_settings::is_paused;
+use crate::market::functions::remove_liquidity::RemoveLiquidityFromMarketParams;
+use crate::market::market_details::LiquidityOperationResult;
+use crate::stable_memory::MARKETS_LIST;
+use crate::user::balance_utils::{
+    get_user_market_liquidity_shares, set_user_market_liquidity_shares, update_user_balance,
+};
+
+/// Redeems all the caller's liquidity shares of a delisted market for their share of the remaining pool value.
+///
+/// # Parameters
+///
+/// * `market_index` (u64): The unique identifier of the delisted market
+///
+/// # Returns
+///
+/// Returns [`LiquidityOperationResult`] which can be:
+/// - `Settled { amount_out }`: Shares were redeemed, `amount_out` was credited to the caller's balance
+/// - `Failed`: The market has not been settled yet or the caller has no shares
+///
+/// # Security Notes
+///
+/// - **No Execution Fee**: Redemptions are not charged the execution fee
+#[update(name = "redeemDelistedLiquidity")]
+pub fn redeem_delisted_liquidity(market_index: u64) -> LiquidityOperationResult {
+    assert!(!is_paused(), "Protocol is paused");
+
+    let owner = msg_caller();
+
+    let Some(final_settlement_price) = get_delisting_details(market_index)
+        .filter(|details| details.settled_at.is_some())
+        .and_then(|details| details.final_settlement_price)
+    else {
+        return LiquidityOperationResult::Failed("Market has not been settled".to_string());
+    };
+
+    let user_shares_balance = get_user_market_liquidity_shares(owner, market_index);
+
+    if user_shares_balance == 0 {
+        return LiquidityOperationResult::Failed("User shares balance is zero".to_string());
+    }
+
+    MARKETS_LIST.with_borrow_mut(|reference| {
+        let mut market = reference.get(market_index).expect("Market does not exist");
+
+        // with every position settled the pool value is the same at any price
+        let result = market._remove_liquidity_from_market_with_price(
+            RemoveLiquidityFromMarketParams {
+                amount_in: user_shares_balance,
+                min_amount_out: 0,
+            },
+            Some(final_settlement_price),
+        );
+
+        if let LiquidityOperationResult::Settled { amount_out } = result {
+            set_user_market_liquidity_shares(owner, market_index, 0);
+
+            update_user_balance(owner, amount_out, true);
+
+            reference.set(market_index, &market);
+
+            record_event(Events::RedeemDelistedLiquidity {
+                owner,
+                market_index,
+                shares: user_shares_balance,
+                amount_out,
+            });
+        }
+
+        result
+    })
+}
@@ -1,7 +1,12 @@
-use ic_cdk::{api::msg_caller, update};
+use candid::Principal;
+use ic_cdk::{api::msg_caller, query, update};
+use icrc_ledger_types::icrc1::account::Account;
 
 use crate::{
-    deposit::deposit_params::DepositParams, house_settings::get_house_asset_ledger,
+    asset_management::functions::user_deposit_account,
+    deposit::deposit_params::DepositParams,
+    events::{event_log::record_event, evnts_type::Events},
+    house_settings::get_house_asset_ledger,
     user::balance_utils::update_user_balance,
 };
 
@@ -36,6 +41,14 @@ use crate::{
 /// 3. Sends the deposit transaction to the ledger
 /// 4. Updates user balance only if the transaction succeeds
 ///
+/// # Ledger Types
+///
+/// - **ICRC / CKBTC**: The amount is pulled from the caller's account, which must have approved the canister
+/// - **ICRC1 / ICP**: The amount (plus the ledger fee) must first be sent to the caller's deposit account
+///   (see [`get_deposit_account`]), it is then moved into the canister's main account
+///
+/// The credited amount is truncated to the precision of the house asset ledger
+///
 
 #[update(name = "depositIntoAccount")]
 pub async fn deposit_into_account(params: DepositParams) -> bool {
@@ -43,11 +56,34 @@ pub async fn deposit_into_account(params: DepositParams) -> bool {
 
     let house_asset_ledger = get_house_asset_ledger();
 
-    let tx_result = house_asset_ledger._send_in(params.amount, user).await;
+    // amounts smaller than one ledger unit can not be transferred
+    let amount =
+        house_asset_ledger.from_ledger_amount(house_asset_ledger.to_ledger_amount(params.amount));
+
+    let tx_result = house_asset_ledger._send_in(amount, user).await;
 
     if tx_result {
-        update_user_balance(user, params.amount, true);
+        update_user_balance(user, amount, true);
+
+        record_event(Events::Deposit { user, amount });
     }
 
     tx_result
 }
+
+/// Gets the deposit account of a user.
+///
+/// For house asset ledgers without ICRC-2 approvals (ICRC1 and ICP), users transfer
+/// the amount to deposit into this account before calling `depositIntoAccount`.
+///
+/// # Parameters
+///
+/// * `user` - The principal ID of the user
+///
+/// # Returns
+///
+/// Returns the ICRC-1 [`Account`] owned by the canister with the user's deposit subaccount
+#[query(name = "getDepositAccount")]
+pub fn get_deposit_account(user: Principal) -> Account {
+    user_deposit_account(&user)
+}
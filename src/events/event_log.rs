@@ -0,0 +1,24 @@
+use crate::events::evnts_type::{EventRecord, Events};
+use crate::stable_memory::EVENTS_LOG;
+
+/// Record Event
+///
+/// appends an event to the event log
+///
+/// Returns the sequence number of the event
+pub fn record_event(event: Events) -> u64 {
+    // the caller and time are only available in a canister, events recorded by unit tests have neither
+    #[cfg(not(test))]
+    let (caller, time) = (ic_cdk::api::msg_caller(), ic_cdk::api::time());
+    #[cfg(test)]
+    let (caller, time) = (candid::Principal::anonymous(), 0);
+
+    let record = EventRecord {
+        caller,
+        time,
+        event,
+    };
+
+    EVENTS_LOG
+        .with_borrow_mut(|reference| reference.append(&record).expect("failed to append event"))
+}
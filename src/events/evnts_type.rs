@@ -1 +1,333 @@
+use std::borrow::Cow;
 
+use candid::{CandidType, Principal};
+use ic_stable_structures::{Storable, storable::Bound};
+use icrc_ledger_types::icrc1::account::Account;
+use serde::{Deserialize, Serialize};
+
+use crate::admin_roles::roles::Role;
+use crate::market::market_config::MarketConfig;
+use crate::market::market_status::MarketStatus;
+use crate::order_management::order::Order;
+use crate::order_management::position_triggers::PositionTriggers;
+use crate::order_management::twap_order::TwapOrder;
+use crate::position::position_details::PositionDetails;
+use crate::timelock::timelocked_change::TimelockedChange;
+
+/// Events
+///
+/// every state-changing action, prices are the last oracle price of the market
+/// and amounts are in quote asset or shares (20-decimal precision)
+#[derive(CandidType, Deserialize, Serialize, Clone)]
+pub enum Events {
+    Deposit {
+        user: Principal,
+        amount: u128,
+    },
+    Withdraw {
+        user: Principal,
+        amount: u128,
+    },
+    OpenPosition {
+        owner: Principal,
+        market_index: u64,
+        position_id: u64,
+        position: PositionDetails,
+        price: u128,
+        position_fee: u128,
+    },
+    IncreasePosition {
+        owner: Principal,
+        market_index: u64,
+        position_id: u64,
+        collateral: u128,
+        position: PositionDetails,
+        price: u128,
+        position_fee: u128,
+    },
+    /// Emitted for partial and full closes, and for every position settled on delisting
+    ClosePosition {
+        owner: Principal,
+        market_index: u64,
+        position_id: u64,
+        units: u128,
+        price: u128,
+        returns: u128,
+        position_fee: u128,
+    },
+    LiquidatePosition {
+        liquidator: Principal,
+        owner: Principal,
+        market_index: u64,
+        position_id: u64,
+        price: u128,
+        liquidator_reward: u128,
+        liquidation_fee: u128,
+        returns: u128,
+    },
+    AddCollateral {
+        owner: Principal,
+        market_index: u64,
+        position_id: u64,
+        amount: u128,
+    },
+    RemoveCollateral {
+        owner: Principal,
+        market_index: u64,
+        position_id: u64,
+        amount: u128,
+    },
+    AddedLiquidity {
+        depositor: Principal,
+        market_index: u64,
+        amount: u128,
+        price: u128,
+        shares: u128,
+    },
+    RemovedLiquidity {
+        owner: Principal,
+        market_index: u64,
+        shares: u128,
+        price: u128,
+        amount_out: u128,
+    },
+    CreateOrder {
+        order_id: u64,
+        order: Order,
+    },
+    CancelOrder {
+        order_id: u64,
+        owner: Principal,
+        market_index: u64,
+    },
+    CreateTwapOrder {
+        order_id: u64,
+        order: TwapOrder,
+    },
+    CancelTwapOrder {
+        order_id: u64,
+        owner: Principal,
+        market_index: u64,
+    },
+    /// None when the triggers were cleared
+    SetPositionTriggers {
+        owner: Principal,
+        market_index: u64,
+        position_id: u64,
+        triggers: Option<PositionTriggers>,
+    },
+    CancelPendingOperation {
+        id: u64,
+        owner: Principal,
+        market_index: u64,
+    },
+    SettleFundingFees {
+        market_index: u64,
+        price: u128,
+    },
+    CollectBorrowFees {
+        market_index: u64,
+        price: u128,
+    },
+    /// Recurring settlement of both funding and borrowing fees
+    SettleMarketFees {
+        market_index: u64,
+        price: u128,
+    },
+    CreateMarket {
+        market_index: u64,
+        config: MarketConfig,
+    },
+    QueueChange {
+        change_id: u64,
+        change: TimelockedChange,
+        eta: u64,
+    },
+    ExecuteChange {
+        change_id: u64,
+        change: TimelockedChange,
+    },
+    CancelChange {
+        change_id: u64,
+    },
+    GrantRole {
+        principal: Principal,
+        role: Role,
+    },
+    RevokeRole {
+        principal: Principal,
+        role: Role,
+    },
+    ProposeOwner {
+        new_owner: Principal,
+    },
+    AcceptOwnership {
+        owner: Principal,
+    },
+    SetProtocolPaused {
+        paused: bool,
+    },
+    SetMarketStatus {
+        market_index: u64,
+        status: MarketStatus,
+    },
+    SetSettlementInterval {
+        market_index: u64,
+        interval_in_secs: u64,
+    },
+    SetTreasuryAccount {
+        account: Account,
+    },
+    SweepTreasuryFees {
+        sweep_id: u64,
+        account: Account,
+        execution_fees: u128,
+        position_fees: u128,
+    },
+    DelistMarket {
+        market_index: u64,
+        settlement_time: u64,
+    },
+    SettleDelistedMarket {
+        market_index: u64,
+        price: u128,
+        settled_positions: u64,
+    },
+    RedeemDelistedLiquidity {
+        owner: Principal,
+        market_index: u64,
+        shares: u128,
+        amount_out: u128,
+    },
+}
+
+impl Events {
+    /// Principal
+    ///
+    /// the user (trader, liquidity provider or granted principal) the event is about,
+    /// None for events about the market or the protocol
+    pub fn principal(&self) -> Option<Principal> {
+        match self {
+            Events::Deposit { user, .. } | Events::Withdraw { user, .. } => Some(*user),
+            Events::OpenPosition { owner, .. }
+            | Events::IncreasePosition { owner, .. }
+            | Events::ClosePosition { owner, .. }
+            | Events::LiquidatePosition { owner, .. }
+            | Events::AddCollateral { owner, .. }
+            | Events::RemoveCollateral { owner, .. }
+            | Events::RemovedLiquidity { owner, .. }
+            | Events::CancelOrder { owner, .. }
+            | Events::CancelTwapOrder { owner, .. }
+            | Events::SetPositionTriggers { owner, .. }
+            | Events::CancelPendingOperation { owner, .. }
+            | Events::RedeemDelistedLiquidity { owner, .. }
+            | Events::AcceptOwnership { owner } => Some(*owner),
+            Events::AddedLiquidity { depositor, .. } => Some(*depositor),
+            Events::CreateOrder { order, .. } => Some(order.owner()),
+            Events::CreateTwapOrder { order, .. } => Some(order.owner()),
+            Events::GrantRole { principal, .. } | Events::RevokeRole { principal, .. } => {
+                Some(*principal)
+            }
+            Events::ProposeOwner { new_owner } => Some(*new_owner),
+            _ => None,
+        }
+    }
+
+    /// Market Index
+    ///
+    /// the market the event is about, None for events about users' accounts or the protocol
+    pub fn market_index(&self) -> Option<u64> {
+        match self {
+            Events::OpenPosition { market_index, .. }
+            | Events::IncreasePosition { market_index, .. }
+            | Events::ClosePosition { market_index, .. }
+            | Events::LiquidatePosition { market_index, .. }
+            | Events::AddCollateral { market_index, .. }
+            | Events::RemoveCollateral { market_index, .. }
+            | Events::AddedLiquidity { market_index, .. }
+            | Events::RemovedLiquidity { market_index, .. }
+            | Events::CancelOrder { market_index, .. }
+            | Events::CancelTwapOrder { market_index, .. }
+            | Events::SetPositionTriggers { market_index, .. }
+            | Events::CancelPendingOperation { market_index, .. }
+            | Events::SettleFundingFees { market_index, .. }
+            | Events::CollectBorrowFees { market_index, .. }
+            | Events::SettleMarketFees { market_index, .. }
+            | Events::CreateMarket { market_index, .. }
+            | Events::SetMarketStatus { market_index, .. }
+            | Events::SetSettlementInterval { market_index, .. }
+            | Events::DelistMarket { market_index, .. }
+            | Events::SettleDelistedMarket { market_index, .. }
+            | Events::RedeemDelistedLiquidity { market_index, .. } => Some(*market_index),
+            Events::CreateOrder { order, .. } => Some(order.market_index()),
+            Events::CreateTwapOrder { order, .. } => Some(order.market_index()),
+            Events::QueueChange { change, .. } | Events::ExecuteChange { change, .. } => {
+                match change {
+                    TimelockedChange::UpdateMarketConfig { market_index, .. } => {
+                        Some(*market_index)
+                    }
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Event Record
+///
+/// an entry of the event log, its sequence number is its index in the log
+#[derive(CandidType, Deserialize, Serialize, Clone)]
+pub struct EventRecord {
+    /// Principal that called the endpoint, the canister itself for actions run by timers
+    pub caller: Principal,
+    pub time: u64,
+    pub event: Events,
+}
+
+/// Event Filter
+///
+/// events matching every field set are returned
+#[derive(CandidType, Deserialize, Clone, Default)]
+pub struct EventFilter {
+    /// Matches the caller or the user the event is about
+    pub principal: Option<Principal>,
+    #[serde(rename = "marketIndex")]
+    pub market_index: Option<u64>,
+}
+
+impl EventFilter {
+    pub fn matches(&self, record: &EventRecord) -> bool {
+        let principal_matches = self.principal.is_none_or(|principal| {
+            record.caller == principal || record.event.principal() == Some(principal)
+        });
+
+        let market_matches = self
+            .market_index
+            .is_none_or(|market_index| record.event.market_index() == Some(market_index));
+
+        principal_matches && market_matches
+    }
+}
+
+impl Storable for EventRecord {
+    fn to_bytes(&self) -> Cow<'_, [u8]> {
+        let serialized = bincode::serialize(self).expect("failed to serialize");
+        Cow::Owned(serialized)
+    }
+
+    /// Converts the element into an owned byte vector.
+    ///
+    /// This method consumes `self` and avoids cloning when possible.
+    fn into_bytes(self) -> Vec<u8> {
+        bincode::serialize(&self).expect("failed to serialize")
+    }
+
+    /// Converts bytes into an element.
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        bincode::deserialize(bytes.as_ref()).expect("failed to desearalize")
+    }
+
+    /// The size bounds of the type.
+    const BOUND: Bound = Bound::Unbounded;
+}
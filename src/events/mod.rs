@@ -1,12 +1,2 @@
-use candid::CandidType;
-use serde::Deserialize;
-
-#[derive(CandidType, Deserialize)]
-pub enum Events {
-    OpenPosition,
-    ClosePosition,
-    AddedLiquidity,
-    RemovedLiquidity,
-    CreateOrder,
-    CancelOrder,
-}
+pub mod event_log;
+pub mod evnts_type;
@@ -3,19 +3,24 @@ use std::borrow::Cow;
 use candid::CandidType;
 use ic_cdk::query;
 use ic_stable_structures::{Storable, storable::Bound};
+use icrc_ledger_types::icrc1::account::Account;
 use serde::{Deserialize, Serialize};
 
 use crate::asset_management::asset_management::AssetLedger;
 use crate::pricing_update_management::price_fetch::AssetPricingDetails;
 use crate::stable_memory::HOUSE_SETTINGS;
 
-#[derive(Serialize, Deserialize, CandidType, Clone)]
+#[derive(Serialize, Deserialize, CandidType, Clone, Default)]
 pub struct HouseDetails {
     pub house_asset_ledger: AssetLedger,
     pub house_asset_pricing_details: AssetPricingDetails,
     pub execution_fee: u128,
     pub execution_fees_accumulated: u128,
     pub position_fees_acccumulated: u128,
+    /// Account the accumulated execution and position fees are swept to
+    pub treasury_account: Option<Account>,
+    /// Global pause, rejects opening and closing positions, adding and removing liquidity and withdrawals in every market
+    pub paused: bool,
 }
 
 #[query(name = "getHouseDetails")]
@@ -65,6 +70,41 @@ pub fn update_execution_fees_accumulated(amount: u128, add: bool) {
     });
 }
 
+pub fn set_execution_fee(execution_fee: u128) {
+    HOUSE_SETTINGS.with_borrow_mut(|reference| {
+        reference.set(HouseDetails {
+            execution_fee,
+            ..reference.get().clone()
+        });
+    });
+}
+
+pub fn is_paused() -> bool {
+    HOUSE_SETTINGS.with_borrow(|reference| reference.get().paused)
+}
+
+pub fn set_paused(paused: bool) {
+    HOUSE_SETTINGS.with_borrow_mut(|reference| {
+        reference.set(HouseDetails {
+            paused,
+            ..reference.get().clone()
+        });
+    });
+}
+
+pub fn get_treasury_account() -> Option<Account> {
+    HOUSE_SETTINGS.with_borrow(|reference| reference.get().treasury_account)
+}
+
+pub fn set_treasury_account(treasury_account: Account) {
+    HOUSE_SETTINGS.with_borrow_mut(|reference| {
+        reference.set(HouseDetails {
+            treasury_account: Some(treasury_account),
+            ..reference.get().clone()
+        });
+    });
+}
+
 pub fn get_house_asset_ledger() -> AssetLedger {
     HOUSE_SETTINGS.with_borrow(|reference| reference.get().house_asset_ledger)
 }
@@ -73,18 +113,6 @@ pub fn get_house_asset_pricing_details() -> AssetPricingDetails {
     HOUSE_SETTINGS.with_borrow(|reference| reference.get().house_asset_pricing_details.clone())
 }
 
-impl Default for HouseDetails {
-    fn default() -> Self {
-        Self {
-            position_fees_acccumulated: 0,
-            house_asset_pricing_details: AssetPricingDetails::default(),
-            execution_fee: 0,
-            execution_fees_accumulated: 0,
-            house_asset_ledger: AssetLedger::default(),
-        }
-    }
-}
-
 impl Storable for HouseDetails {
     fn to_bytes(&self) -> Cow<'_, [u8]> {
         let serialized = bincode::serialize(self).expect("failed to serialize");
@@ -99,10 +127,53 @@ impl Storable for HouseDetails {
     }
 
     /// Converts bytes into an element.
+    ///
+    /// @dev falls back to the legacy layout for house details stored before the treasury account and pause were added
     fn from_bytes(bytes: Cow<[u8]>) -> Self {
-        bincode::deserialize(bytes.as_ref()).expect("failed to desearalize")
+        bincode::deserialize(bytes.as_ref()).unwrap_or_else(|_| {
+            let legacy: LegacyHouseDetails =
+                bincode::deserialize(bytes.as_ref()).expect("failed to desearalize");
+            HouseDetails::from(legacy)
+        })
     }
 
     /// The size bounds of the type.
     const BOUND: Bound = Bound::Unbounded;
 }
+
+/// Legacy House Details
+///
+/// HouseDetails before the treasury account and the global pause were added
+#[derive(Serialize, Deserialize)]
+pub struct LegacyHouseDetails {
+    pub house_asset_ledger: AssetLedger,
+    pub house_asset_pricing_details: AssetPricingDetails,
+    pub execution_fee: u128,
+    pub execution_fees_accumulated: u128,
+    pub position_fees_acccumulated: u128,
+}
+
+impl From<LegacyHouseDetails> for HouseDetails {
+    /// Migrates legacy house details
+    ///
+    /// @dev no treasury account is set and the protocol is not paused
+    fn from(legacy: LegacyHouseDetails) -> Self {
+        let LegacyHouseDetails {
+            house_asset_ledger,
+            house_asset_pricing_details,
+            execution_fee,
+            execution_fees_accumulated,
+            position_fees_acccumulated,
+        } = legacy;
+
+        HouseDetails {
+            house_asset_ledger,
+            house_asset_pricing_details,
+            execution_fee,
+            execution_fees_accumulated,
+            position_fees_acccumulated,
+            treasury_account: None,
+            paused: false,
+        }
+    }
+}
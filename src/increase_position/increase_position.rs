@@ -0,0 +1,167 @@
+use crate::constants::OPEN_POSITION_PRIORITY_INDEX;
+use crate::events::event_log::record_event;
+use crate::events::evnts_type::Events;
+use crate::house_settings::{
+    get_execution_fee, is_paused, update_execution_fees_accumulated,
+    update_position_fees_acccumulated,
+};
+use crate::increase_position::increase_position_params::IncreasePositionParams;
+use crate::market::functions::open_position_in_market::{
+    FailureReason, OpenPositioninMarketResult,
+};
+use crate::math::math::apply_precision;
+use crate::pricing_update_management::price_waiting_operation_trait::PriceWaitingOperation;
+use crate::pricing_update_management::price_waiting_operation_utils::put_price_waiting_operation;
+use crate::stable_memory::MARKETS_LIST;
+use crate::user::balance_utils::{get_user_balance, set_user_balance};
+use crate::user::position_util::_put_user_position_detail;
+use crate::user::user_query::try_get_user_position_details;
+
+use ic_cdk::api::msg_caller;
+use ic_cdk::update;
+
+/// Increases an existing trading position with added collateral and leverage.
+///
+/// The added open interest goes through the same leverage and reserve checks as opening a position
+/// and is merged into the existing position under the same position ID, with a weighted-average entry.
+/// Funding and borrowing fees accrued by the position are settled from the added collateral and the position
+/// starts from fresh checkpoints, the rest of the added collateral is opened at the leverage.
+///
+/// # Parameters
+///
+/// * `params` - [`IncreasePositionParams`] containing:
+///   - `owner` (Principal): The principal ID of the position owner
+///   - `market_index` (u64): The unique identifier of the market containing the position
+///   - `position_id` (u64): The unique identifier of the position to increase
+///   - `collateral` (u128): Collateral amount to add in quote asset (20-decimal precision)
+///   - `leverage_factor` (u128): The leverage multiplier for the added collateral (with 20 decimal places precision)
+///   - `acceptable_price_limit` (u128): Maximum acceptable price for the increase (with 20 decimal places precision)
+///   - `reserve_factor` (u128): Reserve factor for the added open interest (with 20 decimal places precision)
+///
+/// # Returns
+///
+/// Returns [`OpenPositioninMarketResult`] which can be:
+/// - `Settled { position_id, position, position_fee }`: Successfully increased position, returns position ID, the increased position
+///   and the position fee (open fee factor of the added open interest) taken from the user's balance
+/// - `Waiting { id }`: Operation queued due to stale price data, will execute when price updates,
+///   `id` is the ticket for querying the operation status
+/// - `Failed { reason }`: Operation failed with specific reason (InsufficientBalance, PriceLimitExceeded, MarketNotActive, Other)
+///
+/// # Price Update Handling
+///
+/// If the market's price data is stale (beyond the allowed update interval), the operation
+/// is queued as a price waiting operation and will be executed automatically when fresh
+/// price data becomes available.
+#[update(name = "increasePosition")]
+pub fn increase_position(params: IncreasePositionParams) -> OpenPositioninMarketResult {
+    let caller = msg_caller();
+    assert!(
+        caller == params.owner,
+        "Caller is not the owner of the position"
+    );
+    let result = _increase_position(&params);
+
+    if let OpenPositioninMarketResult::Waiting { id: _ } = result {
+        let ticket = put_price_waiting_operation(
+            params.market_index,
+            OPEN_POSITION_PRIORITY_INDEX,
+            PriceWaitingOperation::from(params),
+        );
+
+        return OpenPositioninMarketResult::Waiting { id: Some(ticket) };
+    };
+
+    result
+}
+
+/// Internal implementation of the increase position functionality.
+///
+/// The function:
+/// 1. Validates user has sufficient balance (collateral + execution fee + position fee)
+/// 2. Retrieves the position and verifies it belongs to the specified market
+/// 3. Increases the position in the market if the price is current, otherwise returns `Waiting`
+/// 4. Updates user balance and replaces the position record on success
+pub fn _increase_position(params: &IncreasePositionParams) -> OpenPositioninMarketResult {
+    let trader = params.owner;
+    let trader_balance = get_user_balance(trader);
+
+    let execution_fee = get_execution_fee();
+
+    if trader_balance < params.collateral + execution_fee {
+        return OpenPositioninMarketResult::Failed {
+            reason: FailureReason::InsufficientBalance,
+        };
+    }
+
+    let Some((market_index, position)) = try_get_user_position_details(trader, params.position_id)
+    else {
+        return OpenPositioninMarketResult::Failed {
+            reason: FailureReason::Other,
+        };
+    };
+
+    if market_index != params.market_index {
+        return OpenPositioninMarketResult::Failed {
+            reason: FailureReason::Other,
+        };
+    }
+
+    MARKETS_LIST.with_borrow_mut(|reference| {
+        let mut market = reference.get(market_index).expect("Market does not exist");
+
+        if is_paused() || !market.status.allows_opening() {
+            return OpenPositioninMarketResult::Failed {
+                reason: FailureReason::MarketNotActive,
+            };
+        }
+
+        let position_fee = market.get_position_fee(
+            apply_precision(params.leverage_factor, params.collateral),
+            true,
+        );
+
+        if trader_balance < params.collateral + execution_fee + position_fee {
+            return OpenPositioninMarketResult::Failed {
+                reason: FailureReason::InsufficientBalance,
+            };
+        }
+
+        let result = market.increase_position_in_market(position, *params);
+
+        if let OpenPositioninMarketResult::Settled { position, .. } = result {
+            set_user_balance(
+                trader,
+                trader_balance - (params.collateral + execution_fee + position_fee),
+            );
+
+            // take excution fee
+            update_execution_fees_accumulated(execution_fee, true);
+
+            // take position fee
+            let treasury_fee = market.collect_position_fee(position_fee);
+            update_position_fees_acccumulated(treasury_fee, true);
+
+            _put_user_position_detail(trader, market_index, params.position_id, position);
+
+            reference.set(market_index, &market);
+
+            record_event(Events::IncreasePosition {
+                owner: trader,
+                market_index,
+                position_id: params.position_id,
+                collateral: params.collateral,
+                position,
+                price: market.pricing_manager.price,
+                position_fee,
+            });
+
+            return OpenPositioninMarketResult::Settled {
+                position_id: Some(params.position_id),
+                position,
+                position_fee,
+            };
+        };
+
+        result
+    })
+}
@@ -0,0 +1,86 @@
+use candid::{CandidType, Principal};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    increase_position::increase_position::_increase_position,
+    pricing_update_management::{
+        operation_status::OperationStatus,
+        price_waiting_operation_trait::{PriceWaitingOperation, PriceWaitingOperationTrait},
+    },
+    user::balance_utils::update_user_balance,
+};
+
+/// Parameters for increasing an existing trading position.
+///
+/// The added collateral and leverage are merged into the position, which keeps its position ID.
+/// The owner must be the message caller to ensure security.
+#[cfg_attr(test, derive(Debug, PartialEq, Eq))]
+#[derive(CandidType, Clone, Copy, Deserialize, Serialize)]
+pub struct IncreasePositionParams {
+    /// The principal ID of the position owner.
+    /// **IMPORTANT**: This must match the message caller (`msg_caller()`) for security.
+    pub owner: Principal,
+
+    /// The unique identifier of the market containing the position.
+    #[serde(rename = "marketIndex")]
+    pub market_index: u64,
+
+    /// The unique identifier of the position to increase.
+    #[serde(rename = "positionId")]
+    pub position_id: u64,
+
+    /// The collateral amount to add to the position, in quote asset.
+    /// Uses 20-decimal precision.
+    /// The user's balance must cover this amount plus the execution fee (in quote asset).
+    pub collateral: u128,
+
+    /// The leverage multiplier for the added collateral.
+    /// This should be specified with 20 decimal places precision (e.g., 10000000000000000000000 for 10x leverage).
+    #[serde(rename = "leverageFactor")]
+    pub leverage_factor: u128,
+
+    /// The maximum acceptable price (minimum for shorts) for increasing the position, in quote asset terms.
+    /// Uses 20-decimal precision.
+    #[serde(rename = "acceptablePriceLimit")]
+    pub acceptable_price_limit: u128,
+
+    /// The reserve factor for the added open interest.
+    /// Uses 20-decimal precision.
+    #[serde(rename = "reserveFactor")]
+    pub reserve_factor: u128,
+
+    /// Optional deadline for the operation, in nanoseconds since the UNIX epoch.
+    /// If the operation is queued waiting for a price update and is not executed by this time,
+    /// it is failed and its reserved amounts are released.
+    /// Without a deadline, a queued operation fails if the next price update fails.
+    pub deadline: Option<u64>,
+}
+
+impl PriceWaitingOperationTrait for IncreasePositionParams {
+    fn execute(&self) -> OperationStatus {
+        _increase_position(self).into()
+    }
+
+    fn deadline(&self) -> Option<u64> {
+        self.deadline
+    }
+
+    fn executor(&self) -> Option<Principal> {
+        Some(self.owner)
+    }
+
+    /// reserves the added collateral, the execution fee is taken on execution
+    fn reserve(&self) {
+        update_user_balance(self.owner, self.collateral, false);
+    }
+
+    fn release(&self) {
+        update_user_balance(self.owner, self.collateral, true);
+    }
+}
+
+impl From<IncreasePositionParams> for PriceWaitingOperation {
+    fn from(params: IncreasePositionParams) -> Self {
+        PriceWaitingOperation::IncreasePosition(params)
+    }
+}
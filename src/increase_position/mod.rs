@@ -0,0 +1,3 @@
+#[allow(clippy::module_inception)]
+pub mod increase_position;
+pub mod increase_position_params;
@@ -1,44 +1,77 @@
 use crate::asset_management::asset_management::AssetLedger;
-use crate::stable_memory::{ADMIN, HOUSE_SETTINGS};
+use crate::stable_memory::{HOUSE_SETTINGS, OWNER};
 use candid::{CandidType, Principal};
-use ic_cdk::{export_candid, init};
+use ic_cdk::{export_candid, init, post_upgrade};
 use serde::Deserialize;
 
 use crate::admin_roles::create_market::CreateMarketParams;
+use crate::delisting::delisting_utils::rearm_delisting_timers;
 use crate::house_settings::HouseDetails;
+use crate::market::legacy_market_details::migrate_legacy_markets;
+use crate::order_management::twap_order_utils::rearm_twap_orders_timers;
 use crate::pricing_update_management::price_fetch::AssetPricingDetails;
+use crate::pricing_update_management::price_waiting_operation_utils::rearm_price_waiting_operations_timers;
+use crate::settlement_management::settlement_timer_utils::rearm_market_settlement_timers;
 
 // Import types needed for Candid generation
 use add_liquidity::add_liquidity_params::AddLiquidityParams;
+use admin_roles::roles::Role;
 use close_position::close_position_params::ClosePositionParams;
 use close_position::close_position_result::ClosePositionResult;
+use delisting::delisting_details::DelistingDetails;
 use deposit::deposit_params::DepositParams;
+use events::evnts_type::{EventFilter, EventRecord};
+use icrc_ledger_types::icrc1::account::Account;
+use increase_position::increase_position_params::IncreasePositionParams;
+use liquidate_position::liquidate_position_result::LiquidatePositionResult;
 use market::functions::open_position_in_market::OpenPositioninMarketResult;
+use market::market_config::MarketConfig;
 use market::market_details::LiquidityOperationResult;
 use market::market_details::MarketDetails;
+use market::market_status::MarketStatus;
 use open_position::open_position_params::OpenPositionParams;
+use order_management::create_order::CreateOrderParams;
+use order_management::create_twap_order::CreateTwapOrderParams;
+use order_management::order::Order;
+use order_management::position_triggers::{PositionTriggers, TrailingDistance};
+use order_management::twap_order::TwapOrder;
+use pricing_update_management::operation_status::OperationStatus;
 use query::market_details_query::QueryMarketDetailsResult;
 use query::position_query::QueryPositionDetailsResult;
+use remove_collateral::remove_collateral_params::RemoveCollateralParams;
 use remove_liquidity::remove_liquidity_params::RemoveLiquidityParams;
+use settlement_management::settlement_details::SettlementDetails;
+use timelock::timelocked_change::QueuedChange;
+use treasury::treasury_sweep::TreasurySweep;
 use withdraw::withdraw_params::WithdrawParams;
 
 // Module declarations
+pub mod add_collateral;
 pub mod add_liquidity;
 pub mod admin_roles;
 pub mod asset_management;
+pub mod cancel_pending_operation;
 pub mod close_position;
 pub mod constants;
+pub mod delisting;
 pub mod deposit;
 pub mod events;
 pub mod house_settings;
+pub mod increase_position;
+pub mod liquidate_position;
 pub mod market;
 pub mod math;
 pub mod open_position;
+pub mod order_management;
 pub mod position;
 pub mod pricing_update_management;
 pub mod query;
+pub mod remove_collateral;
 pub mod remove_liquidity;
+pub mod settlement_management;
 pub mod stable_memory;
+pub mod timelock;
+pub mod treasury;
 #[cfg(test)]
 pub mod unit_tests;
 pub mod user;
@@ -48,17 +81,57 @@ pub mod withdraw;
 // These are the functions that will be included in the generated Candid file
 
 // Update functions
+pub use add_collateral::add_collateral::add_collateral;
 pub use add_liquidity::add_liquidity::add_liquidity;
 pub use admin_roles::create_market::create_new_market;
+pub use admin_roles::grant_role::grant_role;
+pub use admin_roles::pause_protocol::{pause_protocol, unpause_protocol};
+pub use admin_roles::revoke_role::revoke_role;
+pub use admin_roles::set_execution_fee::set_execution_fee;
+pub use admin_roles::set_market_status::set_market_status;
+pub use admin_roles::set_settlement_interval::set_market_settlement_interval;
+pub use admin_roles::set_timelock_delay::set_timelock_delay;
+pub use admin_roles::transfer_ownership::{accept_ownership, propose_owner};
+pub use admin_roles::update_market_config::update_market_config;
+pub use cancel_pending_operation::cancel_pending_operation::cancel_pending_operation;
 pub use close_position::close_position::close_position;
+pub use delisting::delist_market::delist_market;
+pub use delisting::redeem_delisted_liquidity::redeem_delisted_liquidity;
 pub use deposit::deposit::deposit_into_account;
+pub use deposit::deposit::get_deposit_account;
 pub use house_settings::get_house_details;
+pub use increase_position::increase_position::increase_position;
+pub use liquidate_position::liquidate_position::liquidate_position;
+pub use market::query_utils::get_market_config;
 pub use market::query_utils::get_market_details;
 pub use market::query_utils::get_markets_count_plus_1;
 pub use open_position::open_position::open_position;
+pub use order_management::cancel_order::cancel_order;
+pub use order_management::cancel_twap_order::cancel_twap_order;
+pub use order_management::create_order::create_order;
+pub use order_management::create_twap_order::create_twap_order;
+pub use order_management::set_position_triggers::{
+    clear_position_triggers, set_position_triggers, set_trailing_stop,
+};
+pub use query::delisting_query::get_market_delisting;
+pub use query::events_query::{get_events, get_events_count};
 pub use query::market_details_query::query_market_details;
+pub use query::operation_status_query::get_operation_status;
+pub use query::order_query::{
+    get_market_orders, get_order_status, get_twap_order, get_user_orders, get_user_twap_orders,
+};
 pub use query::position_query::get_all_user_positions_in_market;
+pub use query::position_triggers_query::get_position_triggers;
+pub use query::roles_query::{get_owner, get_pending_owner, get_roles};
+pub use query::settlement_details_query::get_market_settlement_details;
+pub use query::timelock_query::{get_timelock_delay, get_timelocked_changes};
+pub use query::treasury_query::get_treasury_sweeps;
+pub use remove_collateral::remove_collateral::remove_collateral;
 pub use remove_liquidity::remove_liquidity::remove_liquidity;
+pub use timelock::cancel_change::cancel_timelocked_change;
+pub use timelock::execute_change::execute_timelocked_change;
+pub use treasury::set_treasury_account::set_treasury_account;
+pub use treasury::sweep_treasury_fees::sweep_treasury_fees;
 pub use withdraw::withdraw::withdraw_from_account;
 // Query functions
 
@@ -80,7 +153,8 @@ fn init(init_details: InitParams) {
         house_asset_pricing_details,
         execution_fee,
     } = init_details;
-    ADMIN.with_borrow_mut(|reference| reference.set(admin));
+    // the admin is the initial owner of the protocol
+    OWNER.with_borrow_mut(|reference| reference.set(admin));
 
     HOUSE_SETTINGS.with_borrow_mut(|reference| {
         reference.set(HouseDetails {
@@ -92,5 +166,17 @@ fn init(init_details: InitParams) {
     });
 }
 
+#[post_upgrade]
+fn post_upgrade() {
+    // markets stored with the legacy layout are migrated before any timer reads them
+    migrate_legacy_markets();
+
+    // queued operations, markets settlement details and TWAP orders are kept in stable memory but their timers are not
+    rearm_price_waiting_operations_timers();
+    rearm_market_settlement_timers();
+    rearm_twap_orders_timers();
+    rearm_delisting_timers();
+}
+
 // Export Candid macro - this generates the Candid file automatically
 export_candid!();
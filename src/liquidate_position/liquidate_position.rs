@@ -0,0 +1,128 @@
+use candid::Principal;
+use ic_cdk::{api::msg_caller, update};
+
+use crate::constants::LIQUIDATE_POSITION_PRIORITY_INDEX;
+use crate::events::event_log::record_event;
+use crate::events::evnts_type::Events;
+use crate::liquidate_position::liquidate_position_params::LiquidatePositionParams;
+use crate::liquidate_position::liquidate_position_result::LiquidatePositionResult;
+use crate::pricing_update_management::price_waiting_operation_trait::PriceWaitingOperation;
+use crate::pricing_update_management::price_waiting_operation_utils::put_price_waiting_operation;
+use crate::stable_memory::MARKETS_LIST;
+use crate::user::balance_utils::update_user_balance;
+use crate::user::position_util::remove_user_position_detail;
+use crate::user::user_query::try_get_user_position_details;
+
+/// Liquidates an undercollateralized position in a specific market.
+///
+/// This function can be called by anyone. The position's health is checked against the
+/// market's current price and if the position is liquidatable it is closed, the caller
+/// is paid the liquidation reward and the liquidation fee is sent to the house.
+/// The liquidation reward is paid in full even for underwater positions, by the market
+/// for the part the position's returns do not cover.
+///
+/// # Parameters
+///
+/// * `market_index` (u64): The unique identifier of the market containing the position
+/// * `owner` (Principal): The principal ID of the position owner
+/// * `position_id` (u64): The unique identifier of the position to liquidate
+///
+/// # Returns
+///
+/// Returns [`LiquidatePositionResult`] which can be:
+/// - `Settled { liquidator_reward, liquidation_fee, returns }`: Position was liquidated,
+///   `returns` is what was left for the position owner
+/// - `Waiting { id }`: Operation queued due to stale price data, will execute when price updates,
+///   `id` is the ticket for querying the operation status
+/// - `Failed`: Position does not exist or is not liquidatable
+///
+/// # Price Update Handling
+///
+/// If the market's price data is stale (beyond the allowed update interval), the operation
+/// is queued as a price waiting operation and will be executed automatically when fresh
+/// price data becomes available.
+#[update(name = "liquidatePosition")]
+pub fn liquidate_position(
+    market_index: u64,
+    owner: Principal,
+    position_id: u64,
+) -> LiquidatePositionResult {
+    let params = LiquidatePositionParams {
+        market_index,
+        owner,
+        position_id,
+        liquidator: msg_caller(),
+    };
+
+    let result = _liquidate_position(&params);
+
+    if let LiquidatePositionResult::Waiting { id: _ } = result {
+        let ticket = put_price_waiting_operation(
+            market_index,
+            LIQUIDATE_POSITION_PRIORITY_INDEX,
+            PriceWaitingOperation::from(params),
+        );
+
+        return LiquidatePositionResult::Waiting { id: Some(ticket) };
+    }
+
+    result
+}
+
+/// Internal implementation of the liquidate position functionality.
+///
+/// The function:
+/// 1. Retrieves the position and verifies it belongs to the specified market
+/// 2. Liquidates the position in the market if it is liquidatable at the current price
+/// 3. Pays the liquidator reward and the owner's remaining returns into their balances
+/// 4. Removes the position from the user's positions
+pub fn _liquidate_position(params: &LiquidatePositionParams) -> LiquidatePositionResult {
+    let LiquidatePositionParams {
+        market_index,
+        owner,
+        position_id,
+        liquidator,
+    } = *params;
+
+    let Some((position_market_index, position)) = try_get_user_position_details(owner, position_id)
+    else {
+        return LiquidatePositionResult::Failed("Position does not exist".to_string());
+    };
+
+    if position_market_index != market_index {
+        return LiquidatePositionResult::Failed("Position is not in market".to_string());
+    }
+
+    MARKETS_LIST.with_borrow_mut(|reference| {
+        let mut market = reference.get(market_index).expect("Market does not exist");
+
+        let result = market.liquidate_position_in_market(position);
+
+        if let LiquidatePositionResult::Settled {
+            liquidator_reward,
+            liquidation_fee,
+            returns,
+        } = result
+        {
+            update_user_balance(liquidator, liquidator_reward, true);
+            update_user_balance(owner, returns, true);
+
+            remove_user_position_detail(owner, position_id);
+
+            reference.set(market_index, &market);
+
+            record_event(Events::LiquidatePosition {
+                liquidator,
+                owner,
+                market_index,
+                position_id,
+                price: market.pricing_manager.price,
+                liquidator_reward,
+                liquidation_fee,
+                returns,
+            });
+        }
+
+        result
+    })
+}
@@ -0,0 +1,45 @@
+use candid::{CandidType, Principal};
+use serde::{Deserialize, Serialize};
+
+use crate::liquidate_position::liquidate_position::_liquidate_position;
+use crate::pricing_update_management::operation_status::OperationStatus;
+use crate::pricing_update_management::price_waiting_operation_trait::{
+    PriceWaitingOperation, PriceWaitingOperationTrait,
+};
+
+/// Parameters for liquidating an undercollateralized position.
+///
+/// Liquidations are permissionless, the liquidator is the message caller and receives
+/// the liquidation reward once the position is liquidated.
+#[derive(CandidType, Deserialize, Serialize, Copy, Clone)]
+pub struct LiquidatePositionParams {
+    /// The unique identifier of the market containing the position.
+    #[serde(rename = "marketIndex")]
+    pub market_index: u64,
+
+    /// The principal ID of the position owner.
+    pub owner: Principal,
+
+    /// The unique identifier of the position to liquidate.
+    #[serde(rename = "positionId")]
+    pub position_id: u64,
+
+    /// The principal ID of the liquidator that is paid the liquidation reward.
+    pub liquidator: Principal,
+}
+
+impl PriceWaitingOperationTrait for LiquidatePositionParams {
+    fn execute(&self) -> OperationStatus {
+        _liquidate_position(self).into()
+    }
+
+    fn executor(&self) -> Option<Principal> {
+        Some(self.liquidator)
+    }
+}
+
+impl From<LiquidatePositionParams> for PriceWaitingOperation {
+    fn from(params: LiquidatePositionParams) -> Self {
+        PriceWaitingOperation::LiquidatePosition(params)
+    }
+}
@@ -0,0 +1,16 @@
+use candid::CandidType;
+use serde::Deserialize;
+
+#[cfg_attr(test, derive(Debug, PartialEq, Eq))]
+#[derive(CandidType, Deserialize)]
+pub enum LiquidatePositionResult {
+    Settled {
+        liquidator_reward: u128,
+        liquidation_fee: u128,
+        returns: u128,
+    },
+    Waiting {
+        id: Option<u64>, // operation ticket
+    },
+    Failed(String),
+}
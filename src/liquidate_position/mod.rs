@@ -0,0 +1,4 @@
+#[allow(clippy::module_inception)]
+pub mod liquidate_position;
+pub mod liquidate_position_params;
+pub mod liquidate_position_result;
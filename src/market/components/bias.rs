@@ -21,7 +21,6 @@ pub struct UpdateBiasDetailsParamters {
 
 impl Bias {
     /// Update Bais Details
-
     pub fn update_bias_details(
         &mut self,
         params: UpdateBiasDetailsParamters,
@@ -69,7 +68,7 @@ impl Bias {
 
         let pnl_of_shorts = shorts.house_pnl_by_specific_bias(price, false);
 
-        return pnl_of_shorts + pnl_of_longs;
+        pnl_of_shorts + pnl_of_longs
     }
 }
 
@@ -100,7 +99,7 @@ pub struct BiasDetails {
     /// tracks the debt in taking levarage @dev different from net debt of tokens dynamic
     total_debt_of_traders: u128,
 
-    /// the next borrowing fundig factor to be paid
+    /// the borrowing factor per second to be paid until the next update
     current_borrowing_factor: u128,
 
     /// Cummulative fuding factor since epoch
@@ -154,12 +153,12 @@ impl BiasDetails {
         self.cummulative_borrowing_factor_since_epoch
     }
 
-    /// Traders PNL by Bias calculation
-    ///
-    /// Calculates the current pnl of  traders in a particular bias direction
-    /// @dev at any point in time this figure is bounded
-    /// bounded above by total_reserve as that as that is the max the house can lose
-    /// bounded below by  the difference between  total_open_interest_dynamic (see BiasDetails) and net_debt
+    // Traders PNL by Bias calculation
+    //
+    // Calculates the current pnl of  traders in a particular bias direction
+    // @dev at any point in time this figure is bounded
+    // bounded above by total_reserve as that as that is the max the house can lose
+    // bounded below by  the difference between  total_open_interest_dynamic (see BiasDetails) and net_debt
     // pub fn traders_pnl_for_specific_bias(&self, price: u128, is_long: bool) -> i128 {
     //     let Self {
     //         total_open_interest,
@@ -192,11 +191,18 @@ impl BiasDetails {
     //     return traders_net_payout;
     // }
 
+    /// Calculate Borrowing Factor Per Second
+    ///
+    /// borrowing_factor_per_sec = (base_borrowing_factor * reserve_value ^ borrowing_exponent_factor) / pool_value
     pub fn calculate_borrowing_factor_per_sec(
         &self,
         pool_value: u128,
         reserve_value: u128,
     ) -> u128 {
+        if pool_value == 0 {
+            return 0;
+        }
+
         let Self {
             base_borrowing_factor,
             borrowing_exponent_factor_,
@@ -204,10 +210,7 @@ impl BiasDetails {
         } = *self;
         let reserve_after_expoent = apply_exponent(reserve_value, borrowing_exponent_factor_);
 
-        let borrowing_factor_per_sec =
-            mul_div(base_borrowing_factor, reserve_after_expoent, pool_value);
-
-        return borrowing_factor_per_sec;
+        mul_div(base_borrowing_factor, reserve_after_expoent, pool_value)
     }
 
     pub fn house_pnl_by_specific_bias(&self, price: u128, is_long: bool) -> i128 {
@@ -228,38 +231,43 @@ impl BiasDetails {
             (total_open_interest_dynamic - (total_debt_of_traders as i128)).min(0);
         let minimum_pnl = total_reserve.neg() + reduced_pnl_by_bad_debt;
 
-        let house_pnl = bound_signed(
+        bound_signed(
             (total_open_interest as i128) - (apply_precision(total_units, price) as i128) * sign,
             minimum_pnl,
             total_open_interest_dynamic - (total_debt_of_traders as i128),
-        );
-
-        house_pnl
+        )
     }
 
     /// Update Cummulative Borrowing factor
     ///
     /// @dev this  is simllar to the Update Cummulative funding factor with slight difference explained below
     /// For cummulative funding factor ,it is updated during time of collection as the factor value has been precalculated and stored in the liquidity_manager for Market
-    /// For updating cummulative  borrowing factor ,this  precalculated value is stored in the respective biases and  when
-    /// this function is called with new factor to be paid after durtion as argument ,the current payment is calculated and subtracted from open interest and returned from function  
-    pub fn update_cumulative_borrowing_factor(&mut self, new_cbr: u128) -> u128 {
+    /// For updating cummulative  borrowing factor ,the borrowing factor per second is precalculated and stored in the respective biases and  when
+    /// this function is called with the duration since the last update and the new borrowing factor per second as arguments,
+    /// the current payment for the duration is calculated and subtracted from open interest dynamic and returned from function
+    pub fn update_cumulative_borrowing_factor(
+        &mut self,
+        duration_in_secs: u128,
+        new_borrowing_factor_ps: u128,
+    ) -> u128 {
         let Self {
-            current_borrowing_factor: previous_borrowing_factor,
+            current_borrowing_factor: previous_borrowing_factor_ps,
             total_open_interest,
             ..
         } = *self;
+
+        let borrowing_factor = previous_borrowing_factor_ps * duration_in_secs;
         // amount paid
-        let value = apply_precision(previous_borrowing_factor, total_open_interest);
+        let value = apply_precision(borrowing_factor, total_open_interest);
 
         // net open_interest when trader has lost all collateral
-        self.total_open_interest_dynamic = self.total_open_interest_dynamic - value as i128;
+        self.total_open_interest_dynamic -= value as i128;
 
         // cummulative paid borrwing factor since  instantiating is increased
-        self.cummulative_borrowing_factor_since_epoch += previous_borrowing_factor;
+        self.cummulative_borrowing_factor_since_epoch += borrowing_factor;
 
-        self.current_borrowing_factor = new_cbr;
-        return value;
+        self.current_borrowing_factor = new_borrowing_factor_ps;
+        value
     }
 
     /// Update Cummultive Funding factor
@@ -272,9 +280,9 @@ impl BiasDetails {
 
         let sign = if delta_cfr > 0 { 1 } else { -1 };
 
-        let value = apply_precision(delta_cfr.abs() as u128, self.total_open_interest) as i128;
+        let value = apply_precision(delta_cfr.unsigned_abs(), self.total_open_interest) as i128;
 
-        self.total_open_interest_dynamic = self.total_open_interest_dynamic + (value * sign);
+        self.total_open_interest_dynamic += value * sign;
 
         self.cummulative_funding_factor_since_epoch += delta_cfr;
     }
@@ -283,12 +291,12 @@ impl BiasDetails {
     }
 
     pub fn bias_parameters(&self) -> (u128, i128, u128, u128, u128) {
-        return (
+        (
             self.total_open_interest,
             self.total_open_interest_dynamic,
             self.total_units,
             self.total_reserve,
             self.total_debt_of_traders,
-        );
+        )
     }
 }
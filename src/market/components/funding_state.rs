@@ -1,5 +1,4 @@
 use candid::CandidType;
-use ic_cdk::api::time;
 use serde::{Deserialize, Serialize};
 
 use crate::math::math::{apply_exponent, apply_precision, bound_magnitude_signed, to_precision};
@@ -42,18 +41,26 @@ pub struct FundingState {
 
 impl FundingState {
     pub fn current_funding_factor_ps(&self) -> i128 {
-        return self.current_funding_factor_ps;
+        self.current_funding_factor_ps
     }
 
     /// Update FUnding factor per second
     ///
     /// @dev updates the current funding factor based
+    /// @dev last_time_updated is not set here, it is moved forward by the caller after the funding for the elapsed duration is paid
+    /// @dev the funding factor per second changes by its increase or decrease factor for every second of `duration_in_secs`,
+    /// the duration since the last update
     ///
     /// Funding fee per_sec is calculated as
     /// funding_fee_per_sec =(funding_factor * (longshort difference)^ (funding_factor_exponent))/ (total_open_interest)
     ///
     /// See README.md for more technical overview  info
-    pub fn _update_funding_factor_ps(&mut self, long_short_diff: i128, total_open_interest: u128) {
+    pub fn _update_funding_factor_ps(
+        &mut self,
+        long_short_diff: i128,
+        total_open_interest: u128,
+        duration_in_secs: u128,
+    ) {
         let Self {
             threshold_decrease_funding,
             threshold_stable_funding,
@@ -68,10 +75,11 @@ impl FundingState {
 
         assert!(total_open_interest > 0);
 
-        let long_short_diff_mag = long_short_diff.abs() as u128;
+        let long_short_diff_mag = long_short_diff.unsigned_abs();
 
         if long_short_diff == 0 {
-            self.current_funding_factor_ps = 0
+            self.current_funding_factor_ps = 0;
+            return;
         }
 
         // (imbalance) ^ (funding_expoenent_factor)
@@ -89,8 +97,7 @@ impl FundingState {
             if funding_factor_ps > max_funding_factor_ps {
                 funding_factor_ps = max_funding_factor_ps;
             }
-            let sign = long_short_diff.abs() / long_short_diff;
-            self.current_funding_factor_ps = funding_factor_ps as i128 * sign;
+            self.current_funding_factor_ps = funding_factor_ps as i128 * long_short_diff.signum();
 
             return;
         }
@@ -100,7 +107,7 @@ impl FundingState {
         // if ppsitive then shorts pay long
         let current_funding_factor_ps = self.current_funding_factor_ps;
 
-        let current_funding_factor_ps_mag = current_funding_factor_ps.abs() as u128;
+        let current_funding_factor_ps_mag = current_funding_factor_ps.unsigned_abs();
 
         let mut next_saved_funding_factor_ps = current_funding_factor_ps; // default to currentfunding factor
 
@@ -108,7 +115,7 @@ impl FundingState {
 
         // skew same direction is positive funding and longs more than shorts also when funding is negative shorts are more than longs
         let is_skew_same_direction = (current_funding_factor_ps > 0 && long_short_diff > 0)
-            || (current_funding_factor_ps < 0 || long_short_diff < 0);
+            || (current_funding_factor_ps < 0 && long_short_diff < 0);
 
         if is_skew_same_direction {
             if long_short_diff_to_open_interest_factor > threshold_stable_funding {
@@ -127,7 +134,7 @@ impl FundingState {
             let mut increase_value = (apply_precision(
                 long_short_diff_to_open_interest_factor,
                 funding_increase_factor_ps,
-            ) * self._seconds_since_last_update()) as i128;
+            ) * duration_in_secs) as i128;
 
             // if there are more longs than shorts, then the funding factor should increase
             // otherwise the funding factor per second should increase in the opposite direction / decrease
@@ -139,7 +146,7 @@ impl FundingState {
         }
 
         if change_type == FundingChangeType::Decrease && current_funding_factor_ps.abs() != 0 {
-            let decrease_value = funding_decrease_factor_ps * self._seconds_since_last_update();
+            let decrease_value = funding_decrease_factor_ps * duration_in_secs;
 
             if current_funding_factor_ps_mag <= decrease_value {
                 // set the funding factor to 1 or -1 depending on the original savedFundingFactorPerSecond
@@ -160,10 +167,5 @@ impl FundingState {
         );
 
         self.current_funding_factor_ps = next_saved_funding_factor_ps;
-        self.last_time_updated = time();
-    }
-
-    pub fn _seconds_since_last_update(&self) -> u128 {
-        ((time() - self.last_time_updated) / (10u64.pow(9u32))) as u128
     }
 }
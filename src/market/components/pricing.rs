@@ -15,6 +15,11 @@ pub struct PricingState {
     pub price_impact_exponent_factor: u128,
     pub positive_price_impact_factor: u128,
     pub negative_price_impact_factor: u128,
+    /// Impact Pool
+    ///
+    /// the negative price impact paid by traders through worse execution prices,
+    /// positive price impact is only paid out to traders up to this amount
+    pub impact_pool: u128,
 }
 
 impl PricingState {
@@ -28,9 +33,9 @@ impl PricingState {
 
     pub fn get_price(&self) -> Option<u128> {
         if is_within_price_update_interval(self.last_time_updated) {
-            return Some(self.price);
+            Some(self.price)
         } else {
-            return None;
+            None
         }
     }
 
@@ -38,6 +43,13 @@ impl PricingState {
         self.price = price;
         self.last_time_updated = time()
     }
+
+    /// Update Impact Pool
+    ///
+    /// negative price impact is added to the impact pool and positive price impact is paid out of it
+    pub fn update_impact_pool(&mut self, price_impact: i128) {
+        self.impact_pool = (self.impact_pool as i128 - price_impact) as u128;
+    }
     // Price impact is calculated as:
     //
     // ```
@@ -75,9 +87,7 @@ impl PricingState {
             self.apply_impact_factor(next_diff, *impact_factor),
         );
 
-        let price_impact = to_signed(delta_diff, has_positive_impact);
-
-        return price_impact;
+        to_signed(delta_diff, has_positive_impact)
     }
 
     // @dev get the price impact USD if there is a crossover in balance
@@ -105,9 +115,7 @@ impl PricingState {
 
         let delta_diff = diff(positive_impact, negative_impact);
 
-        let price_imapct = to_signed(delta_diff, positive_impact > negative_impact);
-
-        return price_imapct;
+        to_signed(delta_diff, positive_impact > negative_impact)
     }
 
     // @dev apply the impact factor calculation to a USD diff value
@@ -129,6 +137,6 @@ fn to_signed(a: u128, is_positive: bool) -> i128 {
     if is_positive {
         a as i128
     } else {
-        a as i128 * -1
+        -(a as i128)
     }
 }
@@ -0,0 +1,25 @@
+use crate::market::market_details::MarketDetails;
+use crate::utils::duration_in_seconds;
+
+impl MarketDetails {
+    /// Accrue Funding And Borrowing Fees
+    ///
+    /// brings the cumulative funding and borrowing factors of both biases up to the current time
+    /// @dev called before every interaction with the market so positions and liquidity shares
+    /// are valued with the fees accrued since the last settlement
+    pub fn accrue_funding_and_borrowing_fees(&mut self) -> bool {
+        self._accrue_funding_and_borrowing_fees_after_duration(duration_in_seconds)
+    }
+
+    pub fn _accrue_funding_and_borrowing_fees_after_duration<F>(
+        &mut self,
+        duration_in_secs: F,
+    ) -> bool
+    where
+        F: Fn(u64) -> u64 + Copy,
+    {
+        self._settle_funding_payment_after_duration(duration_in_secs);
+
+        self._collect_borrowing_fees_after_duration(duration_in_secs)
+    }
+}
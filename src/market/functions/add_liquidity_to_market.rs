@@ -24,6 +24,8 @@ impl MarketDetails {
         &mut self,
         params: AddLiquidityToMarketParams,
     ) -> LiquidityOperationResult {
+        self.accrue_funding_and_borrowing_fees();
+
         let active_price = self.pricing_manager.get_price();
 
         self._add_liquidity_to_market_with_price(params, active_price)
@@ -106,8 +108,8 @@ impl MarketDetails {
 
         self.liquidity_state = liquidity_state;
 
-        return LiquidityOperationResult::Settled {
+        LiquidityOperationResult::Settled {
             amount_out: liquidity_shares_out,
-        };
+        }
     }
 }
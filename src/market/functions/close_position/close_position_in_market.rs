@@ -5,7 +5,7 @@ use crate::market::functions::close_position::close_position_with_net_positive_f
 use crate::market::market_details::MarketDetails;
 
 use crate::market::components::bias::UpdateBiasDetailsParamters;
-use crate::math::math::Neg;
+use crate::math::math::{Neg, apply_precision};
 use crate::position::position_details::PositionDetails;
 impl MarketDetails {
     pub fn close_position_in_market(
@@ -13,6 +13,8 @@ impl MarketDetails {
         position: PositionDetails,
         acceptable_price_limit: u128,
     ) -> ClosePositionResult {
+        self.accrue_funding_and_borrowing_fees();
+
         let active_price = self.pricing_manager.get_price();
 
         self._close_position_with_price_option(position, acceptable_price_limit, active_price)
@@ -25,17 +27,44 @@ impl MarketDetails {
         active_price: Option<u128>,
     ) -> ClosePositionResult {
         let Some(price) = active_price else {
-            return ClosePositionResult::Waiting;
+            return ClosePositionResult::Waiting { id: None };
         };
 
         let PositionDetails { long, .. } = position;
+
+        // the position is closed at the price after the price impact of removing its open interest
+        let (price, price_impact) = self.get_execution_price(
+            position.open_interest(),
+            apply_precision(position.units, price),
+            long,
+            false,
+            price,
+        );
+
         // if closing a short and price is higher than acceptable price
         // if closing long ,and price is lower than acceptable price
-        if (long == false && price > acceptable_price_limit)
-            || (long && price < acceptable_price_limit)
-        {
+        if (!long && price > acceptable_price_limit) || (long && price < acceptable_price_limit) {
             return ClosePositionResult::Failed;
         }
+
+        self.pricing_manager.update_impact_pool(price_impact);
+
+        self._close_position_at_execution_price(position, price)
+    }
+
+    /// Close Position At Execution Price
+    ///
+    /// removes a position from the market at the price it is closed at,
+    /// paying its pnl, funding and borrowing fees
+    ///
+    /// Returns the collateral returned to the owner of the position
+    pub fn _close_position_at_execution_price(
+        &mut self,
+        position: PositionDetails,
+        price: u128,
+    ) -> ClosePositionResult {
+        let PositionDetails { long, .. } = position;
+
         let current_cummulative_funding_factor =
             self.get_cummulative_funding_factor_since_epoch(long);
 
@@ -100,9 +129,25 @@ impl MarketDetails {
                 )
             };
 
+        // borrowing fee is paid first from the position's collateral and profit (including funding received)
+        // and returned to the house, repaying any bad debt incured by the position first
+        let position_borrowing_fee_paid = position_net_borrowing_fee.min(
+            position.collateral
+                + position_pnl.max(0) as u128
+                + position_net_funding_fee.max(0) as u128,
+        );
+        let bad_debt_repaid_by_borrowing_fee =
+            incured_house_bad_debt_due_to_position.min(position_borrowing_fee_paid);
+        let incured_house_bad_debt_due_to_position =
+            incured_house_bad_debt_due_to_position - bad_debt_repaid_by_borrowing_fee;
+        net_free_liquidity += position_borrowing_fee_paid - bad_debt_repaid_by_borrowing_fee;
+
         // removed positions share of changes to debt and borrow_fees even if it is not fully repaid
+        // @dev the fees owed are accrued rounded down on each collection, so the positions' fees
+        // (on the sum of the factors) can exceed them by a few units
         current_net_debt -= position.debt;
-        current_borrow_fees_owed -= position_net_borrowing_fee;
+        current_borrow_fees_owed =
+            current_borrow_fees_owed.saturating_sub(position_net_borrowing_fee);
 
         // collateral out is only what is available in market
         collateral_out = collateral_out.min(total_deposit);
@@ -143,6 +188,7 @@ impl MarketDetails {
 
         ClosePositionResult::Settled {
             returns: collateral_out,
+            position_fee: 0,
         }
         // } else {
         //     ClosePositionResult::Waiting
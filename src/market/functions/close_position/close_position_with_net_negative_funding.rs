@@ -30,8 +30,8 @@ pub fn close_position_with_net_negative_funding(
         // collateral is what is left
         collateral_out = (position_value_without_funding_pay - net_funding_fee_magnitude) as u128;
 
-        // net free liquidity is max_reserve - position_pnl
-        net_free_liquidity += (max_reserve as i128 - position_pnl) as u128
+        // net free liquidity is max_reserve + debt repaid - position_pnl
+        net_free_liquidity += ((max_reserve + debt) as i128 - position_pnl) as u128
     } else {
         // This block of code  tracks extreme cases like
         // when position funding_fee_can not be paid fully by a position
@@ -49,9 +49,10 @@ pub fn close_position_with_net_negative_funding(
                 position_value_without_pnl.min(position_pnl_magnitude);
             //
             //
-            net_free_liquidity += max_reserve + house_profit_from_negative_position_pnl as u128;
+            net_free_liquidity +=
+                max_reserve + debt + house_profit_from_negative_position_pnl as u128;
         } else {
-            net_free_liquidity += (max_reserve as i128 - position_pnl_magnitude) as u128
+            net_free_liquidity += ((max_reserve + debt) as i128 - position_pnl_magnitude) as u128
         }
 
         let delta = net_free_liquidity as i128 + position_value_without_funding_pay // basically free liquidity + anything remainng in position - net funding fee magnitude
@@ -61,10 +62,10 @@ pub fn close_position_with_net_negative_funding(
             net_free_liquidity = delta as u128;
         } else {
             net_free_liquidity = 0;
-            bad_debt = delta.abs() as u128;
+            bad_debt = delta.unsigned_abs();
             //funding_paid = (delta + net_funding_fee_magnitude) as u128
         }
     }
 
-    return (net_free_liquidity, collateral_out, bad_debt);
+    (net_free_liquidity, collateral_out, bad_debt)
 }
@@ -48,12 +48,12 @@ pub fn close_position_with_net_positive_funding(
         let house_profit_from_negative_position_pnl =
             position_value_without_pnl.min(position_pnl_magnitude);
 
-        //
-        net_free_liquidity += max_reserve + house_profit_from_negative_position_pnl as u128;
+        // debt taken for leverage is repaid to the house
+        net_free_liquidity += max_reserve + debt + house_profit_from_negative_position_pnl as u128;
     } else {
         // trader gains , house losses
-        net_free_liquidity += max_reserve - position_pnl_magnitude as u128
+        net_free_liquidity += max_reserve + debt - position_pnl_magnitude as u128
     }
 
-    return (net_free_liquidity, net_position_value as u128, 0);
+    (net_free_liquidity, net_position_value as u128, 0)
 }
@@ -1,6 +1,5 @@
-use ic_cdk::api::time;
-
-// use crate::constants::MAX_ALLOWED_PRICE_CHANGE_INTERVAL;
+use crate::constants::_ONE_SECOND;
+use crate::market::components::bias::Bias;
 use crate::market::components::liquidity_state::HouseLiquidityState;
 use crate::market::market_details::MarketDetails;
 use crate::utils::duration_in_seconds;
@@ -13,64 +12,55 @@ impl MarketDetails {
         self._collect_borrowing_fees_after_duration(duration_in_secs)
     }
 
+    /// Collect Borrowing Fees
+    ///
+    /// each bias pays the borrowing factor per second set at the last collection for the duration since,
+    /// the payment is added to the borrow fees owed to the house
+    ///
+    /// the next borrowing factor per second of each bias is calculated from its reserve relative to the pool value
+    /// @dev the pool value used excludes traders pnl, like the max reserves of the market
     pub fn _collect_borrowing_fees_after_duration<F>(&mut self, duration_in_secs: F) -> bool
     where
         F: Fn(u64) -> u64,
     {
-        // let pricing_manager = (*self).pricing_manager;
-
-        // let price = pricing_manager.get_price();
-
-        // //   if let Some(price) = price_update {
-        // let Some(price) = price else {
-        //     return false;
-        // };
-        // let pool_value = self._house_value(price);
-
-        // let Self {
-        //     liquidity_state: liquidity_manager,
-
-        //     bias_tracker,
-        //     ..
-        // } = self;
+        let Self {
+            liquidity_state,
+            bias_tracker,
+            ..
+        } = self;
 
-        // let long_reserve = bias_tracker.longs.reserve_value(price, true);
+        let HouseLiquidityState {
+            current_longs_reserve,
+            current_shorts_reserve,
+            current_borrow_fees_owed,
+            last_time_since_borrow_fees_collected,
+            ..
+        } = *liquidity_state;
 
-        // let short_reserve = bias_tracker.longs.reserve_value(price, false);
+        let pool_value = liquidity_state.static_value().max(0) as u128;
 
-        // let longs_borrow_factor_per_second = bias_tracker
-        //     .longs
-        //     .calculate_borrowing_factor_per_sec(pool_value, long_reserve);
+        let duration = duration_in_secs(last_time_since_borrow_fees_collected) as u128;
 
-        // let shorts_borrow_factor_per_second = bias_tracker
-        //     .shorts
-        //     .calculate_borrowing_factor_per_sec(pool_value, short_reserve);
+        let Bias { longs, shorts } = bias_tracker;
 
-        // let HouseLiquidityState {
-        //     last_time_since_borrow_fees_collected,
-        //     current_borrow_fees_owed,
-        //     ..
-        // } = liquidity_manager;
+        let longs_borrowing_factor_ps =
+            longs.calculate_borrowing_factor_per_sec(pool_value, current_longs_reserve);
 
-        // let duration_in_secs = duration_in_secs(*last_time_since_borrow_fees_collected);
+        let shorts_borrowing_factor_ps =
+            shorts.calculate_borrowing_factor_per_sec(pool_value, current_shorts_reserve);
 
-        // let longs_current_borrow_fee_payment =
-        //     bias_tracker.longs.update_cumulative_borrowing_factor(
-        //         longs_borrow_factor_per_second * duration_in_secs as u128,
-        //     );
+        let longs_borrow_fee_payment =
+            longs.update_cumulative_borrowing_factor(duration, longs_borrowing_factor_ps);
 
-        // let shorts_current_borrow_fee_payement =
-        //     bias_tracker.shorts.update_cumulative_borrowing_factor(
-        //         shorts_borrow_factor_per_second * duration_in_secs as u128,
-        //     );
-        // *last_time_since_borrow_fees_collected = time();
-        // *current_borrow_fees_owed +=
-        //     shorts_current_borrow_fee_payement + longs_current_borrow_fee_payment;
+        let shorts_borrow_fee_payment =
+            shorts.update_cumulative_borrowing_factor(duration, shorts_borrowing_factor_ps);
 
-        return true;
+        liquidity_state.current_borrow_fees_owed =
+            current_borrow_fees_owed + longs_borrow_fee_payment + shorts_borrow_fee_payment;
+        // moved forward by whole seconds only so fractions of a second are paid on the next collection
+        liquidity_state.last_time_since_borrow_fees_collected =
+            last_time_since_borrow_fees_collected + duration as u64 * _ONE_SECOND;
 
-        // } else {
-        //     return false;
-        // }
+        true
     }
 }
@@ -0,0 +1,210 @@
+use crate::increase_position::increase_position_params::IncreasePositionParams;
+use crate::market::components::bias::UpdateBiasDetailsParamters;
+use crate::market::components::liquidity_state::HouseLiquidityState;
+use crate::market::functions::open_position_in_market::{
+    FailureReason, OpenPositioninMarketResult,
+};
+use crate::market::market_details::MarketDetails;
+use crate::math::math::{apply_precision, to_precision};
+use crate::position::position_details::PositionDetails;
+
+impl MarketDetails {
+    pub fn increase_position_in_market(
+        &mut self,
+        position: PositionDetails,
+        params: IncreasePositionParams,
+    ) -> OpenPositioninMarketResult {
+        self.accrue_funding_and_borrowing_fees();
+
+        let active_price = self.pricing_manager.get_price();
+
+        self._increase_position_in_market_with_price(position, params, active_price)
+    }
+
+    /// Increase Position
+    ///
+    /// the added collateral and leverage go through the same checks as opening a position
+    /// and are merged into the position, the entry price becomes the weighted average of both entries
+    ///
+    /// the funding and borrowing fees accrued by the position are settled from the added collateral
+    /// with the same accounting as closing a position (borrowing fees are paid to the free liquidity,
+    /// repaying bad debt first, and funding is paid to or received from the bias),
+    /// the rest of the added collateral is opened at the leverage and the checkpoints are reset
+    /// to the current cumulative factors
+    /// @dev fails if the fees accrued exceed the position's collateral or the added collateral,
+    /// or the increased position's leverage exceeds the market's max leverage
+    pub fn _increase_position_in_market_with_price(
+        &mut self,
+        position: PositionDetails,
+        params: IncreasePositionParams,
+        active_price: Option<u128>,
+    ) -> OpenPositioninMarketResult {
+        let IncreasePositionParams {
+            collateral,
+            leverage_factor,
+            acceptable_price_limit,
+            reserve_factor,
+            ..
+        } = params;
+
+        let Some(price) = active_price else {
+            return OpenPositioninMarketResult::Waiting { id: None };
+        };
+
+        self._increase_position_at_price(
+            position,
+            collateral,
+            leverage_factor,
+            reserve_factor,
+            acceptable_price_limit,
+            price,
+        )
+    }
+
+    /// increases the position with the collateral at the leverage, at the price before its price impact
+    /// @dev also used to open the collateral added beyond the position's debt at 1x (see update_position_collateral)
+    pub fn _increase_position_at_price(
+        &mut self,
+        position: PositionDetails,
+        collateral: u128,
+        leverage_factor: u128,
+        reserve_factor: u128,
+        acceptable_price_limit: u128,
+        price: u128,
+    ) -> OpenPositioninMarketResult {
+        let PositionDetails { long, .. } = position;
+
+        let current_cummulative_funding_factor =
+            self.get_cummulative_funding_factor_since_epoch(long);
+
+        let current_cummulative_borrowing_factor =
+            self.get_cummulative_borrowing_factor_since_epoch(long);
+
+        let position_net_funding_fee =
+            position.get_net_funding_fee(current_cummulative_funding_factor);
+
+        let position_net_borrowing_fee =
+            position.get_net_borrowing_fee(current_cummulative_borrowing_factor);
+
+        let current_collateral = position.collateral as i128 + position_net_funding_fee
+            - position_net_borrowing_fee as i128;
+
+        // the added collateral left after the fees are settled
+        let net_collateral =
+            collateral as i128 + position_net_funding_fee - position_net_borrowing_fee as i128;
+
+        if current_collateral <= 0 || net_collateral <= 0 {
+            return OpenPositioninMarketResult::Failed {
+                reason: FailureReason::Other,
+            };
+        }
+
+        let net_collateral = net_collateral as u128;
+
+        let added_open_interest = apply_precision(leverage_factor, net_collateral);
+
+        // the added open interest is opened at the price after its price impact
+        let (execution_price, price_impact) =
+            self.get_execution_price(added_open_interest, added_open_interest, long, true, price);
+
+        if execution_price == 0
+            || (long && execution_price > acceptable_price_limit)
+            || ((!long) && execution_price < acceptable_price_limit)
+        {
+            return OpenPositioninMarketResult::Failed {
+                reason: FailureReason::PriceLimitExceeded,
+            };
+        }
+
+        let increased_collateral = position.collateral + net_collateral;
+
+        // leverage of the increased position after the fees are settled,
+        // checked before the open interest is added to the market
+        let increased_position_leverage_factor = to_precision(
+            position.open_interest() + added_open_interest,
+            increased_collateral,
+        );
+
+        if increased_position_leverage_factor > self.state.max_leverage_factor {
+            return OpenPositioninMarketResult::Failed {
+                reason: FailureReason::Other,
+            };
+        }
+
+        let Some((debt, added_reserve, units)) = self._add_open_interest(
+            long,
+            net_collateral,
+            leverage_factor,
+            reserve_factor,
+            execution_price,
+        ) else {
+            return OpenPositioninMarketResult::Failed {
+                reason: FailureReason::Other,
+            };
+        };
+
+        self.pricing_manager.update_impact_pool(price_impact);
+
+        self._settle_position_fees(long, position_net_funding_fee, position_net_borrowing_fee);
+
+        let increased_position = PositionDetails {
+            collateral: increased_collateral,
+            debt: position.debt + debt,
+            units: position.units + units,
+            max_reserve: position.max_reserve + added_reserve,
+            pre_cummulative_funding_factor: current_cummulative_funding_factor,
+            pre_cummulative_borrowing_factor: current_cummulative_borrowing_factor,
+            ..position
+        };
+
+        OpenPositioninMarketResult::Settled {
+            position_id: None,
+            position: increased_position,
+            position_fee: 0,
+        }
+    }
+
+    /// settles the funding and borrowing fees of a position that stays open from the collateral deposited with them:
+    /// the borrowing fee is paid to the free liquidity (repaying bad debt first) and removed from the borrow fees owed,
+    /// funding paid is kept for the other side and funding received is paid out of the deposit,
+    /// the fees are removed from the bias' open interest dynamic as the position's checkpoints are reset
+    fn _settle_position_fees(
+        &mut self,
+        long: bool,
+        net_funding_fee: i128,
+        net_borrowing_fee: u128,
+    ) {
+        let Self {
+            liquidity_state,
+            bias_tracker,
+            ..
+        } = self;
+
+        let HouseLiquidityState {
+            total_deposit,
+            free_liquidity,
+            current_house_bad_debt,
+            current_borrow_fees_owed,
+            ..
+        } = *liquidity_state;
+
+        let bad_debt_repaid = net_borrowing_fee.min(current_house_bad_debt);
+
+        *liquidity_state = HouseLiquidityState {
+            // the deposit not opened in the position
+            total_deposit: (total_deposit as i128 + net_borrowing_fee as i128 - net_funding_fee)
+                as u128,
+            free_liquidity: free_liquidity + net_borrowing_fee - bad_debt_repaid,
+            current_house_bad_debt: current_house_bad_debt - bad_debt_repaid,
+            current_borrow_fees_owed: current_borrow_fees_owed.saturating_sub(net_borrowing_fee),
+            ..*liquidity_state
+        };
+
+        let params = UpdateBiasDetailsParamters {
+            delta_total_open_interest_dynamic: net_borrowing_fee as i128 - net_funding_fee,
+            ..Default::default()
+        };
+
+        bias_tracker.update_bias_details(params, long);
+    }
+}
@@ -0,0 +1,117 @@
+use crate::close_position::close_position_result::ClosePositionResult;
+use crate::liquidate_position::liquidate_position_result::LiquidatePositionResult;
+use crate::market::components::liquidity_state::HouseLiquidityState;
+use crate::market::market_details::{MarketDetails, MarketState};
+use crate::math::math::apply_precision;
+use crate::position::position_details::PositionDetails;
+
+impl MarketDetails {
+    pub fn liquidate_position_in_market(
+        &mut self,
+        position: PositionDetails,
+    ) -> LiquidatePositionResult {
+        self.accrue_funding_and_borrowing_fees();
+
+        let active_price = self.pricing_manager.get_price();
+
+        self._liquidate_position_with_price(position, active_price)
+    }
+
+    /// Liquidate Position
+    ///
+    /// checks the health of the position at the current price and if it is liquidatable,
+    /// closes it through the same accounting as a normal close
+    ///
+    /// from the amount returned by closing the position
+    /// the liquidator is paid the liquidation reward first,
+    /// the liquidation fee is sent back to the house (repaying bad debt before increasing free liquidity)
+    /// and whatever is left is returned to the owner
+    ///
+    /// the liquidation reward is always paid in full so underwater positions are still liquidated,
+    /// the part not covered by the returns is paid from the market's free liquidity
+    /// and booked as bad debt once the free liquidity is used up
+    pub fn _liquidate_position_with_price(
+        &mut self,
+        position: PositionDetails,
+        active_price: Option<u128>,
+    ) -> LiquidatePositionResult {
+        let Some(price) = active_price else {
+            return LiquidatePositionResult::Waiting { id: None };
+        };
+
+        let PositionDetails { long, .. } = position;
+
+        let current_cummulative_funding_factor =
+            self.get_cummulative_funding_factor_since_epoch(long);
+
+        let current_cummulative_borrowing_factor =
+            self.get_cummulative_borrowing_factor_since_epoch(long);
+
+        if !position.is_liquidatable(
+            price,
+            current_cummulative_funding_factor,
+            current_cummulative_borrowing_factor,
+            self.liquidity_state.liquidation_factor,
+        ) {
+            return LiquidatePositionResult::Failed("Position is not liquidatable".to_string());
+        }
+
+        // liquidations are executed at the current price (after price impact) regardless of direction
+        let acceptable_price_limit = if long { 0 } else { u128::MAX };
+
+        let ClosePositionResult::Settled { returns, .. } =
+            self._close_position_with_price_option(position, acceptable_price_limit, Some(price))
+        else {
+            return LiquidatePositionResult::Failed("Position could not be closed".to_string());
+        };
+
+        let MarketState {
+            liquidation_reward_factor,
+            liquidation_fee_factor,
+            ..
+        } = self.state;
+
+        let position_open_interest = position.open_interest();
+
+        let liquidator_reward = apply_precision(liquidation_reward_factor, position_open_interest);
+
+        // part of the liquidator reward the returns can not cover
+        let reward_shortfall = liquidator_reward.saturating_sub(returns);
+
+        let liquidation_fee = apply_precision(liquidation_fee_factor, position_open_interest)
+            .min(returns + reward_shortfall - liquidator_reward);
+
+        let HouseLiquidityState {
+            mut total_deposit,
+            mut free_liquidity,
+            mut current_house_bad_debt,
+            ..
+        } = self.liquidity_state;
+
+        // reward shortfall is paid by the market
+        let reward_paid_from_free_liquidity = reward_shortfall.min(free_liquidity);
+        free_liquidity -= reward_paid_from_free_liquidity;
+        current_house_bad_debt += reward_shortfall - reward_paid_from_free_liquidity;
+        total_deposit = total_deposit.saturating_sub(reward_shortfall);
+
+        // liquidation fee stays in the market
+        total_deposit += liquidation_fee;
+
+        let repaid_bad_debt = current_house_bad_debt.min(liquidation_fee);
+        current_house_bad_debt -= repaid_bad_debt;
+        free_liquidity += liquidation_fee - repaid_bad_debt;
+
+        self.liquidity_state = HouseLiquidityState {
+            total_deposit,
+            free_liquidity,
+            current_house_bad_debt,
+            ..self.liquidity_state
+        };
+
+        LiquidatePositionResult::Settled {
+            liquidator_reward,
+            liquidation_fee,
+            returns: returns + reward_shortfall - liquidator_reward - liquidation_fee,
+        }
+    }
+}
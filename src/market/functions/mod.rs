@@ -1,6 +1,12 @@
+pub mod accrue_fees;
 pub mod add_liquidity_to_market;
 pub mod close_position;
 pub mod collect_borrow_fees;
+pub mod increase_position_in_market;
+pub mod liquidate_position_in_market;
 pub mod open_position_in_market;
+pub mod position_fees;
+pub mod price_impact;
 pub mod remove_liquidity;
 pub mod settle_funding_payment;
+pub mod update_position_collateral;
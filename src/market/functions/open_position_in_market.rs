@@ -11,9 +11,17 @@ use crate::position::position_details::PositionDetails;
 #[derive(CandidType, Deserialize)]
 pub enum OpenPositioninMarketResult {
     // Limit {acceptable_price:u128,position:Position},
-    Settled { position: PositionDetails },
-    Waiting,
-    Failed { reason: FailureReason },
+    Settled {
+        position_id: Option<u64>,
+        position: PositionDetails,
+        position_fee: u128,
+    },
+    Waiting {
+        id: Option<u64>, // operation ticket
+    },
+    Failed {
+        reason: FailureReason,
+    },
 }
 
 #[cfg_attr(test, derive(Debug, Clone, Copy, PartialEq, Eq))]
@@ -21,6 +29,8 @@ pub enum OpenPositioninMarketResult {
 pub enum FailureReason {
     PriceLimitExceeded,
     InsufficientBalance,
+    /// Protocol is paused or the market does not accept opening positions
+    MarketNotActive,
     Other,
 }
 
@@ -39,6 +49,8 @@ impl MarketDetails {
         &mut self,
         params: OpenPositionParams,
     ) -> OpenPositioninMarketResult {
+        self.accrue_funding_and_borrowing_fees();
+
         // Gets price within the required time interval
         let active_price = self.pricing_manager.get_price();
 
@@ -79,16 +91,77 @@ impl MarketDetails {
         } = params;
 
         let Some(price) = active_price else {
-            return OpenPositioninMarketResult::Waiting;
+            return OpenPositioninMarketResult::Waiting { id: None };
         };
 
-        if (long && price > acceptable_price_limit) || ((!long) && price < acceptable_price_limit) {
+        let open_interest = apply_precision(leverage_factor, collateral);
+
+        // the position is opened at the price after the price impact of its open interest
+        let (execution_price, price_impact) =
+            self.get_execution_price(open_interest, open_interest, long, true, price);
+
+        if execution_price == 0
+            || (long && execution_price > acceptable_price_limit)
+            || ((!long) && execution_price < acceptable_price_limit)
+        {
             return OpenPositioninMarketResult::Failed {
                 reason: FailureReason::PriceLimitExceeded,
             };
         }
 
-        let market_state = (*self).state;
+        let Some((debt, added_reserve, units)) = self._add_open_interest(
+            long,
+            collateral,
+            leverage_factor,
+            reserve_factor,
+            execution_price,
+        ) else {
+            return OpenPositioninMarketResult::Failed {
+                reason: FailureReason::Other,
+            };
+        };
+
+        self.pricing_manager.update_impact_pool(price_impact);
+
+        let current_cumulative_funding_factor =
+            self.get_cummulative_funding_factor_since_epoch(long);
+
+        let current_cummulative_borrowing_factor =
+            self.get_cummulative_borrowing_factor_since_epoch(long);
+
+        let position = PositionDetails {
+            owner,
+            collateral,
+            long,
+            debt,
+            max_reserve: added_reserve,
+            units,
+            pre_cummulative_funding_factor: current_cumulative_funding_factor,
+            pre_cummulative_borrowing_factor: current_cummulative_borrowing_factor,
+        };
+        OpenPositioninMarketResult::Settled {
+            position_id: None,
+            position,
+            position_fee: 0,
+        }
+    }
+
+    /// Add Open Interest
+    ///
+    /// checks the leverage and reserve factors against the market's limits and
+    /// the debt and added reserve against the free liquidity and the max reserve of the bias,
+    /// then takes the debt and reserve from the house and adds the open interest to the bias
+    ///
+    /// Returns the debt, added reserve and units for the collateral, or None if any check fails
+    pub fn _add_open_interest(
+        &mut self,
+        long: bool,
+        collateral: u128,
+        leverage_factor: u128,
+        reserve_factor: u128,
+        price: u128,
+    ) -> Option<(u128, u128, u128)> {
+        let market_state = self.state;
 
         let MarketState {
             max_leverage_factor,
@@ -97,9 +170,7 @@ impl MarketDetails {
         } = market_state;
 
         if leverage_factor > max_leverage_factor || reserve_factor > max_reserve_factor {
-            return OpenPositioninMarketResult::Failed {
-                reason: FailureReason::Other,
-            };
+            return None;
         };
 
         let debt = apply_precision(leverage_factor, collateral) - collateral;
@@ -140,13 +211,11 @@ impl MarketDetails {
         if debt + added_reserve > free_liquidity
             || added_reserve + *current_reserve_for_bias > max_reserve_for_bias
         {
-            return OpenPositioninMarketResult::Failed {
-                reason: FailureReason::Other,
-            };
+            return None;
         }
 
         // reduce free liquidity
-        free_liquidity = free_liquidity - (added_reserve + debt);
+        free_liquidity -= added_reserve + debt;
         // increase current debt
         current_net_debt += debt;
         // increase current debt for bias
@@ -177,23 +246,7 @@ impl MarketDetails {
 
         bias_tracker.update_bias_details(params, long);
 
-        let current_cumulative_funding_factor =
-            self.get_cummulative_funding_factor_since_epoch(long);
-
-        let current_cummulative_borrowing_factor =
-            self.get_cummulative_borrowing_factor_since_epoch(long);
-
-        let position = PositionDetails {
-            owner,
-            collateral,
-            long,
-            debt,
-            max_reserve: added_reserve,
-            units,
-            pre_cummulative_funding_factor: current_cumulative_funding_factor,
-            pre_cummulative_borrowing_factor: current_cummulative_borrowing_factor,
-        };
-        OpenPositioninMarketResult::Settled { position }
+        Some((debt, added_reserve, units))
     }
 
     // get the amount
@@ -0,0 +1,58 @@
+use crate::market::components::liquidity_state::HouseLiquidityState;
+use crate::market::market_details::{MarketDetails, MarketState};
+use crate::math::math::apply_precision;
+
+impl MarketDetails {
+    /// Get Position Fee
+    ///
+    /// the fee charged for adding (opening or increasing a position) or removing (closing a position) open interest
+    pub fn get_position_fee(&self, open_interest: u128, opening: bool) -> u128 {
+        let MarketState {
+            open_fee_factor,
+            close_fee_factor,
+            ..
+        } = self.state;
+
+        let fee_factor = if opening {
+            open_fee_factor
+        } else {
+            close_fee_factor
+        };
+
+        apply_precision(fee_factor, open_interest)
+    }
+
+    /// Collect Position Fee
+    ///
+    /// splits a position fee between the house treasury and the market's liquidity providers,
+    /// the liquidity providers' share stays in the market (repaying bad debt before increasing free liquidity)
+    ///
+    /// Returns the treasury's share
+    pub fn collect_position_fee(&mut self, position_fee: u128) -> u128 {
+        let treasury_fee = apply_precision(self.state.treasury_fee_factor, position_fee);
+
+        let liquidity_providers_fee = position_fee - treasury_fee;
+
+        let HouseLiquidityState {
+            mut total_deposit,
+            mut free_liquidity,
+            mut current_house_bad_debt,
+            ..
+        } = self.liquidity_state;
+
+        total_deposit += liquidity_providers_fee;
+
+        let repaid_bad_debt = current_house_bad_debt.min(liquidity_providers_fee);
+        current_house_bad_debt -= repaid_bad_debt;
+        free_liquidity += liquidity_providers_fee - repaid_bad_debt;
+
+        self.liquidity_state = HouseLiquidityState {
+            total_deposit,
+            free_liquidity,
+            current_house_bad_debt,
+            ..self.liquidity_state
+        };
+
+        treasury_fee
+    }
+}
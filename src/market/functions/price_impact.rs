@@ -0,0 +1,90 @@
+use crate::market::market_details::MarketDetails;
+use crate::math::math::mul_div;
+
+impl MarketDetails {
+    /// Calculate Price Impact
+    ///
+    /// checks if changing the open interest of a bias changes the current skew direction
+    /// (i.e longs greater than shorts) and gets the price impact from the pricing manager
+    ///
+    /// Params
+    /// Open Interest - the open interest added to or removed from the bias
+    /// Long - the bias direction
+    /// Increase - true if the open interest is added (opening) and false if it is removed (closing)
+    ///
+    /// Returns the price impact, positive if the skew is reduced and negative if it is increased
+    pub fn calculate_price_impact(&self, open_interest: u128, long: bool, increase: bool) -> i128 {
+        let Self {
+            bias_tracker,
+            pricing_manager,
+            ..
+        } = self;
+
+        let initial_diff = bias_tracker.long_short_open_interest_diff();
+
+        let next_diff = if long == increase {
+            initial_diff + open_interest as i128
+        } else {
+            initial_diff - open_interest as i128
+        };
+
+        let same_side_rebalance =
+            (initial_diff > 0 && next_diff > 0) || (initial_diff < 0 && next_diff < 0);
+
+        if same_side_rebalance {
+            pricing_manager.get_price_impact_for_same_side_rebalance(
+                initial_diff.unsigned_abs(),
+                next_diff.unsigned_abs(),
+            )
+        } else {
+            pricing_manager.get_price_impact_for_crossover_rebalance(
+                initial_diff.unsigned_abs(),
+                next_diff.unsigned_abs(),
+            )
+        }
+    }
+
+    /// Get Execution Price
+    ///
+    /// applies the price impact of a trade to the price, a positive price impact gives a better price
+    /// and a negative price impact a worse price than the oracle price
+    /// @dev positive price impact is capped by the market's impact pool
+    ///
+    /// Params
+    /// Open Interest - the open interest added to or removed from the bias
+    /// Size - the value of the trade at the price (equal to open interest for opening)
+    /// Long - the bias direction
+    /// Increase - true for opening and false for closing
+    /// Price - the oracle price
+    ///
+    /// Returns the execution price and the price impact applied
+    pub fn get_execution_price(
+        &self,
+        open_interest: u128,
+        size: u128,
+        long: bool,
+        increase: bool,
+        price: u128,
+    ) -> (u128, i128) {
+        if size == 0 {
+            return (price, 0);
+        }
+
+        let price_impact = self
+            .calculate_price_impact(open_interest, long, increase)
+            .min(self.pricing_manager.impact_pool as i128);
+
+        // opening longs and closing shorts buy the index asset, a positive impact lowers the price paid
+        let buys = long == increase;
+
+        let size_after_impact = if buys {
+            size as i128 - price_impact
+        } else {
+            size as i128 + price_impact
+        };
+
+        let execution_price = mul_div(price, size_after_impact.max(0) as u128, size);
+
+        (execution_price, price_impact)
+    }
+}
@@ -21,6 +21,8 @@ impl MarketDetails {
         &mut self,
         params: RemoveLiquidityFromMarketParams,
     ) -> LiquidityOperationResult {
+        self.accrue_funding_and_borrowing_fees();
+
         let active_price = self.pricing_manager.get_price();
 
         self._remove_liquidity_from_market_with_price(params, active_price)
@@ -1,3 +1,4 @@
+use crate::constants::_ONE_SECOND;
 use crate::market::components::bias::Bias;
 use crate::market::components::funding_state::FundingState;
 use crate::market::market_details::MarketDetails;
@@ -35,37 +36,42 @@ impl MarketDetails {
 
         let duration = duration_in_secs(last_time_updated) as u128;
 
+        // moved forward by whole seconds only so fractions of a second are paid on the next settlement
+        let next_time_updated = last_time_updated + duration as u64 * _ONE_SECOND;
+
         let majority_funding_factor = current_funding_factor_ps * duration as i128;
 
         let long_open_interest = longs.traders_open_interest();
 
         let short_open_interest = shorts.traders_open_interest();
 
-        if current_funding_factor_ps == 0 || long_open_interest == 0 || short_open_interest == 0 {
+        if long_open_interest == 0 || short_open_interest == 0 {
+            // funding is only paid with open interest on both sides
+            funding_state.last_time_updated = next_time_updated;
             return;
         }
 
-        if current_funding_factor_ps <= 0 {
+        if current_funding_factor_ps < 0 {
             // shorts pay long
 
             shorts.update_cumulative_funding_factor(majority_funding_factor);
 
             // long fee = (majority funding * short open interest)/ long_open_interest
             let longs_funding_factor = mul_div(
-                majority_funding_factor.abs() as u128,
+                majority_funding_factor.unsigned_abs(),
                 short_open_interest,
                 long_open_interest,
             );
             //
 
             longs.update_cumulative_funding_factor(longs_funding_factor as i128);
-        } else {
+        } else if current_funding_factor_ps > 0 {
             //longs pay short
             longs.update_cumulative_funding_factor(majority_funding_factor.neg());
 
             // short fee  = (majority_funding_fee * long open interest) / short_open_interest
             let shorts_funding_factor = mul_div(
-                majority_funding_factor.abs() as u128,
+                majority_funding_factor.unsigned_abs(),
                 long_open_interest,
                 short_open_interest,
             );
@@ -76,7 +82,12 @@ impl MarketDetails {
 
         let current_total_open_interest = long_open_interest + short_open_interest;
 
-        funding_state
-            ._update_funding_factor_ps(current_long_short_diff, current_total_open_interest);
+        funding_state._update_funding_factor_ps(
+            current_long_short_diff,
+            current_total_open_interest,
+            duration,
+        );
+
+        funding_state.last_time_updated = next_time_updated;
     }
 }
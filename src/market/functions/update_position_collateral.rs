@@ -0,0 +1,219 @@
+use crate::market::components::bias::UpdateBiasDetailsParamters;
+use crate::market::components::liquidity_state::HouseLiquidityState;
+use crate::market::functions::open_position_in_market::{
+    FailureReason, OpenPositioninMarketResult,
+};
+use crate::market::market_details::MarketDetails;
+use crate::math::math::{FLOAT_PRECISION, Neg, to_precision};
+use crate::position::position_details::PositionDetails;
+
+impl MarketDetails {
+    pub fn add_collateral_to_position(
+        &mut self,
+        position: PositionDetails,
+        amount: u128,
+    ) -> OpenPositioninMarketResult {
+        self.accrue_funding_and_borrowing_fees();
+
+        let active_price = self.pricing_manager.get_price();
+
+        self._add_collateral_to_position_with_price(position, amount, active_price)
+    }
+
+    /// Add Collateral To Position
+    ///
+    /// the added collateral repays the position's debt first so its open interest (and pnl) is unchanged
+    /// while its leverage is reduced, the repaid debt is returned to the free liquidity
+    ///
+    /// the amount left once the debt is repaid is added to the position at 1x leverage
+    /// (its open interest is always its collateral and debt) with the position's reserve factor,
+    /// settling the fees accrued by the position as an increase does
+    /// @dev only needs a price when the amount exceeds the debt, so positions close to liquidation
+    /// can always be rescued by repaying their debt
+    pub fn _add_collateral_to_position_with_price(
+        &mut self,
+        position: PositionDetails,
+        amount: u128,
+        active_price: Option<u128>,
+    ) -> OpenPositioninMarketResult {
+        if amount == 0 {
+            return OpenPositioninMarketResult::Failed {
+                reason: FailureReason::Other,
+            };
+        }
+
+        let repaid_debt = amount.min(position.debt);
+        let remaining_amount = amount - repaid_debt;
+
+        let position = if remaining_amount == 0 {
+            position
+        } else {
+            let Some(price) = active_price else {
+                return OpenPositioninMarketResult::Waiting { id: None };
+            };
+
+            let PositionDetails {
+                long, max_reserve, ..
+            } = position;
+
+            // no price limit, the amount is added at the price after its price impact
+            let acceptable_price_limit = if long { u128::MAX } else { 0 };
+
+            match self._increase_position_at_price(
+                position,
+                remaining_amount,
+                FLOAT_PRECISION,
+                to_precision(max_reserve, position.open_interest()),
+                acceptable_price_limit,
+                price,
+            ) {
+                OpenPositioninMarketResult::Settled { position, .. } => position,
+                result => return result,
+            }
+        };
+
+        let Self {
+            liquidity_state,
+            bias_tracker,
+            ..
+        } = self;
+
+        let HouseLiquidityState {
+            free_liquidity,
+            current_net_debt,
+            total_deposit,
+            ..
+        } = *liquidity_state;
+
+        *liquidity_state = HouseLiquidityState {
+            free_liquidity: free_liquidity + repaid_debt,
+            current_net_debt: current_net_debt - repaid_debt,
+            total_deposit: total_deposit + repaid_debt,
+            ..*liquidity_state
+        };
+
+        let params = UpdateBiasDetailsParamters {
+            delta_net_debt_of_traders: repaid_debt.neg(),
+            ..Default::default()
+        };
+
+        bias_tracker.update_bias_details(params, position.long);
+
+        OpenPositioninMarketResult::Settled {
+            position_id: None,
+            position: PositionDetails {
+                collateral: position.collateral + repaid_debt,
+                debt: position.debt - repaid_debt,
+                ..position
+            },
+            position_fee: 0,
+        }
+    }
+
+    pub fn remove_collateral_from_position(
+        &mut self,
+        position: PositionDetails,
+        amount: u128,
+    ) -> OpenPositioninMarketResult {
+        self.accrue_funding_and_borrowing_fees();
+
+        let active_price = self.pricing_manager.get_price();
+
+        self._remove_collateral_from_position_with_price(position, amount, active_price)
+    }
+
+    /// Remove Collateral From Position
+    ///
+    /// the removed collateral is borrowed from the free liquidity as debt so the position's open interest (and pnl)
+    /// is unchanged while its leverage is increased
+    ///
+    /// fails if the position's leverage (after the fees accrued) exceeds the market's max leverage
+    /// or the position becomes liquidatable at the current price
+    pub fn _remove_collateral_from_position_with_price(
+        &mut self,
+        position: PositionDetails,
+        amount: u128,
+        active_price: Option<u128>,
+    ) -> OpenPositioninMarketResult {
+        let Some(price) = active_price else {
+            return OpenPositioninMarketResult::Waiting { id: None };
+        };
+
+        let PositionDetails {
+            long,
+            collateral,
+            debt,
+            ..
+        } = position;
+
+        if amount == 0 || amount >= collateral || amount > self.liquidity_state.free_liquidity {
+            return OpenPositioninMarketResult::Failed {
+                reason: FailureReason::Other,
+            };
+        }
+
+        let updated_position = PositionDetails {
+            collateral: collateral - amount,
+            debt: debt + amount,
+            ..position
+        };
+
+        let current_cummulative_funding_factor =
+            self.get_cummulative_funding_factor_since_epoch(long);
+
+        let current_cummulative_borrowing_factor =
+            self.get_cummulative_borrowing_factor_since_epoch(long);
+
+        let current_collateral = updated_position.collateral as i128
+            + updated_position.get_net_funding_fee(current_cummulative_funding_factor)
+            - updated_position.get_net_borrowing_fee(current_cummulative_borrowing_factor) as i128;
+
+        if current_collateral <= 0
+            || to_precision(updated_position.open_interest(), current_collateral as u128)
+                > self.state.max_leverage_factor
+            || updated_position.is_liquidatable(
+                price,
+                current_cummulative_funding_factor,
+                current_cummulative_borrowing_factor,
+                self.liquidity_state.liquidation_factor,
+            )
+        {
+            return OpenPositioninMarketResult::Failed {
+                reason: FailureReason::Other,
+            };
+        }
+
+        let Self {
+            liquidity_state,
+            bias_tracker,
+            ..
+        } = self;
+
+        let HouseLiquidityState {
+            free_liquidity,
+            current_net_debt,
+            total_deposit,
+            ..
+        } = *liquidity_state;
+
+        *liquidity_state = HouseLiquidityState {
+            free_liquidity: free_liquidity - amount,
+            current_net_debt: current_net_debt + amount,
+            total_deposit: total_deposit - amount,
+            ..*liquidity_state
+        };
+
+        let params = UpdateBiasDetailsParamters {
+            delta_net_debt_of_traders: amount as i128,
+            ..Default::default()
+        };
+
+        bias_tracker.update_bias_details(params, long);
+
+        OpenPositioninMarketResult::Settled {
+            position_id: None,
+            position: updated_position,
+            position_fee: 0,
+        }
+    }
+}
@@ -0,0 +1,154 @@
+use std::borrow::Cow;
+
+use ic_stable_structures::storable::{Bound, Storable};
+use ic_stable_structures::{Memory, StableVec};
+use serde::{Deserialize, Serialize};
+
+use crate::market::components::bias::Bias;
+use crate::market::components::funding_state::FundingState;
+use crate::market::components::liquidity_state::HouseLiquidityState;
+use crate::market::components::pricing::PricingState;
+use crate::market::market_details::{MarketDetails, MarketState};
+use crate::market::market_status::MarketStatus;
+use crate::pricing_update_management::price_fetch::AssetPricingDetails;
+use crate::stable_memory::{LEGACY_MARKETS_LIST, MARKETS_LIST};
+
+/// Legacy Pricing State
+///
+/// PricingState before the impact pool was added
+#[cfg_attr(test, derive(Debug, PartialEq, Eq))]
+#[derive(Default, Deserialize, Serialize, Clone, Copy)]
+pub struct LegacyPricingState {
+    pub last_time_updated: u64,
+    pub price: u128,
+    pub price_impact_exponent_factor: u128,
+    pub positive_price_impact_factor: u128,
+    pub negative_price_impact_factor: u128,
+}
+
+/// Legacy Market State
+///
+/// MarketState before the liquidation and position fee factors were added
+#[cfg_attr(test, derive(Debug, PartialEq, Eq))]
+#[derive(Default, Deserialize, Serialize, Clone, Copy)]
+pub struct LegacyMarketState {
+    #[serde(rename = "maxLeverageFactor")]
+    pub max_leverage_factor: u128,
+    #[serde(rename = "maxReserveFactor")]
+    pub max_reserve_factor: u128,
+    #[serde(rename = "liquidationFactor")]
+    pub liquidation_factor: u128,
+}
+
+/// Legacy Market Details
+///
+/// MarketDetails as stored before the market status, the fee factors and the impact pool were added
+/// @dev only read in post_upgrade to migrate the markets, never written
+#[derive(Default, Deserialize, Serialize)]
+pub struct LegacyMarketDetails {
+    pub index_asset_pricing_details: AssetPricingDetails,
+    pub bias_tracker: Bias,
+    pub funding_state: FundingState,
+    pub pricing_manager: LegacyPricingState,
+    pub state: LegacyMarketState,
+    pub liquidity_state: HouseLiquidityState,
+}
+
+impl From<LegacyMarketDetails> for MarketDetails {
+    /// Migrates a legacy market
+    ///
+    /// @dev the fee factors are zero until set with updateMarketConfig, the impact pool starts empty
+    /// and the market is Active as every market was before the status was added
+    fn from(legacy: LegacyMarketDetails) -> Self {
+        let LegacyMarketDetails {
+            index_asset_pricing_details,
+            bias_tracker,
+            funding_state,
+            pricing_manager,
+            state,
+            liquidity_state,
+        } = legacy;
+
+        MarketDetails {
+            index_asset_pricing_details,
+            bias_tracker,
+            funding_state,
+            pricing_manager: PricingState {
+                last_time_updated: pricing_manager.last_time_updated,
+                price: pricing_manager.price,
+                price_impact_exponent_factor: pricing_manager.price_impact_exponent_factor,
+                positive_price_impact_factor: pricing_manager.positive_price_impact_factor,
+                negative_price_impact_factor: pricing_manager.negative_price_impact_factor,
+                impact_pool: 0,
+            },
+            state: MarketState {
+                max_leverage_factor: state.max_leverage_factor,
+                max_reserve_factor: state.max_reserve_factor,
+                liquidation_factor: state.liquidation_factor,
+                ..MarketState::default()
+            },
+            liquidity_state,
+            status: MarketStatus::Active,
+        }
+    }
+}
+
+/// Migrate Legacy Markets
+///
+/// moves every market stored with the legacy layout into MARKETS_LIST keeping its market index
+/// @dev called in post_upgrade, a no-op once the legacy markets were migrated
+pub fn migrate_legacy_markets() {
+    LEGACY_MARKETS_LIST.with_borrow(|legacy_markets| {
+        MARKETS_LIST.with_borrow(|markets| _migrate_legacy_markets(legacy_markets, markets))
+    });
+}
+
+/// Migrates the markets of legacy_markets into markets
+///
+/// @dev markets are only migrated into an empty list so the market indexes are unchanged,
+/// the legacy list is emptied afterwards so the migration runs once
+pub fn _migrate_legacy_markets<M: Memory>(
+    legacy_markets: &StableVec<LegacyMarketDetails, M>,
+    markets: &StableVec<MarketDetails, M>,
+) {
+    if legacy_markets.is_empty() {
+        return;
+    }
+
+    assert!(
+        markets.is_empty(),
+        "markets exist in both the legacy and the current layout"
+    );
+
+    for legacy_market in legacy_markets.iter() {
+        markets.push(&MarketDetails::from(legacy_market));
+    }
+
+    while legacy_markets.pop().is_some() {}
+}
+
+impl Storable for LegacyMarketDetails {
+    fn to_bytes(&self) -> Cow<'_, [u8]> {
+        let serialized = bincode::serialize(self).expect("failed to serialize");
+        Cow::Owned(serialized)
+    }
+
+    /// Converts the element into an owned byte vector.
+    ///
+    /// This method consumes `self` and avoids cloning when possible.
+    fn into_bytes(self) -> Vec<u8> {
+        bincode::serialize(&self).expect("failed to serialize")
+    }
+
+    /// Converts bytes into an element.
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        bincode::deserialize(bytes.as_ref()).expect("failed to desearalize")
+    }
+
+    /// The size bounds of the type.
+    /// @dev must stay the bound the legacy markets were stored with, StableVec rejects a different one
+    const BOUND: Bound = Bound::Bounded {
+        max_size: 800,
+        is_fixed_size: false,
+    };
+}
@@ -0,0 +1,244 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+
+use crate::market::market_details::{MarketDetails, MarketState};
+use crate::math::math::FLOAT_PRECISION;
+
+/// Market Config
+///
+/// every tunable parameter of a market, all factors use 20-decimal precision
+#[cfg_attr(test, derive(Debug, PartialEq, Eq))]
+#[derive(Default, Deserialize, Serialize, CandidType, Clone, Copy)]
+pub struct MarketConfig {
+    /// Max leverage of positions, above 1x
+    #[serde(rename = "maxLeverageFactor")]
+    pub max_leverage_factor: u128,
+    /// Max reserve factor of positions (max profit as a share of open interest)
+    #[serde(rename = "maxReserveFactor")]
+    pub max_reserve_factor: u128,
+    /// Share of a position's collateral below which its value makes it liquidatable, below 100%
+    #[serde(rename = "liquidationFactor")]
+    pub liquidation_factor: u128,
+    /// Share of a liquidated position's open interest paid to the liquidator
+    #[serde(rename = "liquidationRewardFactor")]
+    pub liquidation_reward_factor: u128,
+    /// Share of a liquidated position's open interest paid to the house
+    #[serde(rename = "liquidationFeeFactor")]
+    pub liquidation_fee_factor: u128,
+    /// Share of the open interest opened charged as position fee, below 100%
+    #[serde(rename = "openFeeFactor")]
+    pub open_fee_factor: u128,
+    /// Share of the open interest closed charged as position fee, below 100%
+    #[serde(rename = "closeFeeFactor")]
+    pub close_fee_factor: u128,
+    /// Share of position fees sent to the house treasury, at most 100%
+    #[serde(rename = "treasuryFeeFactor")]
+    pub treasury_fee_factor: u128,
+
+    #[serde(rename = "fundingFactor")]
+    pub funding_factor: u128,
+    /// Exponent applied to the open interest imbalance for funding, at least 1
+    #[serde(rename = "fundingExponentFactor")]
+    pub funding_exponent_factor: u128,
+    #[serde(rename = "minFundingFactorPs")]
+    pub min_funding_factor_ps: u128,
+    #[serde(rename = "maxFundingFactorPs")]
+    pub max_funding_factor_ps: u128,
+    #[serde(rename = "thresholdDecreaseFunding")]
+    pub threshold_decrease_funding: u128,
+    #[serde(rename = "thresholdStableFunding")]
+    pub threshold_stable_funding: u128,
+    /// Funding increase per second, zero for funding based only on the imbalance
+    #[serde(rename = "fundingIncreaseFactorPs")]
+    pub funding_increase_factor_ps: u128,
+    #[serde(rename = "fundingDecreaseFactorPs")]
+    pub funding_decrease_factor_ps: u128,
+
+    /// Share of the house value that can be reserved for longs, at most 100%
+    #[serde(rename = "longsMaxReserveFactor")]
+    pub longs_max_reserve_factor: u128,
+    /// Exponent applied to the longs reserve for borrowing, at least 1
+    #[serde(rename = "longsBorrowingExponentFactor")]
+    pub longs_borrowing_exponent_factor: u128,
+    #[serde(rename = "longsBaseBorrowingFactor")]
+    pub longs_base_borrowing_factor: u128,
+    /// Share of the house value that can be reserved for shorts, at most 100%
+    #[serde(rename = "shortsMaxReserveFactor")]
+    pub shorts_max_reserve_factor: u128,
+    /// Exponent applied to the shorts reserve for borrowing, at least 1
+    #[serde(rename = "shortsBorrowingExponentFactor")]
+    pub shorts_borrowing_exponent_factor: u128,
+    #[serde(rename = "shortsBaseBorrowingFactor")]
+    pub shorts_base_borrowing_factor: u128,
+
+    /// Exponent applied to the open interest imbalance for price impact, at least 1
+    #[serde(rename = "priceImpactExponentFactor")]
+    pub price_impact_exponent_factor: u128,
+    /// Price impact factor for trades that reduce the imbalance, at most the negative factor
+    #[serde(rename = "positivePriceImpactFactor")]
+    pub positive_price_impact_factor: u128,
+    /// Price impact factor for trades that increase the imbalance
+    #[serde(rename = "negativePriceImpactFactor")]
+    pub negative_price_impact_factor: u128,
+}
+
+impl MarketConfig {
+    /// Validate
+    ///
+    /// checks the bounds of every parameter and the consistency between parameters
+    ///
+    /// Returns the reason of the first failed check
+    pub fn validate(&self) -> Result<(), String> {
+        let checks = [
+            (
+                self.max_leverage_factor > FLOAT_PRECISION,
+                "Max leverage factor must be above 1x",
+            ),
+            (
+                self.max_reserve_factor > 0,
+                "Max reserve factor must be above 0",
+            ),
+            (
+                self.liquidation_factor < FLOAT_PRECISION,
+                "Liquidation factor must be below 100%",
+            ),
+            (
+                self.liquidation_reward_factor + self.liquidation_fee_factor < FLOAT_PRECISION,
+                "Liquidation reward and fee factors must be below 100%",
+            ),
+            (
+                self.open_fee_factor < FLOAT_PRECISION && self.close_fee_factor < FLOAT_PRECISION,
+                "Position fee factors must be below 100%",
+            ),
+            (
+                self.treasury_fee_factor <= FLOAT_PRECISION,
+                "Treasury fee factor must be at most 100%",
+            ),
+            (
+                self.funding_exponent_factor >= FLOAT_PRECISION,
+                "Funding exponent factor must be at least 1",
+            ),
+            (
+                self.min_funding_factor_ps <= self.max_funding_factor_ps,
+                "Min funding factor must be at most max funding factor",
+            ),
+            (
+                self.threshold_decrease_funding <= self.threshold_stable_funding,
+                "Decrease funding threshold must be at most stable funding threshold",
+            ),
+            (
+                self.longs_max_reserve_factor <= FLOAT_PRECISION
+                    && self.shorts_max_reserve_factor <= FLOAT_PRECISION,
+                "Max reserve factors of biases must be at most 100%",
+            ),
+            (
+                self.longs_borrowing_exponent_factor >= FLOAT_PRECISION
+                    && self.shorts_borrowing_exponent_factor >= FLOAT_PRECISION,
+                "Borrowing exponent factors must be at least 1",
+            ),
+            (
+                self.price_impact_exponent_factor >= FLOAT_PRECISION,
+                "Price impact exponent factor must be at least 1",
+            ),
+            (
+                self.positive_price_impact_factor <= self.negative_price_impact_factor,
+                "Positive price impact factor must be at most negative price impact factor",
+            ),
+        ];
+
+        match checks.into_iter().find(|(valid, _)| !valid) {
+            Some((_, reason)) => Err(reason.to_string()),
+            None => Ok(()),
+        }
+    }
+}
+
+impl MarketDetails {
+    pub fn config(&self) -> MarketConfig {
+        let Self {
+            state,
+            funding_state,
+            liquidity_state,
+            bias_tracker,
+            pricing_manager,
+            ..
+        } = self;
+
+        MarketConfig {
+            max_leverage_factor: state.max_leverage_factor,
+            max_reserve_factor: state.max_reserve_factor,
+            liquidation_factor: liquidity_state.liquidation_factor,
+            liquidation_reward_factor: state.liquidation_reward_factor,
+            liquidation_fee_factor: state.liquidation_fee_factor,
+            open_fee_factor: state.open_fee_factor,
+            close_fee_factor: state.close_fee_factor,
+            treasury_fee_factor: state.treasury_fee_factor,
+            funding_factor: funding_state.funding_factor,
+            funding_exponent_factor: funding_state.funding_exponent_factor,
+            min_funding_factor_ps: funding_state.min_funding_factor_ps,
+            max_funding_factor_ps: funding_state.max_funding_factor_ps,
+            threshold_decrease_funding: funding_state.threshold_decrease_funding,
+            threshold_stable_funding: funding_state.threshold_stable_funding,
+            funding_increase_factor_ps: funding_state.funding_increase_factor_ps,
+            funding_decrease_factor_ps: funding_state.funding_decrease_factor_ps,
+            longs_max_reserve_factor: liquidity_state.longs_max_reserve_factor,
+            longs_borrowing_exponent_factor: bias_tracker.longs.borrowing_exponent_factor_,
+            longs_base_borrowing_factor: bias_tracker.longs.base_borrowing_factor,
+            shorts_max_reserve_factor: liquidity_state.shorts_max_reserve_factor,
+            shorts_borrowing_exponent_factor: bias_tracker.shorts.borrowing_exponent_factor_,
+            shorts_base_borrowing_factor: bias_tracker.shorts.base_borrowing_factor,
+            price_impact_exponent_factor: pricing_manager.price_impact_exponent_factor,
+            positive_price_impact_factor: pricing_manager.positive_price_impact_factor,
+            negative_price_impact_factor: pricing_manager.negative_price_impact_factor,
+        }
+    }
+
+    /// Apply Config
+    ///
+    /// sets every tunable parameter of the market
+    /// @dev the config should be validated first
+    pub fn apply_config(&mut self, config: MarketConfig) {
+        let Self {
+            state,
+            funding_state,
+            liquidity_state,
+            bias_tracker,
+            pricing_manager,
+            ..
+        } = self;
+
+        *state = MarketState {
+            max_leverage_factor: config.max_leverage_factor,
+            max_reserve_factor: config.max_reserve_factor,
+            liquidation_factor: config.liquidation_factor,
+            liquidation_reward_factor: config.liquidation_reward_factor,
+            liquidation_fee_factor: config.liquidation_fee_factor,
+            open_fee_factor: config.open_fee_factor,
+            close_fee_factor: config.close_fee_factor,
+            treasury_fee_factor: config.treasury_fee_factor,
+        };
+
+        funding_state.funding_factor = config.funding_factor;
+        funding_state.funding_exponent_factor = config.funding_exponent_factor;
+        funding_state.min_funding_factor_ps = config.min_funding_factor_ps;
+        funding_state.max_funding_factor_ps = config.max_funding_factor_ps;
+        funding_state.threshold_decrease_funding = config.threshold_decrease_funding;
+        funding_state.threshold_stable_funding = config.threshold_stable_funding;
+        funding_state.funding_increase_factor_ps = config.funding_increase_factor_ps;
+        funding_state.funding_decrease_factor_ps = config.funding_decrease_factor_ps;
+
+        // liquidations are checked against the liquidation factor of the liquidity state
+        liquidity_state.liquidation_factor = config.liquidation_factor;
+        liquidity_state.longs_max_reserve_factor = config.longs_max_reserve_factor;
+        liquidity_state.shorts_max_reserve_factor = config.shorts_max_reserve_factor;
+
+        bias_tracker.longs.borrowing_exponent_factor_ = config.longs_borrowing_exponent_factor;
+        bias_tracker.longs.base_borrowing_factor = config.longs_base_borrowing_factor;
+        bias_tracker.shorts.borrowing_exponent_factor_ = config.shorts_borrowing_exponent_factor;
+        bias_tracker.shorts.base_borrowing_factor = config.shorts_base_borrowing_factor;
+
+        pricing_manager.price_impact_exponent_factor = config.price_impact_exponent_factor;
+        pricing_manager.positive_price_impact_factor = config.positive_price_impact_factor;
+        pricing_manager.negative_price_impact_factor = config.negative_price_impact_factor;
+    }
+}
@@ -9,13 +9,15 @@ use crate::market::components::bias::Bias;
 use crate::market::components::funding_state::FundingState;
 use crate::market::components::liquidity_state::HouseLiquidityState;
 use crate::market::components::pricing::PricingState;
+use crate::market::market_status::MarketStatus;
 use crate::math::math::to_precision;
 use crate::pricing_update_management::price_fetch::AssetPricingDetails;
 
+#[cfg_attr(test, derive(Debug, PartialEq, Eq))]
 #[derive(CandidType, Deserialize)]
 pub enum LiquidityOperationResult {
     Settled { amount_out: u128 },
-    Waiting { id: Option<(u64, u8, u64)> }, // market index ,priority index,operation id
+    Waiting { id: Option<u64> }, // operation ticket
     Failed(String),
 }
 
@@ -28,6 +30,31 @@ pub struct MarketState {
     pub max_reserve_factor: u128,
     #[serde(rename = "liquidationFactor")]
     pub liquidation_factor: u128,
+    /// Liquidation Reward Factor
+    ///
+    /// share of a liquidated position's open interest paid to the liquidator
+    #[serde(rename = "liquidationRewardFactor")]
+    pub liquidation_reward_factor: u128,
+    /// Liquidation Fee Factor
+    ///
+    /// share of a liquidated position's open interest paid to the house
+    #[serde(rename = "liquidationFeeFactor")]
+    pub liquidation_fee_factor: u128,
+    /// Open Fee Factor
+    ///
+    /// share of the open interest added by opening or increasing a position charged as position fee
+    #[serde(rename = "openFeeFactor")]
+    pub open_fee_factor: u128,
+    /// Close Fee Factor
+    ///
+    /// share of the open interest removed by closing a position charged as position fee
+    #[serde(rename = "closeFeeFactor")]
+    pub close_fee_factor: u128,
+    /// Treasury Fee Factor
+    ///
+    /// share of position fees sent to the house treasury, the rest is paid to the market's liquidity providers
+    #[serde(rename = "treasuryFeeFactor")]
+    pub treasury_fee_factor: u128,
 }
 
 #[cfg_attr(test, derive(Debug, Clone, PartialEq, Eq))]
@@ -40,6 +67,8 @@ pub struct MarketDetails {
     pub pricing_manager: PricingState,
     pub state: MarketState,
     pub liquidity_state: HouseLiquidityState,
+    /// Activity accepted by the market, set by the pauser
+    pub status: MarketStatus,
 }
 
 impl MarketDetails {
@@ -61,11 +90,11 @@ impl MarketDetails {
     /// the current borrow fees owed
     pub fn _house_value(&self, price: u128) -> u128 {
         let house_value = max(
-            self.liquidity_state.static_value() as i128 + self.bias_tracker.net_house_pnl(price),
+            self.liquidity_state.static_value() + self.bias_tracker.net_house_pnl(price),
             0,
         );
 
-        return house_value as u128;
+        house_value as u128
     }
     pub fn index_asset_pricing_details(&self) -> AssetPricingDetails {
         self.index_asset_pricing_details.clone()
@@ -90,43 +119,6 @@ impl MarketDetails {
             shorts.cummulative_borrowing_factor_since_epcoh()
         }
     }
-
-    // Calculates the Price imapct for opening a position
-    //
-    // checks if position opening is chnages the currentr skew direction i.e (longs greater than shorts )
-    // then calls pricing manager to get price impact fee ,if pricing impact
-    // fn calculate_price_impact_open_position(&self, open_interest: u128) -> i128 {
-    //     let Self {
-    //         bias_tracker,
-    //         pricing_manager,
-    //         ..
-    //     } = self;
-    //     let current_net_long_open_interest = bias_tracker.long.traders_open_interest();
-    //     let current_net_short_open_interest = bias_tracker.short.traders_open_interest();
-
-    //     let initial_diff = bias_tracker.long_short_open_interest_diff();
-
-    //     let next_diff = (current_net_long_open_interest + open_interest) as i128
-    //         - current_net_short_open_interest as i128;
-
-    //     let same_side_rebalance =
-    //         (initial_diff > 0 && next_diff > 0) || (initial_diff < 0 && next_diff < 0);
-    //     let price_impact_fee;
-    //     if same_side_rebalance {
-    //         price_impact_fee = pricing_manager.get_price_impact_for_same_side_rebalance(
-    //             initial_diff.abs() as u128,
-    //             next_diff.abs() as u128,
-    //         );
-    //     } else {
-    //         price_impact_fee = pricing_manager.get_price_impact_for_crossover_rebalance(
-    //             initial_diff.abs() as u128,
-    //             next_diff.abs() as u128,
-    //         );
-    //     }
-    //     return price_impact_fee;
-    // }
-
-    //price impact = (initial USD difference) ^ (price impact exponent) * (price impact factor) - (next USD difference) ^ (price impact exponent) * (price impact factor)
 }
 
 impl Storable for MarketDetails {
@@ -149,7 +141,7 @@ impl Storable for MarketDetails {
 
     /// The size bounds of the type.
     const BOUND: Bound = Bound::Bounded {
-        max_size: 800,
+        max_size: 2048,
         is_fixed_size: false,
     };
 }
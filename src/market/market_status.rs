@@ -0,0 +1,62 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+
+use crate::house_settings::is_paused;
+use crate::stable_memory::MARKETS_LIST;
+
+/// Market Status
+///
+/// the activity accepted by a market, from the least to the most restrictive
+/// @dev liquidations are accepted in every status so the house is not exposed to undercollateralised positions
+#[cfg_attr(test, derive(Debug))]
+#[derive(
+    Default, Deserialize, Serialize, CandidType, Clone, Copy, PartialEq, Eq, PartialOrd, Ord,
+)]
+pub enum MarketStatus {
+    /// Accepts every operation
+    #[default]
+    Active,
+    /// Accepts closing positions and removing liquidity only
+    CloseOnly,
+    /// Rejects opening and closing positions and adding and removing liquidity
+    Paused,
+    /// The final settlement price was fixed and every position is (being) settled at it,
+    /// only liquidity redemptions are accepted once the final settlement is completed
+    Delisted,
+}
+
+impl MarketStatus {
+    /// Allows Opening
+    ///
+    /// true if positions can be opened or increased and liquidity can be added
+    pub fn allows_opening(&self) -> bool {
+        *self == MarketStatus::Active
+    }
+
+    /// Allows Closing
+    ///
+    /// true if positions can be closed and liquidity can be removed
+    pub fn allows_closing(&self) -> bool {
+        matches!(self, MarketStatus::Active | MarketStatus::CloseOnly)
+    }
+}
+
+/// Market Accepts
+///
+/// true if the protocol is not paused and the market's status allows opening (`opening`) or closing
+/// @dev used by orders, which are checked when they are created and again when they are executed
+pub fn market_accepts(market_index: u64, opening: bool) -> bool {
+    if is_paused() {
+        return false;
+    }
+
+    MARKETS_LIST.with_borrow(|reference| {
+        reference.get(market_index).is_some_and(|market| {
+            if opening {
+                market.status.allows_opening()
+            } else {
+                market.status.allows_closing()
+            }
+        })
+    })
+}
@@ -1,4 +1,7 @@
 pub mod components;
 pub mod functions;
+pub mod legacy_market_details;
+pub mod market_config;
 pub mod market_details;
+pub mod market_status;
 pub mod query_utils;
@@ -1,11 +1,18 @@
+use crate::market::market_config::MarketConfig;
 use crate::market::market_details::MarketDetails;
 use crate::stable_memory::MARKETS_LIST;
 
 #[ic_cdk::query]
 pub fn get_market_details(market_index: u64) -> MarketDetails {
+    MARKETS_LIST
+        .with_borrow(|reference| reference.get(market_index).expect("Market does not exist"))
+}
+
+#[ic_cdk::query(name = "getMarketConfig")]
+pub fn get_market_config(market_index: u64) -> MarketConfig {
     MARKETS_LIST.with_borrow(|reference| {
         let market = reference.get(market_index).expect("Market does not exist");
-        market
+        market.config()
     })
 }
 
@@ -1,9 +1,9 @@
 use primitive_types::{U256, U512};
 
-/// float math library for exponention in float number
-///
-/// This is based on PRB math floating point arihtmetic library here https://github.com/PaulRBerg/prb-math/tree/main/src
-/// follows an unsigned 60.18-decimal fixed point number arithmeitc for operation   
+// float math library for exponention in float number
+//
+// This is based on PRB math floating point arihtmetic library here https://github.com/PaulRBerg/prb-math/tree/main/src
+// follows an unsigned 60.18-decimal fixed point number arithmeitc for operation
 
 const ZERO: U256 = U256([0, 0, 0, 0]);
 const UNIT: U256 = U256([1_000_000_000_000_000_000u64, 0, 0, 0]); //1e18
@@ -17,7 +17,6 @@ const HALF_UNIT: U256 = U256([500_000_000_000_000_000u64, 0, 0, 0]);
 /// @param x The exponent as an unsigned 192.64-bit fixed-point number.
 /// @return result The result as an unsigned 60.18-decimal fixed-point number.
 /// @custom:smtchecker abstract-function-nondet
-
 pub fn exp192x64(x: U256) -> U256 {
     // Start from 0.5 in the 192.64-bit fixed-point format.
     let mut result = U256::from(1) << 191;
@@ -161,9 +160,9 @@ pub fn exp192x64(x: U256) -> U256 {
     result *= UNIT; // 1e18
     // Shift right by (191 - x >> 64) to adjust for the exponent
     let shift = 191u32 - ((x >> 64).as_u32());
-    result = result >> shift;
+    result >>= shift;
     // Scale to 60.18-decimal fixed-point format
-    return result;
+    result
 }
 
 /// @notice Calculates the binary exponent of x using the binary fraction method.
@@ -187,7 +186,7 @@ pub fn exp2(x: U256) -> U256 {
     print!("the result of exp2 in 192.64 is {}", exp192x64(x_192x64));
 
     // Pass x to the {Common.exp2} function, which uses the 192.64-bit fixed-point number representation.
-    return exp192x64(x_192x64);
+    exp192x64(x_192x64)
 }
 
 /// @notice Calculates the binary logarithm of x using the iterative approximation algorithm:
@@ -213,7 +212,6 @@ pub fn exp2(x: U256) -> U256 {
 /// @param x The UD60x18 number for which to calculate the binary logarithm.
 /// @return result The binary logarithm as a UD60x18 number.
 /// @custom:smtchecker abstract-function-nondet
-
 fn log2(x: U256) -> U256 {
     assert!(x >= UNIT);
 
@@ -250,7 +248,7 @@ fn log2(x: U256) -> U256 {
         delta >>= 1
     }
 
-    return result;
+    result
 }
 
 /// @notice Raises x to the power of y.
@@ -281,7 +279,6 @@ fn log2(x: U256) -> U256 {
 /// @param y The exponent as a UD60x18 number.
 /// @return result The result as a UD60x18 number.
 /// @custom:smtchecker abstract-function-nondet
-
 pub fn pow(x: U256, y: U256) -> U256 {
     // If both x and y are zero, the result is `UNIT`. If just x is zero, the result is always zero.
     if x == ZERO {
@@ -304,13 +301,13 @@ pub fn pow(x: U256, y: U256) -> U256 {
     // If x is > UNIT, use the standard formula.
 
     if x > UNIT {
-        return exp2(mulldiv18(log2(x), y));
+        exp2(mulldiv18(log2(x), y))
     }
     // Conversely, if x < UNIT, use the equivalent formula.
     else {
         let i = UNIT_SQUARED / x;
         let w = exp2(mulldiv18(log2(i), y));
-        return UNIT_SQUARED / w;
+        UNIT_SQUARED / w
     }
 }
 
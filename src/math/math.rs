@@ -28,7 +28,7 @@ impl Neg for u128 {
 }
 
 pub fn apply_precision(value: u128, factor: u128) -> u128 {
-    return mul_div(value, factor, FLOAT_PRECISION);
+    mul_div(value, factor, FLOAT_PRECISION)
 }
 
 // #[test]
@@ -57,23 +57,21 @@ pub fn apply_exponent(value: u128, exponent: u128) -> u128 {
         U256::from(float_to_u60x18(exponent)),
     );
 
-    let result = u60x18_to_float(u256_to_u128_native(x));
-    return result;
+    u60x18_to_float(u256_to_u128_native(x))
 }
 
 pub fn to_precision(value: u128, factor: u128) -> u128 {
-    return mul_div(value, FLOAT_PRECISION, factor);
+    mul_div(value, FLOAT_PRECISION, factor)
 }
 
 /// Bound magnitude signed
 ///
 /// sets a lower and an upper bound for the magnitude of a signed integer
-
 pub fn bound_magnitude_signed(value: i128, min: u128, max: u128) -> i128 {
-    let magnitude = bound_unsigned(value.abs() as u128, min, max);
+    let magnitude = bound_unsigned(value.unsigned_abs(), min, max);
 
     let sign = if value == 0 { 1 } else { value / value.abs() };
-    return magnitude as i128 * sign;
+    magnitude as i128 * sign
 }
 
 /// Bound signed
@@ -112,8 +110,7 @@ pub fn bound_unsigned(value: u128, min: u128, max: u128) -> u128 {
 
 /// Percentage Functions
 ///
-/// These functions  calculates percentages  
-
+/// These functions  calculates percentages
 pub fn _percentage<T>(x: u64, value: T) -> T
 where
     T: std::ops::Mul<Output = T> + std::ops::Div<Output = T> + From<u64>,
@@ -122,7 +119,7 @@ where
 }
 
 pub fn diff(a: u128, b: u128) -> u128 {
-    if a > b { a - b } else { b - a }
+    a.abs_diff(b)
 }
 
 fn float_to_u60x18(value: u128) -> u128 {
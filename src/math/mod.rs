@@ -1,2 +1,3 @@
 pub mod floatmath;
+#[allow(clippy::module_inception)]
 pub mod math;
@@ -1,2 +1,3 @@
+#[allow(clippy::module_inception)]
 pub mod open_position;
 pub mod open_position_params;
@@ -1,18 +1,22 @@
 use crate::constants::OPEN_POSITION_PRIORITY_INDEX;
-use crate::house_settings::{get_execution_fee, update_execution_fees_accumulated};
+use crate::events::event_log::record_event;
+use crate::events::evnts_type::Events;
+use crate::house_settings::{
+    get_execution_fee, is_paused, update_execution_fees_accumulated,
+    update_position_fees_acccumulated,
+};
 use crate::market::functions::open_position_in_market::{
     FailureReason, OpenPositioninMarketResult,
 };
+use crate::math::math::apply_precision;
 use crate::open_position::open_position_params::OpenPositionParams;
 use crate::pricing_update_management::price_waiting_operation_trait::PriceWaitingOperation;
-use crate::pricing_update_management::price_waiting_operation_utils::{
-    is_within_price_update_interval, put_price_waiting_operation,
-};
+use crate::pricing_update_management::price_waiting_operation_utils::put_price_waiting_operation;
 use crate::stable_memory::MARKETS_LIST;
 use crate::user::balance_utils::{get_user_balance, set_user_balance};
-use crate::user::position_util::_put_user_position_detail;
+use crate::user::position_util::{_next_user_position_id, _put_user_position_detail};
 
-use ic_cdk::api::{msg_caller, time};
+use ic_cdk::api::msg_caller;
 use ic_cdk::update;
 
 /// Opens a new trading position in a specific market.
@@ -35,16 +39,18 @@ use ic_cdk::update;
 /// # Returns
 ///
 /// Returns [`OpenPositioninMarketResult`] which can be:
-/// - `Settled { position }`: Successfully opened position, returns position details
-/// - `Waiting`: Operation queued due to stale price data, will execute when price updates
-/// - `Failed { reason }`: Operation failed with specific reason (InsufficientBalance, PriceLimitExceeded, Other)
+/// - `Settled { position_id, position, position_fee }`: Successfully opened position, returns position ID, details
+///   and the position fee (open fee factor of the position's open interest) taken from the user's balance
+/// - `Waiting { id }`: Operation queued due to stale price data, will execute when price updates,
+///   `id` is the ticket for querying the operation status
+/// - `Failed { reason }`: Operation failed with specific reason (InsufficientBalance, PriceLimitExceeded, MarketNotActive, Other)
 ///
 /// # Security Notes
 ///
 /// - **Caller Verification**: The `owner` parameter must match the message caller (`msg_caller()`)
 ///   to prevent unauthorized position creation
-/// - **Balance Check**: User must have sufficient balance to cover both the collateral amount
-///   and the execution fee
+/// - **Balance Check**: User must have sufficient balance to cover the collateral amount,
+///   the execution fee and the position fee
 /// - **Execution Fee**: Deducted in quote asset from the user's balance
 ///
 /// # Price Update Handling
@@ -64,14 +70,15 @@ use ic_cdk::update;
 ///     leverage_factor: 10000000000000000000000, // 10x leverage with 20 decimal places
 ///     acceptable_price_limit: 2000000000000000000000, // 2.0 units max price with 20 decimal places
 ///     reserve_factor: 500000000000000000000, // 0.05 units reserve with 20 decimal places
+///     deadline: None, // fail if still waiting for a price update at this time
 /// };
 ///
 /// let result = open_position(params);
 /// match result {
-///     OpenPositioninMarketResult::Settled { position } => {
-///         // Successfully opened position, received position details
+///     OpenPositioninMarketResult::Settled { position_id, position, position_fee } => {
+///         // Successfully opened position, received position ID and details
 ///     },
-///     OpenPositioninMarketResult::Waiting => {
+///     OpenPositioninMarketResult::Waiting { id } => {
 ///         // Operation queued, will execute when price updates
 ///     },
 ///     OpenPositioninMarketResult::Failed { reason } => {
@@ -88,15 +95,17 @@ pub fn open_position(params: OpenPositionParams) -> OpenPositioninMarketResult {
     );
     let result = _open_position(&params);
 
-    if let OpenPositioninMarketResult::Waiting = result {
-        put_price_waiting_operation(
+    if let OpenPositioninMarketResult::Waiting { id: _ } = result {
+        let ticket = put_price_waiting_operation(
             params.market_index,
             OPEN_POSITION_PRIORITY_INDEX,
             PriceWaitingOperation::from(params),
         );
+
+        return OpenPositioninMarketResult::Waiting { id: Some(ticket) };
     };
 
-    return result;
+    result
 }
 
 /// Internal implementation of the open position functionality.
@@ -118,7 +127,8 @@ pub fn open_position(params: OpenPositionParams) -> OpenPositioninMarketResult {
 /// # Implementation Details
 ///
 /// The function:
-/// 1. Validates user has sufficient balance (collateral + execution fee)
+/// 1. Validates user has sufficient balance (collateral + execution fee + position fee)
+///    and the protocol is not paused and the market is active
 /// 2. Checks if market price data is current
 /// 3. If price is stale, returns `Waiting` to queue the operation
 /// 4. If price is current, executes the position opening
@@ -144,21 +154,60 @@ pub fn _open_position(params: &OpenPositionParams) -> OpenPositioninMarketResult
             .get(params.market_index)
             .expect("Market does not exist");
 
+        if is_paused() || !market.status.allows_opening() {
+            return OpenPositioninMarketResult::Failed {
+                reason: FailureReason::MarketNotActive,
+            };
+        }
+
+        let position_fee = market.get_position_fee(
+            apply_precision(params.leverage_factor, params.collateral),
+            true,
+        );
+
+        if trader_balance < params.collateral + execution_fee + position_fee {
+            return OpenPositioninMarketResult::Failed {
+                reason: FailureReason::InsufficientBalance,
+            };
+        }
+
         let result = market.open_position_in_market(*params);
 
         // if it was settled we need to update the user position and balance
-        if let OpenPositioninMarketResult::Settled { position } = result {
-            set_user_balance(trader, trader_balance - (params.collateral + execution_fee));
+        if let OpenPositioninMarketResult::Settled { position, .. } = result {
+            set_user_balance(
+                trader,
+                trader_balance - (params.collateral + execution_fee + position_fee),
+            );
 
             // take excution fee
             update_execution_fees_accumulated(execution_fee, true);
 
-            let position_id = time();
+            // take position fee
+            let treasury_fee = market.collect_position_fee(position_fee);
+            update_position_fees_acccumulated(treasury_fee, true);
+
+            let position_id = _next_user_position_id(trader);
             _put_user_position_detail(trader, params.market_index, position_id, position);
 
             reference.set(params.market_index, &market);
+
+            record_event(Events::OpenPosition {
+                owner: trader,
+                market_index: params.market_index,
+                position_id,
+                position,
+                price: market.pricing_manager.price,
+                position_fee,
+            });
+
+            return OpenPositioninMarketResult::Settled {
+                position_id: Some(position_id),
+                position,
+                position_fee,
+            };
         };
 
-        return result;
+        result
     })
 }
@@ -1,18 +1,20 @@
 use candid::{CandidType, Principal};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{
     open_position::open_position::_open_position,
-    pricing_update_management::price_waiting_operation_trait::{
-        PriceWaitingOperation, PriceWaitingOperationTrait,
+    pricing_update_management::{
+        operation_status::OperationStatus,
+        price_waiting_operation_trait::{PriceWaitingOperation, PriceWaitingOperationTrait},
     },
+    user::balance_utils::update_user_balance,
 };
 /// Parameters for opening a new trading position in a market.
 ///
 /// This struct contains all the necessary information to open a leveraged trading position
 /// in a specific market. The owner must be the message caller to ensure security.
 #[cfg_attr(test, derive(Debug, PartialEq, Eq))]
-#[derive(CandidType, Clone, Copy, Deserialize)]
+#[derive(CandidType, Clone, Copy, Deserialize, Serialize)]
 pub struct OpenPositionParams {
     /// The principal ID of the position owner.
     /// **IMPORTANT**: This must match the message caller (`msg_caller()`) for security.
@@ -52,11 +54,34 @@ pub struct OpenPositionParams {
     /// This factor is used in risk calculations and position sizing.
     #[serde(rename = "reserveFactor")]
     pub reserve_factor: u128,
+
+    /// Optional deadline for the operation, in nanoseconds since the UNIX epoch.
+    /// If the operation is queued waiting for a price update and is not executed by this time,
+    /// it is failed and its reserved amounts are released.
+    /// Without a deadline, a queued operation fails if the next price update fails.
+    pub deadline: Option<u64>,
 }
 
 impl PriceWaitingOperationTrait for OpenPositionParams {
-    fn execute(&self) {
-        _open_position(self);
+    fn execute(&self) -> OperationStatus {
+        _open_position(self).into()
+    }
+
+    fn deadline(&self) -> Option<u64> {
+        self.deadline
+    }
+
+    fn executor(&self) -> Option<Principal> {
+        Some(self.owner)
+    }
+
+    /// reserves the collateral, the execution fee is taken on execution
+    fn reserve(&self) {
+        update_user_balance(self.owner, self.collateral, false);
+    }
+
+    fn release(&self) {
+        update_user_balance(self.owner, self.collateral, true);
     }
 }
 
@@ -0,0 +1,48 @@
+use ic_cdk::{api::msg_caller, update};
+
+use crate::events::event_log::record_event;
+use crate::events::evnts_type::Events;
+use crate::order_management::order_utils::{get_order, remove_order, set_order_status};
+use crate::pricing_update_management::operation_status::OperationStatus;
+use crate::pricing_update_management::price_waiting_operation_trait::PriceWaitingOperationTrait;
+
+/// Cancels an order in a market's order book.
+///
+/// # Parameters
+///
+/// * `order_id` (u64): The order id returned when the order was created
+///
+/// # Returns
+///
+/// Returns `bool` indicating success:
+/// - `true`: Order was removed from the order book and its reserved amounts were released
+/// - `false`: No order exists with the order id (it was already executed or cancelled)
+///
+/// # Security Notes
+///
+/// - **Caller Verification**: The caller must be the owner of the order
+#[update(name = "cancelOrder")]
+pub fn cancel_order(order_id: u64) -> bool {
+    let Some((key, order)) = get_order(order_id) else {
+        return false;
+    };
+
+    assert!(
+        order.owner() == msg_caller(),
+        "Caller is not the owner of the order"
+    );
+
+    remove_order(key);
+
+    order.release();
+
+    set_order_status(order_id, OperationStatus::Cancelled);
+
+    record_event(Events::CancelOrder {
+        order_id,
+        owner: order.owner(),
+        market_index: order.market_index(),
+    });
+
+    true
+}
@@ -0,0 +1,56 @@
+use ic_cdk::{api::msg_caller, update};
+
+use crate::events::event_log::record_event;
+use crate::events::evnts_type::Events;
+use crate::order_management::twap_order::TwapOrderStatus;
+use crate::order_management::twap_order_utils::{
+    clear_twap_order_timer, get_twap_order, set_twap_order,
+};
+use crate::user::balance_utils::update_user_balance;
+
+/// Cancels the children of a TWAP order not yet executed.
+///
+/// # Parameters
+///
+/// * `order_id` (u64): The TWAP order id returned when the order was created
+///
+/// # Returns
+///
+/// Returns `bool` indicating success:
+/// - `true`: Order was cancelled and the collateral reserved for its remaining children was released
+/// - `false`: No active order exists with the order id (it was already completed or cancelled)
+///
+/// # Security Notes
+///
+/// - **Caller Verification**: The caller must be the owner of the order
+#[update(name = "cancelTwapOrder")]
+pub fn cancel_twap_order(order_id: u64) -> bool {
+    let Some(mut order) = get_twap_order(order_id) else {
+        return false;
+    };
+
+    if order.status != TwapOrderStatus::Active {
+        return false;
+    }
+
+    assert!(
+        order.owner() == msg_caller(),
+        "Caller is not the owner of the order"
+    );
+
+    clear_twap_order_timer(order_id);
+
+    update_user_balance(order.owner(), order.reserved_collateral(), true);
+
+    order.status = TwapOrderStatus::Cancelled;
+
+    record_event(Events::CancelTwapOrder {
+        order_id,
+        owner: order.owner(),
+        market_index: order.market_index(),
+    });
+
+    set_twap_order(order_id, order);
+
+    true
+}
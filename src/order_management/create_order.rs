@@ -0,0 +1,139 @@
+use candid::CandidType;
+use ic_cdk::{api::msg_caller, api::time, update};
+use serde::Deserialize;
+
+use crate::constants::{MAX_ORDERS_PER_POSITION, MAX_ORDERS_PER_USER};
+use crate::events::event_log::record_event;
+use crate::events::evnts_type::Events;
+use crate::house_settings::get_execution_fee;
+use crate::market::market_status::market_accepts;
+use crate::order_management::order::{Order, OrderType};
+use crate::order_management::order_utils::{get_user_orders, put_order};
+use crate::stable_memory::MARKETS_LIST;
+use crate::user::balance_utils::get_user_balance;
+use crate::user::user_query::try_get_user_position_details;
+
+#[derive(CandidType, Deserialize)]
+pub struct CreateOrderParams {
+    #[serde(rename = "orderType")]
+    pub order_type: OrderType,
+    #[serde(rename = "triggerPrice")]
+    pub trigger_price: u128,
+}
+
+/// Creates a limit order in a market's order book.
+///
+/// Limit orders are evaluated every time the market's price is updated and executed
+/// through the same path as market orders once the price is at or better than the trigger price.
+///
+/// # Parameters
+///
+/// * `params` - [`CreateOrderParams`] containing:
+///   - `order_type` ([`OrderType`]): `LimitOpen` with the open position parameters or `LimitClose`
+///     with the close position parameters, each with its own acceptable price limit,
+///     the `deadline` of the parameters is the expiry of the order
+///   - `trigger_price` (u128): The price at which the order is triggered (20-decimal precision)
+///
+/// # Returns
+///
+/// Returns the order id, used for cancelling the order and querying its status
+///
+/// # Security Notes
+///
+/// - **Caller Verification**: The `owner` of the order parameters must match the message caller (`msg_caller()`)
+/// - **Balance Check**: For limit open orders, the user must have sufficient balance to cover the collateral
+///   and the execution fee, the collateral is reserved until the order is executed or cancelled
+/// - **Position Ownership**: For limit close orders, the position must be owned by the caller and be in the market
+/// - **Order Limits**: A user can have at most `MAX_ORDERS_PER_USER` orders in the order books
+///   and at most `MAX_ORDERS_PER_POSITION` limit close orders on the same position
+/// - **Market Status**: The protocol must not be paused and the market must accept opening (limit open orders)
+///   or closing (limit close orders) positions, triggered orders are kept in the order book while it does not
+#[update(name = "createOrder")]
+pub fn create_order(params: CreateOrderParams) -> u64 {
+    let CreateOrderParams {
+        order_type,
+        trigger_price,
+    } = params;
+
+    let user_orders = get_user_orders(msg_caller());
+
+    assert!(
+        user_orders.len() < MAX_ORDERS_PER_USER,
+        "Maximum number of orders reached"
+    );
+
+    let order = match order_type {
+        OrderType::LimitOpen(params) => {
+            assert!(
+                msg_caller() == params.owner,
+                "Caller is not the owner of the position"
+            );
+
+            let market_exists =
+                MARKETS_LIST.with_borrow(|reference| params.market_index < reference.len());
+            assert!(market_exists, "Market does not exist");
+
+            assert!(
+                market_accepts(params.market_index, true),
+                "Market is not active"
+            );
+
+            assert!(
+                get_user_balance(params.owner) >= params.collateral + get_execution_fee(),
+                "Insufficient balance"
+            );
+
+            Order {
+                order_type,
+                trigger_price,
+                long: params.long,
+                created_at: time(),
+            }
+        }
+        OrderType::LimitClose(params) => {
+            assert!(
+                msg_caller() == params.owner,
+                "Caller is not the owner of the position"
+            );
+
+            let Some((market_index, position)) =
+                try_get_user_position_details(params.owner, params.position_id)
+            else {
+                panic!("Position does not exist");
+            };
+
+            assert!(
+                market_index == params.market_index,
+                "Position is not in market"
+            );
+
+            let position_orders = user_orders
+                .iter()
+                .filter(|(_, order)| order.position_id() == Some(params.position_id))
+                .count();
+
+            assert!(
+                position_orders < MAX_ORDERS_PER_POSITION,
+                "Maximum number of orders on the position reached"
+            );
+
+            assert!(
+                market_accepts(params.market_index, false),
+                "Market is not active"
+            );
+
+            Order {
+                order_type,
+                trigger_price,
+                long: position.long,
+                created_at: time(),
+            }
+        }
+    };
+
+    let order_id = put_order(order);
+
+    record_event(Events::CreateOrder { order_id, order });
+
+    order_id
+}
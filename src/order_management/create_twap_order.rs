@@ -0,0 +1,153 @@
+use candid::CandidType;
+use ic_cdk::{api::msg_caller, api::time, update};
+use serde::Deserialize;
+
+use crate::constants::{MAX_TWAP_ORDER_CHILDREN, MIN_TWAP_ORDER_INTERVAL};
+use crate::events::event_log::record_event;
+use crate::events::evnts_type::Events;
+use crate::house_settings::get_execution_fee;
+use crate::market::market_status::market_accepts;
+use crate::order_management::twap_order::{TwapOrder, TwapOrderStatus, TwapOrderType};
+use crate::order_management::twap_order_utils::put_twap_order;
+use crate::stable_memory::MARKETS_LIST;
+use crate::user::balance_utils::{get_user_balance, update_user_balance};
+use crate::user::user_query::try_get_user_position_details;
+
+#[derive(CandidType, Deserialize)]
+pub struct CreateTwapOrderParams {
+    #[serde(rename = "orderType")]
+    pub order_type: TwapOrderType,
+    pub children: u64,
+    #[serde(rename = "intervalInSecs")]
+    pub interval_in_secs: u64,
+}
+
+/// Creates a TWAP order.
+///
+/// The order is split into `children` equal child executions spaced by `interval_in_secs`,
+/// the first child is executed immediately. Every child is executed on its own at the market price
+/// of its execution time and fails on its own if that price is beyond the acceptable price limit,
+/// the next children are still executed.
+///
+/// # Parameters
+///
+/// * `params` - [`CreateTwapOrderParams`] containing:
+///   - `order_type` ([`TwapOrderType`]): `Open` with the open position parameters, the collateral
+///     is split between the children, or `Close` with the close position parameters, the position's units
+///     are split between the children (`size_delta` is not used), the `deadline` of the parameters is not used
+///   - `children` (u64): The number of children, between 2 and `MAX_TWAP_ORDER_CHILDREN`
+///   - `interval_in_secs` (u64): The interval between two children, at least `MIN_TWAP_ORDER_INTERVAL` seconds
+///
+/// # Returns
+///
+/// Returns the TWAP order id, used for cancelling the order and querying its progress
+///
+/// # Security Notes
+///
+/// - **Caller Verification**: The `owner` of the order parameters must match the message caller (`msg_caller()`)
+/// - **Balance Check**: For open orders, the user must have sufficient balance to cover the collateral
+///   and the execution fee, the collateral of the children not yet executed is reserved until they are executed
+///   or the order is cancelled, the execution fee is taken on every settled child
+/// - **Position Ownership**: For close orders, the position must be owned by the caller and be in the market
+/// - **Market Status**: The protocol must not be paused and the market must accept opening (open orders)
+///   or closing (close orders) positions, children are postponed by one interval while it does not
+#[update(name = "createTwapOrder")]
+pub fn create_twap_order(params: CreateTwapOrderParams) -> u64 {
+    let CreateTwapOrderParams {
+        order_type,
+        children,
+        interval_in_secs,
+    } = params;
+
+    assert!(
+        (2..=MAX_TWAP_ORDER_CHILDREN).contains(&children),
+        "Invalid number of children"
+    );
+    assert!(
+        interval_in_secs >= MIN_TWAP_ORDER_INTERVAL,
+        "Interval is below minimum"
+    );
+
+    match order_type {
+        TwapOrderType::Open(params) => {
+            assert!(
+                msg_caller() == params.owner,
+                "Caller is not the owner of the position"
+            );
+
+            let market_exists =
+                MARKETS_LIST.with_borrow(|reference| params.market_index < reference.len());
+            assert!(market_exists, "Market does not exist");
+
+            assert!(
+                market_accepts(params.market_index, true),
+                "Market is not active"
+            );
+
+            assert!(
+                params.collateral / children as u128 > 0,
+                "Collateral is too low for the number of children"
+            );
+
+            assert!(
+                get_user_balance(params.owner) >= params.collateral + get_execution_fee(),
+                "Insufficient balance"
+            );
+
+            update_user_balance(params.owner, params.collateral, false);
+        }
+        TwapOrderType::Close(params) => {
+            assert!(
+                msg_caller() == params.owner,
+                "Caller is not the owner of the position"
+            );
+
+            let Some((market_index, position)) =
+                try_get_user_position_details(params.owner, params.position_id)
+            else {
+                panic!("Position does not exist");
+            };
+
+            assert!(
+                market_index == params.market_index,
+                "Position is not in market"
+            );
+
+            assert!(
+                market_accepts(params.market_index, false),
+                "Market is not active"
+            );
+
+            assert!(
+                position.units / children as u128 > 0,
+                "Position is too small for the number of children"
+            );
+        }
+    };
+
+    let position_id = match order_type {
+        TwapOrderType::Open(_) => None,
+        TwapOrderType::Close(params) => Some(params.position_id),
+    };
+
+    let current_time = time();
+
+    let order = TwapOrder {
+        order_type,
+        children,
+        interval_in_secs,
+        executed_children: 0,
+        filled_size: 0,
+        position_id,
+        failed_children: Vec::new(),
+        next_execution_time: current_time,
+        status: TwapOrderStatus::Active,
+        created_at: current_time,
+    };
+
+    let order_id = put_twap_order(order.clone());
+
+    record_event(Events::CreateTwapOrder { order_id, order });
+
+    order_id
+}
@@ -0,0 +1,11 @@
+pub mod cancel_order;
+pub mod cancel_twap_order;
+pub mod create_order;
+pub mod create_twap_order;
+pub mod order;
+pub mod order_utils;
+pub mod position_triggers;
+pub mod set_position_triggers;
+pub mod triggers_scan;
+pub mod twap_order;
+pub mod twap_order_utils;
@@ -0,0 +1,135 @@
+use std::borrow::Cow;
+
+use candid::{CandidType, Principal};
+use ic_stable_structures::{Storable, storable::Bound};
+use serde::{Deserialize, Serialize};
+
+use crate::close_position::close_position_params::ClosePositionParams;
+use crate::open_position::open_position_params::OpenPositionParams;
+use crate::pricing_update_management::operation_status::OperationStatus;
+use crate::pricing_update_management::price_waiting_operation_trait::PriceWaitingOperationTrait;
+
+/// Order Type
+///
+/// the operation executed when the order is triggered, with its own acceptable price limit
+#[derive(CandidType, Deserialize, Serialize, Clone, Copy)]
+pub enum OrderType {
+    /// Opens a position at or better than the trigger price
+    /// (at or below it for longs and at or above it for shorts)
+    LimitOpen(OpenPositionParams),
+    /// Closes a position at or better than the trigger price
+    /// (at or above it for longs and at or below it for shorts)
+    LimitClose(ClosePositionParams),
+}
+
+/// Order
+///
+/// an order stored in a market's order book until its trigger price is reached
+#[derive(CandidType, Deserialize, Serialize, Clone, Copy)]
+pub struct Order {
+    #[serde(rename = "orderType")]
+    pub order_type: OrderType,
+    /// Price at which the order is triggered (20-decimal precision)
+    #[serde(rename = "triggerPrice")]
+    pub trigger_price: u128,
+    /// Only for limit close orders, true if the position closed is a long
+    pub long: bool,
+    #[serde(rename = "createdAt")]
+    pub created_at: u64,
+}
+
+impl Order {
+    pub fn market_index(&self) -> u64 {
+        match self.order_type {
+            OrderType::LimitOpen(params) => params.market_index,
+            OrderType::LimitClose(params) => params.market_index,
+        }
+    }
+
+    /// true for orders opening a position, false for orders closing one
+    pub fn opens(&self) -> bool {
+        matches!(self.order_type, OrderType::LimitOpen(_))
+    }
+
+    /// the position closed by limit close orders
+    pub fn position_id(&self) -> Option<u64> {
+        match self.order_type {
+            OrderType::LimitOpen(_) => None,
+            OrderType::LimitClose(params) => Some(params.position_id),
+        }
+    }
+
+    pub fn owner(&self) -> Principal {
+        match self.order_type {
+            OrderType::LimitOpen(params) => params.owner,
+            OrderType::LimitClose(params) => params.owner,
+        }
+    }
+
+    /// Is Triggered
+    ///
+    /// limit orders are triggered when the price is at or better than the trigger price
+    pub fn is_triggered(&self, price: u128) -> bool {
+        let buys = match self.order_type {
+            OrderType::LimitOpen(params) => params.long,
+            OrderType::LimitClose(_) => !self.long,
+        };
+
+        if buys {
+            price <= self.trigger_price
+        } else {
+            price >= self.trigger_price
+        }
+    }
+
+    fn params(&self) -> &dyn PriceWaitingOperationTrait {
+        match &self.order_type {
+            OrderType::LimitOpen(params) => params,
+            OrderType::LimitClose(params) => params,
+        }
+    }
+}
+
+impl PriceWaitingOperationTrait for Order {
+    fn execute(&self) -> OperationStatus {
+        self.params().execute()
+    }
+
+    fn executor(&self) -> Option<Principal> {
+        self.params().executor()
+    }
+
+    fn deadline(&self) -> Option<u64> {
+        self.params().deadline()
+    }
+
+    fn reserve(&self) {
+        self.params().reserve()
+    }
+
+    fn release(&self) {
+        self.params().release()
+    }
+}
+
+impl Storable for Order {
+    fn to_bytes(&self) -> Cow<'_, [u8]> {
+        let serialized = bincode::serialize(self).expect("failed to serialize");
+        Cow::Owned(serialized)
+    }
+
+    /// Converts the element into an owned byte vector.
+    ///
+    /// This method consumes `self` and avoids cloning when possible.
+    fn into_bytes(self) -> Vec<u8> {
+        bincode::serialize(&self).expect("failed to serialize")
+    }
+
+    /// Converts bytes into an element.
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        bincode::deserialize(bytes.as_ref()).expect("failed to desearalize")
+    }
+
+    /// The size bounds of the type.
+    const BOUND: Bound = Bound::Unbounded;
+}
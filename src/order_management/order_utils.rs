@@ -0,0 +1,231 @@
+use candid::Principal;
+use ic_cdk::api::time;
+
+use crate::constants::TRIGGERS_EXECUTION_BATCH;
+use crate::events::event_log::record_event;
+use crate::events::evnts_type::Events;
+use crate::market::market_status::market_accepts;
+use crate::order_management::order::Order;
+use crate::pricing_update_management::operation_status::{OperationStatus, prune_statuses};
+use crate::pricing_update_management::price_waiting_operation_trait::PriceWaitingOperationTrait;
+use crate::stable_memory::{
+    MARKET_ORDERS, ORDERS_COUNTER, ORDERS_MARKETS, ORDERS_STATUS, USERS_ORDERS,
+};
+
+pub fn get_order_status(order_id: u64) -> Option<OperationStatus> {
+    ORDERS_STATUS.with_borrow(|reference| reference.get(&order_id))
+}
+
+pub fn set_order_status(order_id: u64, status: OperationStatus) {
+    let next_order_id = ORDERS_COUNTER.with_borrow(|reference| *reference.get());
+
+    ORDERS_STATUS.with_borrow_mut(|reference| {
+        reference.insert(order_id, status);
+
+        prune_statuses(reference, next_order_id);
+    });
+}
+
+/// Put Order
+///
+/// adds an order to its market's order book and reserves the amounts it uses until it is executed or cancelled
+///
+/// Returns the order id
+pub fn put_order(order: Order) -> u64 {
+    let order_id = ORDERS_COUNTER.with_borrow_mut(|reference| {
+        let order_id = *reference.get();
+        reference.set(order_id + 1);
+        order_id
+    });
+
+    order.reserve();
+
+    _insert_order((order.market_index(), order_id), order);
+
+    set_order_status(order_id, OperationStatus::Pending);
+
+    order_id
+}
+
+/// Get Order
+///
+/// finds an order in the order books by its order id
+///
+/// Returns the key (market index and order id) and the order
+pub fn get_order(order_id: u64) -> Option<((u64, u64), Order)> {
+    let market_index = ORDERS_MARKETS.with_borrow(|reference| reference.get(&order_id))?;
+
+    let key = (market_index, order_id);
+
+    MARKET_ORDERS
+        .with_borrow(|reference| reference.get(&key))
+        .map(|order| (key, order))
+}
+
+/// Get User Orders
+///
+/// Returns the order id and the order of every order of the user in the order books
+pub fn get_user_orders(user: Principal) -> Vec<(u64, Order)> {
+    let keys: Vec<(u64, u64)> = USERS_ORDERS.with_borrow(|reference| {
+        reference
+            .range((user, 0)..=(user, u64::MAX))
+            .map(|entry| entry.into_pair())
+            .map(|((_, order_id), market_index)| (market_index, order_id))
+            .collect()
+    });
+
+    MARKET_ORDERS.with_borrow(|reference| {
+        keys.into_iter()
+            .filter_map(|key| reference.get(&key).map(|order| (key.1, order)))
+            .collect()
+    })
+}
+
+/// Remove Order
+///
+/// removes an order from its market's order book and from the order id and owner indexes
+///
+/// Returns the order, None if it is not in the order book
+pub fn remove_order(key: (u64, u64)) -> Option<Order> {
+    let order = MARKET_ORDERS.with_borrow_mut(|reference| reference.remove(&key))?;
+
+    ORDERS_MARKETS.with_borrow_mut(|reference| {
+        reference.remove(&key.1);
+    });
+
+    USERS_ORDERS.with_borrow_mut(|reference| {
+        reference.remove(&(order.owner(), key.1));
+    });
+
+    Some(order)
+}
+
+/// Cancel Position Orders
+///
+/// cancels the limit close orders of a position and releases their reserved amounts
+/// @dev called when the position is removed (closed, liquidated or settled)
+pub fn cancel_position_orders(owner: Principal, position_id: u64) {
+    let orders = get_user_orders(owner)
+        .into_iter()
+        .filter(|(_, order)| order.position_id() == Some(position_id));
+
+    for (order_id, order) in orders {
+        remove_order((order.market_index(), order_id));
+
+        order.release();
+
+        set_order_status(order_id, OperationStatus::Cancelled);
+
+        record_event(Events::CancelOrder {
+            order_id,
+            owner,
+            market_index: order.market_index(),
+        });
+    }
+}
+
+/// adds an order to its market's order book and to the order id and owner indexes
+fn _insert_order(key: (u64, u64), order: Order) {
+    let (market_index, order_id) = key;
+
+    ORDERS_MARKETS.with_borrow_mut(|reference| {
+        reference.insert(order_id, market_index);
+    });
+
+    USERS_ORDERS.with_borrow_mut(|reference| {
+        reference.insert((order.owner(), order_id), market_index);
+    });
+
+    MARKET_ORDERS.with_borrow_mut(|reference| {
+        reference.insert(key, order);
+    });
+}
+
+/// Execute Triggered Orders
+///
+/// checks the next `TRIGGERS_EXECUTION_BATCH` orders of a market's order book starting from `from`
+/// and executes the ones triggered by the price in order of creation,
+/// orders past their deadline are failed,
+/// orders the market does not accept (protocol paused or market not active) are kept in the order book
+///
+/// Returns the key to resume from, None once the end of the order book is reached
+/// @dev called in batches after the new price is stored in the market (see triggers_scan)
+pub fn execute_triggered_orders(
+    market_index: u64,
+    price: u128,
+    from: Option<(u64, u64)>,
+) -> Option<(u64, u64)> {
+    _execute_triggered_orders(market_index, price, from, time())
+}
+
+pub fn _execute_triggered_orders(
+    market_index: u64,
+    price: u128,
+    from: Option<(u64, u64)>,
+    current_time: u64,
+) -> Option<(u64, u64)> {
+    let accepts_opening = market_accepts(market_index, true);
+    let accepts_closing = market_accepts(market_index, false);
+
+    let (orders, next) = MARKET_ORDERS.with_borrow(|reference| {
+        let mut orders: Vec<((u64, u64), Order)> = reference
+            .range(from.unwrap_or((market_index, 0))..=(market_index, u64::MAX))
+            .take(TRIGGERS_EXECUTION_BATCH + 1)
+            .map(|entry| entry.into_pair())
+            .collect();
+
+        let next = (orders.len() > TRIGGERS_EXECUTION_BATCH)
+            .then(|| orders.pop().map(|(key, _)| key))
+            .flatten();
+
+        (orders, next)
+    });
+
+    for (key, order) in orders {
+        let (_, order_id) = key;
+
+        let expired = order
+            .deadline()
+            .is_some_and(|deadline| current_time > deadline);
+
+        if !expired && !order.is_triggered(price) {
+            continue;
+        }
+
+        let accepted = if order.opens() {
+            accepts_opening
+        } else {
+            accepts_closing
+        };
+
+        if !expired && !accepted {
+            continue;
+        }
+
+        // cancelled by an order executed before it in the batch (e.g the close of its position)
+        if remove_order(key).is_none() {
+            continue;
+        }
+
+        order.release();
+
+        if expired {
+            set_order_status(
+                order_id,
+                OperationStatus::Failed("Deadline exceeded".to_string()),
+            );
+            continue;
+        }
+
+        match order.execute() {
+            // kept in the order book if the market price is no longer current
+            OperationStatus::Pending => {
+                order.reserve();
+                _insert_order(key, order);
+            }
+            status => set_order_status(order_id, status),
+        }
+    }
+
+    next
+}
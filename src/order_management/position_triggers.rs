@@ -0,0 +1,282 @@
+use std::borrow::Cow;
+
+use candid::{CandidType, Principal};
+use ic_stable_structures::{Storable, storable::Bound};
+use serde::{Deserialize, Serialize};
+
+use crate::close_position::close_position::_close_position;
+use crate::close_position::close_position_params::ClosePositionParams;
+use crate::close_position::close_position_result::ClosePositionResult;
+use crate::constants::TRIGGERS_EXECUTION_BATCH;
+use crate::market::market_status::market_accepts;
+use crate::math::math::apply_precision;
+use crate::stable_memory::POSITIONS_TRIGGERS;
+use crate::user::user_query::try_get_user_position_details;
+
+/// Trailing Distance
+///
+/// the distance of a trailing stop from the best price seen since it was placed
+#[derive(CandidType, Deserialize, Serialize, Clone, Copy)]
+pub enum TrailingDistance {
+    /// Share of the best price (20-decimal precision, e.g 5000000000000000000 for 5%)
+    Percentage(u128),
+    /// Price distance (20-decimal precision)
+    Absolute(u128),
+}
+
+/// Trailing Stop
+#[derive(CandidType, Deserialize, Serialize, Clone, Copy)]
+pub struct TrailingStop {
+    pub distance: TrailingDistance,
+    /// Best price seen since the trailing stop was placed,
+    /// the high-water mark for longs and the low-water mark for shorts
+    #[serde(rename = "waterMark")]
+    pub water_mark: u128,
+}
+
+impl TrailingStop {
+    /// Trailing Level
+    ///
+    /// the price the trailing stop is triggered at, the distance below the water mark for longs
+    /// and above it for shorts
+    pub fn trailing_level(&self, long: bool) -> u128 {
+        let distance = match self.distance {
+            TrailingDistance::Percentage(factor) => apply_precision(factor, self.water_mark),
+            TrailingDistance::Absolute(distance) => distance,
+        };
+
+        if long {
+            self.water_mark.saturating_sub(distance)
+        } else {
+            self.water_mark + distance
+        }
+    }
+
+    /// advances the water mark to the price if it is better
+    ///
+    /// Returns true if the water mark moved
+    pub fn update_water_mark(&mut self, price: u128, long: bool) -> bool {
+        let is_better_price = if long {
+            price > self.water_mark
+        } else {
+            price < self.water_mark
+        };
+
+        let moved = self.water_mark == 0 || is_better_price;
+
+        if moved {
+            self.water_mark = price
+        }
+
+        moved
+    }
+}
+
+/// Position Triggers
+///
+/// the stop-loss, take-profit and trailing stop triggers of a position,
+/// when one of them closes the position the others are cancelled with it (one-cancels-other)
+#[derive(CandidType, Deserialize, Serialize, Clone, Copy, Default)]
+pub struct PositionTriggers {
+    /// true if the position is a long
+    pub long: bool,
+    /// Price at which the position is closed to limit its loss (20-decimal precision)
+    #[serde(rename = "stopLoss")]
+    pub stop_loss: Option<u128>,
+    /// Price at which the position is closed to take its profit (20-decimal precision)
+    #[serde(rename = "takeProfit")]
+    pub take_profit: Option<u128>,
+    /// Stop that follows the best price seen, the position is closed when the price crosses its trailing level
+    #[serde(rename = "trailingStop")]
+    pub trailing_stop: Option<TrailingStop>,
+}
+
+impl PositionTriggers {
+    /// Stop Loss Is Triggered
+    ///
+    /// at or below the stop-loss price for longs and at or above it for shorts
+    pub fn stop_loss_is_triggered(&self, price: u128) -> bool {
+        self.stop_loss.is_some_and(|stop_loss| {
+            if self.long {
+                price <= stop_loss
+            } else {
+                price >= stop_loss
+            }
+        })
+    }
+
+    /// Take Profit Is Triggered
+    ///
+    /// at or above the take-profit price for longs and at or below it for shorts
+    pub fn take_profit_is_triggered(&self, price: u128) -> bool {
+        self.take_profit.is_some_and(|take_profit| {
+            if self.long {
+                price >= take_profit
+            } else {
+                price <= take_profit
+            }
+        })
+    }
+
+    /// Trailing Stop Is Triggered
+    ///
+    /// at or below the trailing level for longs and at or above it for shorts
+    pub fn trailing_stop_is_triggered(&self, price: u128) -> bool {
+        self.trailing_stop.is_some_and(|trailing_stop| {
+            let trailing_level = trailing_stop.trailing_level(self.long);
+            if self.long {
+                price <= trailing_level
+            } else {
+                price >= trailing_level
+            }
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.stop_loss.is_none() && self.take_profit.is_none() && self.trailing_stop.is_none()
+    }
+
+    /// checks that the stop-loss is below the take-profit for longs and above it for shorts
+    pub fn is_valid(&self) -> bool {
+        match (self.stop_loss, self.take_profit) {
+            (Some(stop_loss), Some(take_profit)) => {
+                (self.long && stop_loss < take_profit) || (!self.long && stop_loss > take_profit)
+            }
+            _ => true,
+        }
+    }
+}
+
+pub fn get_position_triggers(
+    market_index: u64,
+    owner: Principal,
+    position_id: u64,
+) -> Option<PositionTriggers> {
+    POSITIONS_TRIGGERS.with_borrow(|reference| reference.get(&(market_index, owner, position_id)))
+}
+
+pub fn put_position_triggers(
+    market_index: u64,
+    owner: Principal,
+    position_id: u64,
+    triggers: PositionTriggers,
+) {
+    POSITIONS_TRIGGERS.with_borrow_mut(|reference| {
+        reference.insert((market_index, owner, position_id), triggers);
+    });
+}
+
+pub fn remove_position_triggers(market_index: u64, owner: Principal, position_id: u64) {
+    POSITIONS_TRIGGERS.with_borrow_mut(|reference| {
+        reference.remove(&(market_index, owner, position_id));
+    });
+}
+
+/// Execute Position Triggers
+///
+/// checks the triggers of the next `TRIGGERS_EXECUTION_BATCH` positions of a market starting from `from`,
+/// advances the water marks of their trailing stops to the price
+/// and closes the positions whose stop-loss, trailing stop or take-profit is triggered by it,
+/// stops are closed at any price and take-profits at the take-profit price or better,
+/// triggered positions are not closed while the protocol is paused or the market does not accept closing
+///
+/// Returns the key to resume from, None once the last position of the market is reached
+/// @dev called in batches after the new price is stored in the market (see triggers_scan),
+/// the triggers of a closed position are removed with the position (see remove_user_position_detail)
+pub fn execute_position_triggers(
+    market_index: u64,
+    price: u128,
+    from: Option<(u64, Principal, u64)>,
+) -> Option<(u64, Principal, u64)> {
+    let accepts_closing = market_accepts(market_index, false);
+
+    let start = from.unwrap_or((market_index, Principal::management_canister(), 0));
+
+    let (triggered, next) = POSITIONS_TRIGGERS.with_borrow_mut(|reference| {
+        let mut entries: Vec<((u64, Principal, u64), PositionTriggers)> = reference
+            .range(start..)
+            .take_while(|entry| entry.key().0 == market_index)
+            .take(TRIGGERS_EXECUTION_BATCH + 1)
+            .map(|entry| entry.into_pair())
+            .collect();
+
+        let next = (entries.len() > TRIGGERS_EXECUTION_BATCH)
+            .then(|| entries.pop().map(|(key, _)| key))
+            .flatten();
+
+        let mut triggered = Vec::new();
+
+        for (key, mut triggers) in entries {
+            // only written back when the water mark moved
+            let water_mark_moved = triggers
+                .trailing_stop
+                .as_mut()
+                .is_some_and(|trailing_stop| trailing_stop.update_water_mark(price, triggers.long));
+
+            if water_mark_moved {
+                reference.insert(key, triggers);
+            }
+
+            let acceptable_price_limit = if triggers.stop_loss_is_triggered(price)
+                || triggers.trailing_stop_is_triggered(price)
+            {
+                if triggers.long { 0 } else { u128::MAX }
+            } else if triggers.take_profit_is_triggered(price) {
+                triggers.take_profit.unwrap_or_default()
+            } else {
+                continue;
+            };
+
+            if accepts_closing {
+                triggered.push((key, acceptable_price_limit));
+            }
+        }
+
+        (triggered, next)
+    });
+
+    for ((market_index, owner, position_id), acceptable_price_limit) in triggered {
+        let params = ClosePositionParams {
+            market_index,
+            owner,
+            position_id,
+            acceptable_price_limit,
+            size_delta: None,
+            deadline: None,
+        };
+
+        // closing the position removes its triggers, they are kept if the close failed
+        // (e.g take-profit price limit exceeded) and only removed if the position no longer exists
+        let result = _close_position(&params);
+
+        if matches!(result, ClosePositionResult::Failed)
+            && try_get_user_position_details(owner, position_id).is_none()
+        {
+            remove_position_triggers(market_index, owner, position_id);
+        }
+    }
+
+    next
+}
+
+impl Storable for PositionTriggers {
+    fn to_bytes(&self) -> Cow<'_, [u8]> {
+        let serialized = bincode::serialize(self).expect("failed to serialize");
+        Cow::Owned(serialized)
+    }
+
+    /// Converts the element into an owned byte vector.
+    ///
+    /// This method consumes `self` and avoids cloning when possible.
+    fn into_bytes(self) -> Vec<u8> {
+        bincode::serialize(&self).expect("failed to serialize")
+    }
+
+    /// Converts bytes into an element.
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        bincode::deserialize(bytes.as_ref()).expect("failed to desearalize")
+    }
+
+    /// The size bounds of the type.
+    const BOUND: Bound = Bound::Unbounded;
+}
@@ -0,0 +1,149 @@
+use ic_cdk::{api::msg_caller, update};
+
+use candid::Principal;
+
+use crate::events::event_log::record_event;
+use crate::events::evnts_type::Events;
+use crate::math::math::FLOAT_PRECISION;
+use crate::order_management::position_triggers::{
+    PositionTriggers, TrailingDistance, TrailingStop, get_position_triggers, put_position_triggers,
+    remove_position_triggers,
+};
+use crate::stable_memory::MARKETS_LIST;
+use crate::user::user_query::try_get_user_position_details;
+
+/// Sets the stop-loss and take-profit trigger prices of one of the caller's positions.
+///
+/// The triggers are checked every time the market's price is updated and close the whole position
+/// when reached, the other triggers are cancelled with the position (one-cancels-other).
+/// Calling it again modifies the triggers, `None` clears a trigger.
+///
+/// # Parameters
+///
+/// * `market_index` (u64): The unique identifier of the market containing the position
+/// * `position_id` (u64): The unique identifier of the position
+/// * `stop_loss` (Option<u128>): Stop-loss price (20-decimal precision), below the take-profit for longs and above it for shorts
+/// * `take_profit` (Option<u128>): Take-profit price (20-decimal precision)
+#[update(name = "setPositionTriggers")]
+pub fn set_position_triggers(
+    market_index: u64,
+    position_id: u64,
+    stop_loss: Option<u128>,
+    take_profit: Option<u128>,
+) {
+    let owner = msg_caller();
+
+    let Some((position_market_index, position)) = try_get_user_position_details(owner, position_id)
+    else {
+        panic!("Position does not exist");
+    };
+
+    assert!(
+        position_market_index == market_index,
+        "Position is not in market"
+    );
+
+    let triggers = PositionTriggers {
+        long: position.long,
+        stop_loss,
+        take_profit,
+        ..get_position_triggers(market_index, owner, position_id).unwrap_or_default()
+    };
+
+    assert!(triggers.is_valid(), "Invalid trigger prices");
+
+    _update_position_triggers(market_index, owner, position_id, triggers);
+}
+
+/// Sets the trailing stop of one of the caller's positions.
+///
+/// The trailing stop follows the best price seen since it was placed (the high-water mark for longs and
+/// the low-water mark for shorts), which is advanced on every price update of the market.
+/// The whole position is closed once the price crosses the trailing level, cancelling the other triggers.
+///
+/// # Parameters
+///
+/// * `market_index` (u64): The unique identifier of the market containing the position
+/// * `position_id` (u64): The unique identifier of the position
+/// * `distance` (Option<TrailingDistance>): Percentage or absolute distance of the trailing level from the best price,
+///   `None` clears the trailing stop
+#[update(name = "setTrailingStop")]
+pub fn set_trailing_stop(market_index: u64, position_id: u64, distance: Option<TrailingDistance>) {
+    let owner = msg_caller();
+
+    let Some((position_market_index, position)) = try_get_user_position_details(owner, position_id)
+    else {
+        panic!("Position does not exist");
+    };
+
+    assert!(
+        position_market_index == market_index,
+        "Position is not in market"
+    );
+
+    let trailing_stop = distance.map(|distance| {
+        let distance_is_valid = match distance {
+            TrailingDistance::Percentage(factor) => factor > 0 && factor < FLOAT_PRECISION,
+            TrailingDistance::Absolute(distance) => distance > 0,
+        };
+        assert!(distance_is_valid, "Invalid trailing distance");
+
+        // the best price starts from the last price of the market
+        let water_mark = MARKETS_LIST.with_borrow(|reference| {
+            reference
+                .get(market_index)
+                .expect("Market does not exist")
+                .pricing_manager
+                .price
+        });
+
+        TrailingStop {
+            distance,
+            water_mark,
+        }
+    });
+
+    let triggers = PositionTriggers {
+        long: position.long,
+        trailing_stop,
+        ..get_position_triggers(market_index, owner, position_id).unwrap_or_default()
+    };
+
+    _update_position_triggers(market_index, owner, position_id, triggers);
+}
+
+/// Clears the stop-loss, take-profit and trailing stop of one of the caller's positions.
+#[update(name = "clearPositionTriggers")]
+pub fn clear_position_triggers(market_index: u64, position_id: u64) {
+    let owner = msg_caller();
+
+    remove_position_triggers(market_index, owner, position_id);
+
+    record_event(Events::SetPositionTriggers {
+        owner,
+        market_index,
+        position_id,
+        triggers: None,
+    });
+}
+
+fn _update_position_triggers(
+    market_index: u64,
+    owner: Principal,
+    position_id: u64,
+    triggers: PositionTriggers,
+) {
+    let triggers = (!triggers.is_empty()).then_some(triggers);
+
+    match triggers {
+        Some(triggers) => put_position_triggers(market_index, owner, position_id, triggers),
+        None => remove_position_triggers(market_index, owner, position_id),
+    }
+
+    record_event(Events::SetPositionTriggers {
+        owner,
+        market_index,
+        position_id,
+        triggers,
+    });
+}
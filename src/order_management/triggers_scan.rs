@@ -0,0 +1,117 @@
+use std::time::Duration;
+
+use candid::Principal;
+
+use crate::order_management::order_utils::execute_triggered_orders;
+use crate::order_management::position_triggers::execute_position_triggers;
+use crate::stable_memory::{MARKET_TRIGGERS_SCANS, MARKETS_LIST};
+
+/// Triggers Scan Stage
+///
+/// protective exits are checked before limit orders
+#[derive(Clone, Copy)]
+pub enum TriggersScanStage {
+    /// Checking the stop-loss, take-profit and trailing stop triggers of the market's positions,
+    /// from the next key or the first position if None
+    PositionTriggers { next: Option<(u64, Principal, u64)> },
+    /// Checking the market's order book, from the next key or the first order if None
+    Orders { next: Option<(u64, u64)> },
+}
+
+impl Default for TriggersScanStage {
+    fn default() -> Self {
+        TriggersScanStage::PositionTriggers { next: None }
+    }
+}
+
+/// Triggers Scan
+///
+/// progress of the scan of a market's position triggers and order book started by a price update,
+/// run in batches of `TRIGGERS_EXECUTION_BATCH` entries, one batch per message
+#[derive(Clone, Copy, Default)]
+pub struct TriggersScan {
+    stage: TriggersScanStage,
+    /// true if the price was updated during the scan,
+    /// the position triggers and the order book are scanned again once the scan is completed
+    rescan: bool,
+}
+
+/// Start Triggers Scan
+///
+/// checks the market's position triggers and order book against its new price,
+/// the first batch is run immediately and the next ones in their own messages,
+/// a scan already running is completed and started again so every entry is checked at the new price
+/// @dev called after the new price is stored in the market
+pub fn start_triggers_scan(market_index: u64) {
+    let running =
+        MARKET_TRIGGERS_SCANS.with_borrow_mut(|reference| match reference.get_mut(&market_index) {
+            Some(scan) => {
+                scan.rescan = true;
+                true
+            }
+            None => {
+                reference.insert(market_index, TriggersScan::default());
+                false
+            }
+        });
+
+    if !running {
+        run_triggers_scan(market_index);
+    }
+}
+
+/// runs the next batch of the scan at the market's current price,
+/// the scan is stopped if the price is no longer current (it is started again by the next price update)
+fn run_triggers_scan(market_index: u64) {
+    let Some(mut scan) =
+        MARKET_TRIGGERS_SCANS.with_borrow(|reference| reference.get(&market_index).copied())
+    else {
+        return;
+    };
+
+    let price = MARKETS_LIST.with_borrow(|reference| {
+        reference
+            .get(market_index)
+            .and_then(|market| market.pricing_manager.get_price())
+    });
+
+    let Some(price) = price else {
+        _end_triggers_scan(market_index);
+        return;
+    };
+
+    scan.stage = match scan.stage {
+        TriggersScanStage::PositionTriggers { next } => {
+            match execute_position_triggers(market_index, price, next) {
+                Some(next) => TriggersScanStage::PositionTriggers { next: Some(next) },
+                None => TriggersScanStage::Orders { next: None },
+            }
+        }
+        TriggersScanStage::Orders { next } => {
+            match execute_triggered_orders(market_index, price, next) {
+                Some(next) => TriggersScanStage::Orders { next: Some(next) },
+                None if scan.rescan => {
+                    scan.rescan = false;
+                    TriggersScanStage::default()
+                }
+                None => {
+                    _end_triggers_scan(market_index);
+                    return;
+                }
+            }
+        }
+    };
+
+    MARKET_TRIGGERS_SCANS.with_borrow_mut(|reference| {
+        reference.insert(market_index, scan);
+    });
+
+    // the next batch is run in its own message
+    ic_cdk_timers::set_timer(Duration::ZERO, move || run_triggers_scan(market_index));
+}
+
+fn _end_triggers_scan(market_index: u64) {
+    MARKET_TRIGGERS_SCANS.with_borrow_mut(|reference| {
+        reference.remove(&market_index);
+    });
+}
@@ -0,0 +1,209 @@
+use std::borrow::Cow;
+
+use candid::{CandidType, Principal};
+use ic_stable_structures::{Storable, storable::Bound};
+use serde::{Deserialize, Serialize};
+
+use crate::close_position::close_position_params::ClosePositionParams;
+use crate::increase_position::increase_position_params::IncreasePositionParams;
+use crate::open_position::open_position_params::OpenPositionParams;
+use crate::pricing_update_management::price_waiting_operation_trait::PriceWaitingOperation;
+use crate::user::user_query::try_get_user_position_details;
+
+/// TWAP Order Type
+///
+/// the operation split into the children of the TWAP order
+#[derive(CandidType, Deserialize, Serialize, Clone, Copy)]
+pub enum TwapOrderType {
+    /// Opens a position with an equal part of the collateral on every child,
+    /// the first settled child opens the position and the next children increase it
+    Open(OpenPositionParams),
+    /// Closes a position by an equal part of its remaining units on every child,
+    /// the last child closes whatever is left of the position
+    Close(ClosePositionParams),
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+pub enum TwapOrderStatus {
+    /// Children are still being executed
+    Active,
+    /// All children were executed (settled or failed)
+    Completed,
+    /// Order was cancelled by its owner before all children were executed
+    Cancelled,
+}
+
+/// TWAP Child Failure
+///
+/// a child of a TWAP order that could not be executed
+#[derive(CandidType, Deserialize, Serialize, Clone)]
+pub struct TwapChildFailure {
+    /// Index of the child (starting from 0)
+    pub index: u64,
+    pub reason: String,
+}
+
+/// TWAP Order
+///
+/// an order executed as a number of equal children spaced by an interval
+#[derive(CandidType, Deserialize, Serialize, Clone)]
+pub struct TwapOrder {
+    #[serde(rename = "orderType")]
+    pub order_type: TwapOrderType,
+    /// Number of children the order is split into
+    pub children: u64,
+    /// Interval between the execution of two children in seconds
+    #[serde(rename = "intervalInSecs")]
+    pub interval_in_secs: u64,
+    /// Number of children executed (settled or failed)
+    #[serde(rename = "executedChildren")]
+    pub executed_children: u64,
+    /// Size filled by the settled children,
+    /// the collateral for open orders and the units closed for close orders
+    #[serde(rename = "filledSize")]
+    pub filled_size: u128,
+    /// ID of the position opened or closed by the order,
+    /// None for open orders until a child is settled
+    #[serde(rename = "positionId")]
+    pub position_id: Option<u64>,
+    #[serde(rename = "failedChildren")]
+    pub failed_children: Vec<TwapChildFailure>,
+    /// Time of the execution of the next child in nanoseconds
+    #[serde(rename = "nextExecutionTime")]
+    pub next_execution_time: u64,
+    pub status: TwapOrderStatus,
+    #[serde(rename = "createdAt")]
+    pub created_at: u64,
+}
+
+impl TwapOrder {
+    pub fn market_index(&self) -> u64 {
+        match self.order_type {
+            TwapOrderType::Open(params) => params.market_index,
+            TwapOrderType::Close(params) => params.market_index,
+        }
+    }
+
+    pub fn owner(&self) -> Principal {
+        match self.order_type {
+            TwapOrderType::Open(params) => params.owner,
+            TwapOrderType::Close(params) => params.owner,
+        }
+    }
+
+    /// Child Collateral
+    ///
+    /// the collateral of a child of an open order,
+    /// the last child also takes the remainder of the division
+    pub fn child_collateral(&self, index: u64) -> u128 {
+        let TwapOrderType::Open(params) = self.order_type else {
+            return 0;
+        };
+
+        let collateral_per_child = params.collateral / self.children as u128;
+
+        if index + 1 == self.children {
+            params.collateral - collateral_per_child * (self.children as u128 - 1)
+        } else {
+            collateral_per_child
+        }
+    }
+
+    /// Reserved Collateral
+    ///
+    /// the collateral of the children not yet executed, reserved from the owner's balance
+    pub fn reserved_collateral(&self) -> u128 {
+        (self.executed_children..self.children)
+            .map(|index| self.child_collateral(index))
+            .sum()
+    }
+
+    /// Next Child Operation
+    ///
+    /// the operation executed by the next child and the size it fills
+    ///
+    /// Returns None if the position closed by the order no longer exists
+    pub fn next_child_operation(&self) -> Option<(PriceWaitingOperation, u128)> {
+        let index = self.executed_children;
+
+        match self.order_type {
+            TwapOrderType::Open(params) => {
+                let collateral = self.child_collateral(index);
+
+                // a new position is opened if the position opened by the previous children was closed
+                let position_id = self.position_id.filter(|position_id| {
+                    try_get_user_position_details(params.owner, *position_id)
+                        .is_some_and(|(market_index, _)| market_index == params.market_index)
+                });
+
+                let operation = match position_id {
+                    Some(position_id) => {
+                        PriceWaitingOperation::IncreasePosition(IncreasePositionParams {
+                            owner: params.owner,
+                            market_index: params.market_index,
+                            position_id,
+                            collateral,
+                            leverage_factor: params.leverage_factor,
+                            acceptable_price_limit: params.acceptable_price_limit,
+                            reserve_factor: params.reserve_factor,
+                            deadline: None,
+                        })
+                    }
+                    None => PriceWaitingOperation::OpenPosition(OpenPositionParams {
+                        collateral,
+                        deadline: None,
+                        ..params
+                    }),
+                };
+
+                Some((operation, collateral))
+            }
+            TwapOrderType::Close(params) => {
+                let (market_index, position) =
+                    try_get_user_position_details(params.owner, params.position_id)?;
+
+                if market_index != params.market_index {
+                    return None;
+                }
+
+                let remaining_children = (self.children - index) as u128;
+
+                let size_delta = if remaining_children == 1 {
+                    None
+                } else {
+                    Some(position.units / remaining_children)
+                };
+
+                let operation = PriceWaitingOperation::ClosePosition(ClosePositionParams {
+                    size_delta,
+                    deadline: None,
+                    ..params
+                });
+
+                Some((operation, size_delta.unwrap_or(position.units)))
+            }
+        }
+    }
+}
+
+impl Storable for TwapOrder {
+    fn to_bytes(&self) -> Cow<'_, [u8]> {
+        let serialized = bincode::serialize(self).expect("failed to serialize");
+        Cow::Owned(serialized)
+    }
+
+    /// Converts the element into an owned byte vector.
+    ///
+    /// This method consumes `self` and avoids cloning when possible.
+    fn into_bytes(self) -> Vec<u8> {
+        bincode::serialize(&self).expect("failed to serialize")
+    }
+
+    /// Converts bytes into an element.
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        bincode::deserialize(bytes.as_ref()).expect("failed to desearalize")
+    }
+
+    /// The size bounds of the type.
+    const BOUND: Bound = Bound::Unbounded;
+}
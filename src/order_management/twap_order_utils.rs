@@ -0,0 +1,182 @@
+use std::time::Duration;
+
+use ic_cdk::api::time;
+
+use crate::constants::_ONE_SECOND;
+use crate::market::market_status::market_accepts;
+use crate::order_management::twap_order::{
+    TwapChildFailure, TwapOrder, TwapOrderStatus, TwapOrderType,
+};
+use crate::pricing_update_management::operation_status::{OperationOutcome, OperationStatus};
+use crate::pricing_update_management::price_fetch::update_price;
+use crate::pricing_update_management::price_waiting_operation_trait::PriceWaitingOperationTrait;
+use crate::stable_memory::{TWAP_ORDERS, TWAP_ORDERS_COUNTER, TWAP_ORDERS_TIMERS};
+
+pub fn get_twap_order(order_id: u64) -> Option<TwapOrder> {
+    TWAP_ORDERS.with_borrow(|reference| reference.get(&order_id))
+}
+
+pub fn set_twap_order(order_id: u64, order: TwapOrder) {
+    TWAP_ORDERS.with_borrow_mut(|reference| {
+        reference.insert(order_id, order);
+    });
+}
+
+/// Put TWAP Order
+///
+/// stores a new TWAP order and schedules the execution of its first child
+///
+/// Returns the order id
+pub fn put_twap_order(order: TwapOrder) -> u64 {
+    let order_id = TWAP_ORDERS_COUNTER.with_borrow_mut(|reference| {
+        let order_id = *reference.get();
+        reference.set(order_id + 1);
+        order_id
+    });
+
+    let delay = order.next_execution_time.saturating_sub(time());
+
+    set_twap_order(order_id, order);
+
+    _set_twap_order_timer(order_id, delay);
+
+    order_id
+}
+
+/// Rearm TWAP Orders Timers
+///
+/// schedules the next child execution of every active TWAP order,
+/// children whose execution time passed during the upgrade are executed immediately
+/// @dev called after an upgrade since timers do not persist
+pub fn rearm_twap_orders_timers() {
+    let current_time = time();
+
+    let orders: Vec<(u64, u64)> = TWAP_ORDERS.with_borrow(|reference| {
+        reference
+            .iter()
+            .map(|entry| entry.into_pair())
+            .filter(|(_, order)| order.status == TwapOrderStatus::Active)
+            .map(|(order_id, order)| (order_id, order.next_execution_time))
+            .collect()
+    });
+
+    for (order_id, next_execution_time) in orders {
+        _set_twap_order_timer(order_id, next_execution_time.saturating_sub(current_time));
+    }
+}
+
+pub fn clear_twap_order_timer(order_id: u64) {
+    TWAP_ORDERS_TIMERS.with_borrow_mut(|reference| {
+        if let Some(timer_id) = reference.remove(&order_id) {
+            ic_cdk_timers::clear_timer(timer_id);
+        }
+    });
+}
+
+/// Execute TWAP Order Child
+///
+/// executes the next child of a TWAP order,
+/// if the market price is not current it is updated and the child is retried once,
+/// if the protocol is paused or the market does not accept the child it is postponed by one interval
+pub async fn execute_twap_order_child(order_id: u64) {
+    TWAP_ORDERS_TIMERS.with_borrow_mut(|reference| {
+        reference.remove(&order_id);
+    });
+
+    let Some(market_index) = _execute_twap_order_child(order_id, false) else {
+        return;
+    };
+
+    // retried later if the price update fails
+    let Some(order) = get_twap_order(order_id) else {
+        return;
+    };
+    _set_twap_order_timer(order_id, order.interval_in_secs * _ONE_SECOND);
+
+    update_price(market_index).await;
+
+    _execute_twap_order_child(order_id, true);
+}
+
+/// executes the next child of an active TWAP order and records its outcome
+///
+/// Returns the market index if the child is waiting for a price update (only when it is not the last attempt)
+fn _execute_twap_order_child(order_id: u64, last_attempt: bool) -> Option<u64> {
+    let mut order = get_twap_order(order_id)?;
+
+    if order.status != TwapOrderStatus::Active {
+        return None;
+    }
+
+    // postponed by one interval while the market does not accept the child
+    let opens = matches!(order.order_type, TwapOrderType::Open(_));
+    if !market_accepts(order.market_index(), opens) {
+        order.next_execution_time = time() + order.interval_in_secs * _ONE_SECOND;
+        _set_twap_order_timer(order_id, order.interval_in_secs * _ONE_SECOND);
+        set_twap_order(order_id, order);
+        return None;
+    }
+
+    let status = match order.next_child_operation() {
+        Some((operation, size)) => {
+            // the collateral of the child is released to be taken on execution
+            operation.release();
+
+            match operation.execute() {
+                OperationStatus::Pending if !last_attempt => {
+                    operation.reserve();
+                    return Some(order.market_index());
+                }
+                OperationStatus::Pending => {
+                    OperationStatus::Failed("Market price could not be updated".to_string())
+                }
+                OperationStatus::Settled(outcome) => {
+                    order.filled_size += size;
+
+                    if let OperationOutcome::Position { position_id, .. } = outcome {
+                        order.position_id = Some(position_id);
+                    }
+
+                    OperationStatus::Settled(outcome)
+                }
+                status => status,
+            }
+        }
+        None => OperationStatus::Failed("Position does not exist".to_string()),
+    };
+
+    if let OperationStatus::Failed(reason) = status {
+        order.failed_children.push(TwapChildFailure {
+            index: order.executed_children,
+            reason,
+        });
+    }
+
+    order.executed_children += 1;
+
+    if order.executed_children == order.children {
+        order.status = TwapOrderStatus::Completed;
+        clear_twap_order_timer(order_id);
+    } else {
+        order.next_execution_time = time() + order.interval_in_secs * _ONE_SECOND;
+        _set_twap_order_timer(order_id, order.interval_in_secs * _ONE_SECOND);
+    }
+
+    set_twap_order(order_id, order);
+
+    None
+}
+
+fn _set_twap_order_timer(order_id: u64, delay: u64) {
+    let new_timer = ic_cdk_timers::set_timer(Duration::from_nanos(delay), move || {
+        ic_cdk::futures::spawn(async move {
+            execute_twap_order_child(order_id).await;
+        });
+    });
+
+    TWAP_ORDERS_TIMERS.with_borrow_mut(|reference| {
+        if let Some(timer_id) = reference.insert(order_id, new_timer) {
+            ic_cdk_timers::clear_timer(timer_id);
+        }
+    });
+}
@@ -4,10 +4,9 @@ use candid::{CandidType, Principal};
 use ic_stable_structures::{Storable, storable::Bound};
 use serde::{Deserialize, Serialize};
 
-use crate::math::math::{apply_precision, bound_above_signed};
+use crate::math::math::{apply_precision, bound_above_signed, mul_div};
 
 /// Position
-
 #[cfg_attr(test, derive(Debug, PartialEq, Eq))]
 #[derive(Copy, Clone, Deserialize, CandidType, Serialize)]
 pub struct PositionDetails {
@@ -62,7 +61,7 @@ impl PositionDetails {
         let net_borrowing_factor: u128 =
             current_cummulative_borrowing_factor - self.pre_cummulative_borrowing_factor;
         let open_interest = self.open_interest();
-        return apply_precision(net_borrowing_factor, open_interest);
+        apply_precision(net_borrowing_factor, open_interest)
     }
 
     pub fn get_net_funding_fee(&self, current_cummulative_funding_factor: i128) -> i128 {
@@ -73,12 +72,71 @@ impl PositionDetails {
 
         let open_interest = self.open_interest();
 
-        return apply_precision(net_funding_factor.abs() as u128, open_interest) as i128 * sign;
+        apply_precision(net_funding_factor.unsigned_abs(), open_interest) as i128 * sign
     }
 
     pub fn open_interest(&self) -> u128 {
         self.debt + self.collateral
     }
+
+    /// Split
+    ///
+    /// splits the position into the part with `units_delta` units and the remaining part,
+    /// collateral, debt and max reserve are split proportionally to the units
+    /// @dev both parts keep the funding and borrowing checkpoints so each pays its share of the fees accrued
+    ///
+    /// Returns the part with `units_delta` units and the remaining part
+    pub fn split(&self, units_delta: u128) -> (PositionDetails, PositionDetails) {
+        let Self {
+            collateral,
+            debt,
+            units,
+            max_reserve,
+            ..
+        } = *self;
+
+        let split_position = PositionDetails {
+            collateral: mul_div(collateral, units_delta, units),
+            debt: mul_div(debt, units_delta, units),
+            units: units_delta,
+            max_reserve: mul_div(max_reserve, units_delta, units),
+            ..*self
+        };
+
+        let remaining_position = PositionDetails {
+            collateral: collateral - split_position.collateral,
+            debt: debt - split_position.debt,
+            units: units - units_delta,
+            max_reserve: max_reserve - split_position.max_reserve,
+            ..*self
+        };
+
+        (split_position, remaining_position)
+    }
+
+    /// Is Liquidatable
+    ///
+    /// A position is liquidatable when its remaining value (collateral after funding and borrowing fees plus pnl)
+    /// drops to or below the liquidation factor share of its current collateral
+    pub fn is_liquidatable(
+        &self,
+        price: u128,
+        current_cummulative_funding_factor: i128,
+        current_cummulative_borrowing_factor: u128,
+        liquidation_factor: u128,
+    ) -> bool {
+        let current_collateral = self.collateral as i128
+            + self.get_net_funding_fee(current_cummulative_funding_factor)
+            - self.get_net_borrowing_fee(current_cummulative_borrowing_factor) as i128;
+
+        if current_collateral <= 0 {
+            return true;
+        }
+
+        let remaining_value = current_collateral + self.get_pnl(price);
+
+        remaining_value <= apply_precision(liquidation_factor, current_collateral as u128) as i128
+    }
 }
 
 impl Storable for PositionDetails {
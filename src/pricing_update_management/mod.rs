@@ -1,3 +1,4 @@
+pub mod operation_status;
 pub mod price_fetch;
 pub mod price_waiting_operation_trait;
 pub mod price_waiting_operation_utils;
@@ -0,0 +1,175 @@
+use std::borrow::Cow;
+
+use candid::CandidType;
+use ic_stable_structures::{Memory, StableBTreeMap, Storable, storable::Bound};
+use serde::{Deserialize, Serialize};
+
+use crate::close_position::close_position_result::ClosePositionResult;
+use crate::constants::{MAX_RETAINED_STATUSES, STATUS_PRUNING_BATCH};
+use crate::liquidate_position::liquidate_position_result::LiquidatePositionResult;
+use crate::market::functions::open_position_in_market::{
+    FailureReason, OpenPositioninMarketResult,
+};
+use crate::market::market_details::LiquidityOperationResult;
+use crate::position::position_details::PositionDetails;
+use crate::stable_memory::{PRICE_WAITING_OPERATIONS_COUNTER, PRICE_WAITING_OPERATIONS_STATUS};
+
+/// Operation Status
+///
+/// the status of a price waiting operation, tracked by the ticket returned when it was queued
+#[cfg_attr(test, derive(Debug, PartialEq, Eq))]
+#[derive(CandidType, Deserialize, Serialize, Clone)]
+pub enum OperationStatus {
+    /// Operation is queued and waiting for a price update
+    Pending,
+    /// Operation was executed successfully
+    Settled(OperationOutcome),
+    /// Operation failed with the reason
+    Failed(String),
+    /// Operation was cancelled by its executor before it was executed
+    Cancelled,
+}
+
+/// Operation Outcome
+///
+/// the output of a settled operation
+#[cfg_attr(test, derive(Debug, PartialEq, Eq))]
+#[derive(CandidType, Deserialize, Serialize, Clone)]
+pub enum OperationOutcome {
+    /// The position opened and its ID
+    Position {
+        #[serde(rename = "positionId")]
+        position_id: u64,
+        position: PositionDetails,
+    },
+    /// The amount received (quote asset for closes and liquidity removals, market shares for liquidity additions)
+    Amount(u128),
+    /// Operation has no output (e.g borrow fees collection)
+    Executed,
+}
+
+pub fn get_operation_status(ticket: u64) -> Option<OperationStatus> {
+    PRICE_WAITING_OPERATIONS_STATUS.with_borrow(|reference| reference.get(&ticket))
+}
+
+pub fn set_operation_status(ticket: u64, status: OperationStatus) {
+    let next_ticket = PRICE_WAITING_OPERATIONS_COUNTER.with_borrow(|reference| *reference.get());
+
+    PRICE_WAITING_OPERATIONS_STATUS.with_borrow_mut(|reference| {
+        reference.insert(ticket, status);
+
+        prune_statuses(reference, next_ticket);
+    });
+}
+
+/// Prune Statuses
+///
+/// removes the final statuses (settled, failed or cancelled) of ids older than the latest
+/// `MAX_RETAINED_STATUSES` ids, checking at most `STATUS_PRUNING_BATCH` of them
+/// @dev called every time a status is set so the status maps stay bounded,
+/// pending statuses are kept until the operation (or order) is executed or cancelled
+pub fn prune_statuses<M: Memory>(
+    statuses: &mut StableBTreeMap<u64, OperationStatus, M>,
+    next_id: u64,
+) {
+    let Some(oldest_retained_id) = next_id.checked_sub(MAX_RETAINED_STATUSES) else {
+        return;
+    };
+
+    let prunable_ids: Vec<u64> = statuses
+        .range(..oldest_retained_id)
+        .take(STATUS_PRUNING_BATCH)
+        .map(|entry| entry.into_pair())
+        .filter(|(_, status)| !matches!(status, OperationStatus::Pending))
+        .map(|(id, _)| id)
+        .collect();
+
+    for id in prunable_ids {
+        statuses.remove(&id);
+    }
+}
+
+impl From<OpenPositioninMarketResult> for OperationStatus {
+    fn from(result: OpenPositioninMarketResult) -> Self {
+        match result {
+            OpenPositioninMarketResult::Settled {
+                position_id,
+                position,
+                ..
+            } => OperationStatus::Settled(OperationOutcome::Position {
+                position_id: position_id.unwrap_or_default(),
+                position,
+            }),
+            OpenPositioninMarketResult::Waiting { .. } => OperationStatus::Pending,
+            OpenPositioninMarketResult::Failed { reason } => {
+                let reason = match reason {
+                    FailureReason::PriceLimitExceeded => "Price limit exceeded",
+                    FailureReason::InsufficientBalance => "Insufficient balance",
+                    FailureReason::MarketNotActive => "Market is not active",
+                    FailureReason::Other => "Position could not be opened",
+                };
+                OperationStatus::Failed(reason.to_string())
+            }
+        }
+    }
+}
+
+impl From<ClosePositionResult> for OperationStatus {
+    fn from(result: ClosePositionResult) -> Self {
+        match result {
+            ClosePositionResult::Settled { returns, .. } => {
+                OperationStatus::Settled(OperationOutcome::Amount(returns))
+            }
+            ClosePositionResult::Waiting { .. } => OperationStatus::Pending,
+            ClosePositionResult::Failed => {
+                OperationStatus::Failed("Position could not be closed".to_string())
+            }
+        }
+    }
+}
+
+impl From<LiquidityOperationResult> for OperationStatus {
+    fn from(result: LiquidityOperationResult) -> Self {
+        match result {
+            LiquidityOperationResult::Settled { amount_out } => {
+                OperationStatus::Settled(OperationOutcome::Amount(amount_out))
+            }
+            LiquidityOperationResult::Waiting { .. } => OperationStatus::Pending,
+            LiquidityOperationResult::Failed(reason) => OperationStatus::Failed(reason),
+        }
+    }
+}
+
+impl From<LiquidatePositionResult> for OperationStatus {
+    fn from(result: LiquidatePositionResult) -> Self {
+        match result {
+            LiquidatePositionResult::Settled { returns, .. } => {
+                OperationStatus::Settled(OperationOutcome::Amount(returns))
+            }
+            LiquidatePositionResult::Waiting { .. } => OperationStatus::Pending,
+            LiquidatePositionResult::Failed(reason) => OperationStatus::Failed(reason),
+        }
+    }
+}
+
+impl Storable for OperationStatus {
+    fn to_bytes(&self) -> Cow<'_, [u8]> {
+        let serialized = bincode::serialize(self).expect("failed to serialize");
+        Cow::Owned(serialized)
+    }
+
+    /// Converts the element into an owned byte vector.
+    ///
+    /// This method consumes `self` and avoids cloning when possible.
+    fn into_bytes(self) -> Vec<u8> {
+        bincode::serialize(&self).expect("failed to serialize")
+    }
+
+    /// Converts bytes into an element.
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        bincode::deserialize(bytes.as_ref()).expect("failed to desearalize")
+    }
+
+    /// The size bounds of the type.
+    const BOUND: Bound = Bound::Unbounded;
+}
@@ -6,14 +6,21 @@ use ic_cdk::call::Call;
 use serde::{Deserialize, Serialize};
 
 use crate::house_settings::get_house_asset_pricing_details;
+use crate::order_management::triggers_scan::start_triggers_scan;
 use crate::stable_memory::MARKETS_LIST;
 
 const XRC_ID: &str = "uf6dk-hyaaa-aaaaq-qaaaq-cai";
+/// error code used for failures of the call to the exchange rate canister itself
+const XRC_CALL_FAILED_ERROR_CODE: u32 = 0;
 
 pub async fn update_price(market_index: u64) {
-    let mut market = MARKETS_LIST.with_borrow(|reference| reference.get(market_index).unwrap());
+    let base_asset = MARKETS_LIST.with_borrow(|reference| {
+        reference
+            .get(market_index)
+            .unwrap()
+            .index_asset_pricing_details()
+    });
     let quote_asset = get_house_asset_pricing_details();
-    let base_asset = market.index_asset_pricing_details();
 
     let request = GetExchangeRateRequest {
         base_asset,
@@ -23,39 +30,55 @@ pub async fn update_price(market_index: u64) {
 
     let result: GetExchangeRateResult = _get_exchange_rate(request).await;
     if let Ok(response) = result {
-        market._update_price(response.rate, response.metadata.decimals);
-        //  last_price_update_timer = time();
+        // the market is read again after the call since it may have changed while the price was fetched
         MARKETS_LIST.with_borrow_mut(|reference| {
+            let mut market = reference.get(market_index).unwrap();
+            market._update_price(response.rate, response.metadata.decimals);
             reference.set(market_index, &market);
         });
+
+        start_triggers_scan(market_index);
     }
 }
 
 /// tries to fetch the current exchange rate of the pair and returns the result
+/// @dev a rejected call or an undecodable response is returned as `ExchangeRateError::Other`
+/// instead of trapping, so the state changes made before the call are kept
 pub async fn _get_exchange_rate(request: GetExchangeRateRequest) -> GetExchangeRateResult {
     let canister_id = Principal::from_str(XRC_ID).unwrap();
     let call = Call::unbounded_wait(canister_id, "get_exchange_rate")
         .with_arg(request)
         .with_cycles(1_000_000_000); // i trillion
 
-    return call.await.unwrap().candid().unwrap();
+    let response = match call.await {
+        Ok(response) => response,
+        Err(error) => {
+            return Err(ExchangeRateError::Other(OtherError {
+                code: XRC_CALL_FAILED_ERROR_CODE,
+                description: error.to_string(),
+            }));
+        }
+    };
+
+    match response.candid::<GetExchangeRateResult>() {
+        Ok(result) => result,
+        Err(error) => Err(ExchangeRateError::Other(OtherError {
+            code: XRC_CALL_FAILED_ERROR_CODE,
+            description: error.to_string(),
+        })),
+    }
 }
 
 #[cfg_attr(test, derive(Debug, PartialEq, Eq))]
-#[derive(Deserialize, Serialize, Copy, Clone, CandidType)]
+#[derive(Deserialize, Serialize, Copy, Clone, CandidType, Default)]
 pub enum AssetClass {
     /// The cryptocurrency asset class.
+    #[default]
     Cryptocurrency,
     /// The fiat currency asset class.
     FiatCurrency,
 }
 
-impl Default for AssetClass {
-    fn default() -> Self {
-        AssetClass::Cryptocurrency
-    }
-}
-
 #[derive(CandidType, Deserialize)]
 pub struct GetExchangeRateRequest {
     /// The base asset, i.e., the first asset in a currency pair. For example,
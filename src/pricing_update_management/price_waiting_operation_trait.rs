@@ -1,35 +1,135 @@
+use std::borrow::Cow;
+
+use candid::Principal;
+use ic_stable_structures::{Storable, storable::Bound};
+use serde::{Deserialize, Serialize};
+
 use crate::{
     add_liquidity::add_liquidity_params::AddLiquidityParams,
     admin_roles::collect_borrowing_fees::CollectBorrowFeesParams,
     close_position::close_position_params::ClosePositionParams,
+    increase_position::increase_position_params::IncreasePositionParams,
+    liquidate_position::liquidate_position_params::LiquidatePositionParams,
     open_position::open_position_params::OpenPositionParams,
+    pricing_update_management::operation_status::OperationStatus,
+    remove_collateral::remove_collateral_params::RemoveCollateralParams,
     remove_liquidity::remove_liquidity_params::RemoveLiquidityParams,
 };
 
 /// A trait for all price waiting operations to enable a priority list for maximum execution
 pub trait PriceWaitingOperationTrait {
     /// excutes the paritucular operation on the market
-    fn execute(&self);
+    ///
+    /// Returns the status of the operation after execution
+    fn execute(&self) -> OperationStatus;
+
+    /// the principal that queued the operation and can cancel it
+    ///
+    /// None for operations queued by the house
+    fn executor(&self) -> Option<Principal>;
+
+    /// the time after which the operation is failed if it has not been executed
+    fn deadline(&self) -> Option<u64> {
+        None
+    }
+
+    /// reserves the amounts used by the operation from the executor's balances while it is queued
+    fn reserve(&self) {}
 
-    //  fn executor(&self) -> Principal;
+    /// releases the amounts reserved for the operation back to the executor's balances
+    fn release(&self) {}
 }
 
+/// Price Waiting Operation
+///
+/// an operation queued on a market until its price is updated
+/// @dev stored in stable memory so queued operations survive upgrades
+#[derive(Serialize, Deserialize, Clone)]
 pub enum PriceWaitingOperation {
     OpenPosition(OpenPositionParams),
     ClosePosition(ClosePositionParams),
     AddLiquidity(AddLiquidityParams),
     RemoveLiquidity(RemoveLiquidityParams),
     CollectBorrowFees(CollectBorrowFeesParams),
+    LiquidatePosition(LiquidatePositionParams),
+    IncreasePosition(IncreasePositionParams),
+    RemoveCollateral(RemoveCollateralParams),
 }
 
-impl PriceWaitingOperationTrait for PriceWaitingOperation {
-    fn execute(&self) {
+impl PriceWaitingOperation {
+    fn params(&self) -> &dyn PriceWaitingOperationTrait {
         match self {
-            PriceWaitingOperation::OpenPosition(params) => params.execute(),
-            PriceWaitingOperation::ClosePosition(params) => params.execute(),
-            PriceWaitingOperation::AddLiquidity(params) => params.execute(),
-            PriceWaitingOperation::RemoveLiquidity(params) => params.execute(),
-            PriceWaitingOperation::CollectBorrowFees(params) => params.execute(),
+            PriceWaitingOperation::OpenPosition(params) => params,
+            PriceWaitingOperation::ClosePosition(params) => params,
+            PriceWaitingOperation::AddLiquidity(params) => params,
+            PriceWaitingOperation::RemoveLiquidity(params) => params,
+            PriceWaitingOperation::CollectBorrowFees(params) => params,
+            PriceWaitingOperation::LiquidatePosition(params) => params,
+            PriceWaitingOperation::IncreasePosition(params) => params,
+            PriceWaitingOperation::RemoveCollateral(params) => params,
+        }
+    }
+}
+
+impl PriceWaitingOperationTrait for PriceWaitingOperation {
+    fn execute(&self) -> OperationStatus {
+        self.params().execute()
+    }
+
+    fn executor(&self) -> Option<Principal> {
+        self.params().executor()
+    }
+
+    fn deadline(&self) -> Option<u64> {
+        self.params().deadline()
+    }
+
+    fn reserve(&self) {
+        self.params().reserve()
+    }
+
+    fn release(&self) {
+        self.params().release()
+    }
+}
+
+/// Layout version of the stored price waiting operations
+///
+/// operations are stored after their layout version so operations queued before an upgrade
+/// can still be decoded after it, the version is bumped whenever the params of an operation change
+/// and the operations stored with the previous version are decoded into the new layout in `from_bytes`
+pub const PRICE_WAITING_OPERATION_LAYOUT_VERSION: u8 = 1;
+
+impl Storable for PriceWaitingOperation {
+    fn to_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Owned(self.clone().into_bytes())
+    }
+
+    /// Converts the element into an owned byte vector.
+    ///
+    /// This method consumes `self` and avoids cloning when possible.
+    fn into_bytes(self) -> Vec<u8> {
+        let mut bytes = vec![PRICE_WAITING_OPERATION_LAYOUT_VERSION];
+        bincode::serialize_into(&mut bytes, &self).expect("failed to serialize");
+        bytes
+    }
+
+    /// Converts bytes into an element.
+    ///
+    /// @dev panics for a layout version without a decoder, so operations are never decoded with the wrong layout
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        let Some((version, operation)) = bytes.split_first() else {
+            panic!("empty price waiting operation");
+        };
+
+        match *version {
+            PRICE_WAITING_OPERATION_LAYOUT_VERSION => {
+                bincode::deserialize(operation).expect("failed to desearalize")
+            }
+            version => panic!("unknown price waiting operation layout version {}", version),
         }
     }
+
+    /// The size bounds of the type.
+    const BOUND: Bound = Bound::Unbounded;
 }
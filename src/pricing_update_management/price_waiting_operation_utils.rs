@@ -1,64 +1,276 @@
-use std::collections::HashMap;
+use std::collections::BTreeSet;
 use std::time::Duration;
 
-use crate::constants::MAX_ALLOWED_PRICE_CHANGE_INTERVAL;
+use crate::constants::{
+    MAX_ALLOWED_PRICE_CHANGE_INTERVAL, PRICE_WAITING_OPERATIONS_EXECUTION_DELAY,
+    PRICE_WAITING_OPERATIONS_RETRY_INTERVAL,
+};
+use crate::pricing_update_management::operation_status::{OperationStatus, set_operation_status};
 use crate::pricing_update_management::price_fetch::update_price;
 use crate::pricing_update_management::price_waiting_operation_trait::{
     PriceWaitingOperation, PriceWaitingOperationTrait,
 };
-use crate::stable_memory::MARKET_PRICE_WAITING_OPERATION;
+use crate::stable_memory::{
+    MARKET_PRICE_WAITING_OPERATION, MARKET_PRICE_WAITING_OPERATION_TIMERS,
+    PRICE_WAITING_OPERATIONS_COUNTER, PRICE_WAITING_OPERATIONS_KEYS,
+};
 
 use ic_cdk::api::time;
 
 pub fn is_within_price_update_interval(last_price_update_time: u64) -> bool {
     let current_time = time();
 
-    return current_time - last_price_update_time <= MAX_ALLOWED_PRICE_CHANGE_INTERVAL;
+    current_time - last_price_update_time <= MAX_ALLOWED_PRICE_CHANGE_INTERVAL
 }
 
+/// Put Price Waiting Operation
+///
+/// queues an operation on a market until the next price update and schedules the price update
+/// if it is not already scheduled
+///
+/// Returns the operation id, used as the ticket for querying the operation status
 pub fn put_price_waiting_operation(
     market_index: u64,
     operation_priority_index: u8,
     operation: PriceWaitingOperation,
-) -> usize {
-    let new_timer = ic_cdk_timers::set_timer(Duration::from_millis(500), move || {
-        ic_cdk::futures::spawn(async move {
-            schedule_execution_of_price_waiting_operations(market_index).await;
-        });
+) -> u64 {
+    let operation_id =
+        _queue_price_waiting_operation(market_index, operation_priority_index, operation);
+
+    // the timer is not reset so operations are not delayed by newer ones
+    let timer_is_set = MARKET_PRICE_WAITING_OPERATION_TIMERS
+        .with_borrow(|reference| reference.contains_key(&market_index));
+
+    if !timer_is_set {
+        _set_price_waiting_operations_timer(market_index, PRICE_WAITING_OPERATIONS_EXECUTION_DELAY);
+    }
+
+    operation_id
+}
+
+/// queues an operation with the next operation id, reserves its amounts and sets its status to pending
+///
+/// Returns the operation id
+pub fn _queue_price_waiting_operation(
+    market_index: u64,
+    operation_priority_index: u8,
+    operation: PriceWaitingOperation,
+) -> u64 {
+    let operation_id = PRICE_WAITING_OPERATIONS_COUNTER.with_borrow_mut(|reference| {
+        let operation_id = *reference.get();
+        reference.set(operation_id + 1);
+        operation_id
     });
 
-    MARKET_PRICE_WAITING_OPERATION.with_borrow_mut(|reference| {
-        let (_, operations) = reference
-            .entry(market_index)
-            .and_modify(|value| {
-                let (timer_id, _) = value;
-                ic_cdk_timers::clear_timer(*timer_id);
-
-                *timer_id = new_timer;
-            })
-            .or_insert((new_timer, HashMap::new()));
-
-        operations
-            .get_mut(&operation_priority_index)
-            .unwrap_or(&mut Vec::new())
-            .push(operation);
-
-        return operations.len() - 1;
-    })
+    // amounts used by the operation are reserved until it is executed or cancelled
+    operation.reserve();
+
+    _insert_price_waiting_operation(
+        (market_index, operation_priority_index, operation_id),
+        operation,
+    );
+
+    set_operation_status(operation_id, OperationStatus::Pending);
+
+    operation_id
 }
 
+/// Rearm Price Waiting Operations Timers
+///
+/// sets the execution timer for every market with queued operations
+/// @dev called after an upgrade since timers do not persist
+pub fn rearm_price_waiting_operations_timers() {
+    let markets: BTreeSet<u64> = MARKET_PRICE_WAITING_OPERATION.with_borrow(|reference| {
+        reference
+            .keys()
+            .map(|(market_index, _, _)| market_index)
+            .collect()
+    });
+
+    for market_index in markets {
+        _set_price_waiting_operations_timer(market_index, PRICE_WAITING_OPERATIONS_EXECUTION_DELAY);
+    }
+}
+
+/// Get Price Waiting Operation
+///
+/// finds a queued operation by its operation id
+///
+/// Returns the key (market index, priority index and operation id) and the operation
+pub fn get_price_waiting_operation(
+    operation_id: u64,
+) -> Option<((u64, u8, u64), PriceWaitingOperation)> {
+    let (market_index, operation_priority_index) =
+        PRICE_WAITING_OPERATIONS_KEYS.with_borrow(|reference| reference.get(&operation_id))?;
+
+    let key = (market_index, operation_priority_index, operation_id);
+
+    MARKET_PRICE_WAITING_OPERATION
+        .with_borrow(|reference| reference.get(&key))
+        .map(|operation| (key, operation))
+}
+
+/// Remove Price Waiting Operation
+///
+/// removes a queued operation and clears the market's execution timer if no other operation is queued
+pub fn remove_price_waiting_operation(key: (u64, u8, u64)) -> Option<PriceWaitingOperation> {
+    let (market_index, _, operation_id) = key;
+
+    PRICE_WAITING_OPERATIONS_KEYS.with_borrow_mut(|reference| {
+        reference.remove(&operation_id);
+    });
+
+    let (operation, market_queue_is_empty) =
+        MARKET_PRICE_WAITING_OPERATION.with_borrow_mut(|reference| {
+            let operation = reference.remove(&key);
+            let market_queue_is_empty = reference
+                .keys_range((market_index, 0, 0)..=(market_index, u8::MAX, u64::MAX))
+                .next()
+                .is_none();
+
+            (operation, market_queue_is_empty)
+        });
+
+    if market_queue_is_empty {
+        MARKET_PRICE_WAITING_OPERATION_TIMERS.with_borrow_mut(|reference| {
+            if let Some(timer_id) = reference.remove(&market_index) {
+                ic_cdk_timers::clear_timer(timer_id);
+            }
+        });
+    }
+
+    operation
+}
+
+/// Schedule Execution Of Price Waiting Operations
+///
+/// updates the market's price and executes all its queued operations
+/// strictly in order of priority index and then operation id (FIFO)
+///
+/// operations past their deadline are failed,
+/// operations that could not be executed because the price update failed are retried until their deadline
 pub async fn schedule_execution_of_price_waiting_operations(market_index: u64) {
-    update_price(market_index).await;
+    // removed before the price update so operations queued while it is awaited (or after it failed)
+    // always arm a new timer instead of waiting on this one
+    MARKET_PRICE_WAITING_OPERATION_TIMERS.with_borrow_mut(|reference| {
+        reference.remove(&market_index);
+    });
 
-    let (_, operations) = MARKET_PRICE_WAITING_OPERATION.with_borrow_mut(|reference| {
+    let market_queue_is_empty = MARKET_PRICE_WAITING_OPERATION.with_borrow(|reference| {
         reference
-            .remove(&market_index)
-            .expect("Market was removed before oepration started")
+            .keys_range((market_index, 0, 0)..=(market_index, u8::MAX, u64::MAX))
+            .next()
+            .is_none()
     });
 
-    for operations in operations.values().into_iter() {
-        for operation in operations.iter() {
-            operation.execute();
+    if market_queue_is_empty {
+        return;
+    }
+
+    update_price(market_index).await;
+
+    let operations = _take_price_waiting_operations(market_index);
+
+    if let Some(delay) = _execute_price_waiting_operations(operations, time()) {
+        _set_price_waiting_operations_timer(market_index, delay);
+    }
+}
+
+/// removes all the queued operations of a market in the order they are executed,
+/// by priority index and then operation id
+pub fn _take_price_waiting_operations(
+    market_index: u64,
+) -> Vec<((u64, u8, u64), PriceWaitingOperation)> {
+    let operations = MARKET_PRICE_WAITING_OPERATION.with_borrow_mut(|reference| {
+        let keys: Vec<(u64, u8, u64)> = reference
+            .keys_range((market_index, 0, 0)..=(market_index, u8::MAX, u64::MAX))
+            .collect();
+
+        keys.into_iter()
+            .filter_map(|key| reference.remove(&key).map(|operation| (key, operation)))
+            .collect::<Vec<((u64, u8, u64), PriceWaitingOperation)>>()
+    });
+
+    PRICE_WAITING_OPERATIONS_KEYS.with_borrow_mut(|reference| {
+        for ((_, _, operation_id), _) in operations.iter() {
+            reference.remove(operation_id);
+        }
+    });
+
+    operations
+}
+
+/// executes the operations taken from a market's queue and sets their statuses
+///
+/// Returns the delay before the operations requeued because the price update failed are retried
+pub fn _execute_price_waiting_operations(
+    operations: Vec<((u64, u8, u64), PriceWaitingOperation)>,
+    current_time: u64,
+) -> Option<u64> {
+    let mut retry_delay: Option<u64> = None;
+
+    for (key, operation) in operations {
+        let (_, _, operation_id) = key;
+
+        operation.release();
+
+        let deadline = operation.deadline();
+
+        if deadline.is_some_and(|deadline| current_time > deadline) {
+            set_operation_status(
+                operation_id,
+                OperationStatus::Failed("Deadline exceeded".to_string()),
+            );
+            continue;
         }
+
+        let status = match (operation.execute(), deadline) {
+            (OperationStatus::Pending, Some(deadline)) => {
+                // requeued with the same key to keep its position in the queue
+                operation.reserve();
+                _insert_price_waiting_operation(key, operation);
+
+                // retried before the deadline or just after it to fail the operation
+                let delay =
+                    PRICE_WAITING_OPERATIONS_RETRY_INTERVAL.min(deadline - current_time + 1);
+                retry_delay = Some(retry_delay.map_or(delay, |retry_delay| retry_delay.min(delay)));
+                continue;
+            }
+            (OperationStatus::Pending, None) => {
+                OperationStatus::Failed("Market price could not be updated".to_string())
+            }
+            (status, _) => status,
+        };
+
+        set_operation_status(operation_id, status);
     }
+
+    retry_delay
+}
+
+fn _set_price_waiting_operations_timer(market_index: u64, delay: u64) {
+    let new_timer = ic_cdk_timers::set_timer(Duration::from_nanos(delay), move || {
+        ic_cdk::futures::spawn(async move {
+            schedule_execution_of_price_waiting_operations(market_index).await;
+        });
+    });
+
+    MARKET_PRICE_WAITING_OPERATION_TIMERS.with_borrow_mut(|reference| {
+        if let Some(timer_id) = reference.insert(market_index, new_timer) {
+            ic_cdk_timers::clear_timer(timer_id);
+        }
+    });
+}
+
+/// adds an operation to its market's queue and to the operation id index
+fn _insert_price_waiting_operation(key: (u64, u8, u64), operation: PriceWaitingOperation) {
+    let (market_index, operation_priority_index, operation_id) = key;
+
+    PRICE_WAITING_OPERATIONS_KEYS.with_borrow_mut(|reference| {
+        reference.insert(operation_id, (market_index, operation_priority_index));
+    });
+
+    MARKET_PRICE_WAITING_OPERATION.with_borrow_mut(|reference| {
+        reference.insert(key, operation);
+    });
 }
@@ -0,0 +1,9 @@
+use ic_cdk::query;
+
+use crate::delisting::delisting_details::{self, DelistingDetails};
+
+/// Gets the delisting of a market, None if the market is not being delisted.
+#[query(name = "getMarketDelisting")]
+pub fn get_market_delisting(market_index: u64) -> Option<DelistingDetails> {
+    delisting_details::get_delisting_details(market_index)
+}
@@ -0,0 +1,35 @@
+use ic_cdk::query;
+
+use crate::constants::MAX_EVENTS_QUERY_LIMIT;
+use crate::events::evnts_type::{EventFilter, EventRecord};
+use crate::stable_memory::EVENTS_LOG;
+
+/// Gets the events of the event log.
+///
+/// # Parameters
+///
+/// * `start` (u64): The sequence number to start from
+/// * `limit` (u64): The maximum number of events returned, at most `MAX_EVENTS_QUERY_LIMIT`
+/// * `filter` (Option<EventFilter>): Only events matching the filter are returned
+///
+/// # Returns
+///
+/// Returns the sequence number and the record of every matching event from `start`, in order of sequence number
+#[query(name = "getEvents")]
+pub fn get_events(start: u64, limit: u64, filter: Option<EventFilter>) -> Vec<(u64, EventRecord)> {
+    let filter = filter.unwrap_or_default();
+
+    EVENTS_LOG.with_borrow(|reference| {
+        (start..reference.len())
+            .filter_map(|sequence| reference.get(sequence).map(|record| (sequence, record)))
+            .filter(|(_, record)| filter.matches(record))
+            .take(limit.min(MAX_EVENTS_QUERY_LIMIT) as usize)
+            .collect()
+    })
+}
+
+/// Gets the number of events in the event log, the sequence number of the next event.
+#[query(name = "getEventsCount")]
+pub fn get_events_count() -> u64 {
+    EVENTS_LOG.with_borrow(|reference| reference.len())
+}
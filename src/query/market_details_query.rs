@@ -71,14 +71,14 @@ pub fn query_market_details(market_index: u64) -> QueryMarketDetailsResult {
                 // longs pay shorts
                 current_funding_factor_per_hour_long = current_funding_factor_per_sec.neg() * 3600; //ONE HOUR
                 current_funding_factor_per_hour_short = mul_div(
-                    current_funding_factor_per_hour_long.abs() as u128,
+                    current_funding_factor_per_hour_long.unsigned_abs(),
                     longs_total_open_interest,
                     shorts_total_open_interest,
                 ) as i128;
             } else {
                 current_funding_factor_per_hour_short = current_funding_factor_per_sec * 3600;
                 current_funding_factor_per_hour_long = mul_div(
-                    current_funding_factor_per_hour_short.abs() as u128,
+                    current_funding_factor_per_hour_short.unsigned_abs(),
                     shorts_total_open_interest,
                     longs_total_open_interest,
                 ) as i128;
@@ -1,2 +1,11 @@
+pub mod delisting_query;
+pub mod events_query;
 pub mod market_details_query;
+pub mod operation_status_query;
+pub mod order_query;
 pub mod position_query;
+pub mod position_triggers_query;
+pub mod roles_query;
+pub mod settlement_details_query;
+pub mod timelock_query;
+pub mod treasury_query;
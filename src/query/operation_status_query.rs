@@ -0,0 +1,22 @@
+use ic_cdk::query;
+
+use crate::pricing_update_management::operation_status::{self, OperationStatus};
+
+/// Gets the status of a price waiting operation.
+///
+/// # Parameters
+///
+/// * `ticket` (u64): The ticket returned in the `Waiting { id }` result when the operation was queued
+///
+/// # Returns
+///
+/// Returns `None` if no operation was queued with the ticket or its status was pruned
+/// (only the statuses of the latest `MAX_RETAINED_STATUSES` tickets are kept once final),
+/// otherwise the [`OperationStatus`]:
+/// - `Pending`: Operation is waiting for a price update
+/// - `Settled`: Operation was executed, with the position opened or the amount received
+/// - `Failed`: Operation failed, with the reason
+#[query(name = "getOperationStatus")]
+pub fn get_operation_status(ticket: u64) -> Option<OperationStatus> {
+    operation_status::get_operation_status(ticket)
+}
@@ -0,0 +1,78 @@
+use candid::Principal;
+use ic_cdk::query;
+
+use crate::order_management::order::Order;
+use crate::order_management::order_utils;
+use crate::order_management::twap_order::TwapOrder;
+use crate::order_management::twap_order_utils;
+use crate::pricing_update_management::operation_status::OperationStatus;
+use crate::stable_memory::{MARKET_ORDERS, TWAP_ORDERS};
+
+/// Gets the status of an order.
+///
+/// # Returns
+///
+/// Returns `None` if no order was created with the order id or its status was pruned
+/// (only the statuses of the latest `MAX_RETAINED_STATUSES` orders are kept once final),
+/// otherwise the [`OperationStatus`]:
+/// - `Pending`: Order is in the order book waiting for its trigger price
+/// - `Settled`: Order was executed, with the position opened or the amount received
+/// - `Failed`: Order was triggered but failed, with the reason
+/// - `Cancelled`: Order was cancelled by its owner
+#[query(name = "getOrderStatus")]
+pub fn get_order_status(order_id: u64) -> Option<OperationStatus> {
+    order_utils::get_order_status(order_id)
+}
+
+/// Gets the orders of a user in the order books of all markets.
+///
+/// # Returns
+///
+/// Returns the order id and the order of every order of the user waiting for its trigger price
+#[query(name = "getUserOrders")]
+pub fn get_user_orders(user: Principal) -> Vec<(u64, Order)> {
+    order_utils::get_user_orders(user)
+}
+
+/// Gets the order book of a market.
+///
+/// # Returns
+///
+/// Returns the order id and the order of every order in the market waiting for its trigger price
+#[query(name = "getMarketOrders")]
+pub fn get_market_orders(market_index: u64) -> Vec<(u64, Order)> {
+    MARKET_ORDERS.with_borrow(|reference| {
+        reference
+            .range((market_index, 0)..=(market_index, u64::MAX))
+            .map(|entry| entry.into_pair())
+            .map(|((_, order_id), order)| (order_id, order))
+            .collect()
+    })
+}
+
+/// Gets a TWAP order.
+///
+/// # Returns
+///
+/// Returns `None` if no TWAP order was created with the order id, otherwise the [`TwapOrder`]
+/// with its status, the number of children executed, the size filled and the failed children
+#[query(name = "getTwapOrder")]
+pub fn get_twap_order(order_id: u64) -> Option<TwapOrder> {
+    twap_order_utils::get_twap_order(order_id)
+}
+
+/// Gets the TWAP orders of a user.
+///
+/// # Returns
+///
+/// Returns the order id and the order of every TWAP order of the user (active, completed or cancelled)
+#[query(name = "getUserTwapOrders")]
+pub fn get_user_twap_orders(user: Principal) -> Vec<(u64, TwapOrder)> {
+    TWAP_ORDERS.with_borrow(|reference| {
+        reference
+            .iter()
+            .map(|entry| entry.into_pair())
+            .filter(|(_, order)| order.owner() == user)
+            .collect()
+    })
+}
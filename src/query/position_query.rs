@@ -59,10 +59,7 @@ pub fn get_all_user_positions_in_market(
 }
 
 fn get_market_details(market_id: u64) -> MarketDetails {
-    MARKETS_LIST.with_borrow(|market_reference| {
-        let market = market_reference.get(market_id).unwrap();
-        market
-    })
+    MARKETS_LIST.with_borrow(|market_reference| market_reference.get(market_id).unwrap())
 }
 
 #[derive(CandidType, Deserialize)]
@@ -97,7 +94,7 @@ fn get_position_current_details(
 
     let liquidation_price = _get_liquidation_price(
         current_collateral,
-        position.debt,
+        position.open_interest(),
         position.units,
         liquidation_factor,
         position.long,
@@ -113,9 +110,12 @@ fn get_position_current_details(
     }
 }
 
+/// Liquidation price
+///
+/// the price at which the position becomes liquidatable (see PositionDetails::is_liquidatable)
 fn _get_liquidation_price(
     current_collateral: u128,
-    position_debt: u128,
+    entry_value: u128,
     position_units: u128,
     liquidation_factor: u128,
     is_long: bool,
@@ -124,25 +124,21 @@ fn _get_liquidation_price(
     let max_instant_pnl_loss =
         current_collateral - apply_precision(liquidation_factor, current_collateral);
 
-    let liquidation_price = if is_long {
+    if is_long {
         // For long positions: price decreases as position loses value
         // Entry value = collateral + debt
-        let current_position_value = current_collateral + position_debt;
 
         // Liquidation occurs when position value drops by max_loss_allowed
-        let liquidation_value = current_position_value - max_instant_pnl_loss;
+        let liquidation_value = entry_value.saturating_sub(max_instant_pnl_loss);
 
         to_precision(liquidation_value, position_units)
     } else {
         // For short positions: price increases as position loses value
         // Entry value = collateral + debt
-        let entry_value = current_collateral + position_debt;
 
         // Liquidation occurs when position value increases by max_loss_allowed
         let liquidation_value = entry_value + max_instant_pnl_loss;
 
         to_precision(liquidation_value, position_units)
-    };
-
-    liquidation_price
+    }
 }
@@ -0,0 +1,18 @@
+use candid::Principal;
+use ic_cdk::query;
+
+use crate::order_management::position_triggers::{self, PositionTriggers};
+
+/// Gets the stop-loss and take-profit trigger prices of a position.
+///
+/// # Returns
+///
+/// Returns `None` if the position has no triggers set
+#[query(name = "getPositionTriggers")]
+pub fn get_position_triggers(
+    market_index: u64,
+    owner: Principal,
+    position_id: u64,
+) -> Option<PositionTriggers> {
+    position_triggers::get_position_triggers(market_index, owner, position_id)
+}
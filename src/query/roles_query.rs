@@ -0,0 +1,29 @@
+use candid::Principal;
+use ic_cdk::query;
+
+use crate::admin_roles::roles::{self, Role};
+
+#[query(name = "getOwner")]
+pub fn get_owner() -> Principal {
+    roles::get_owner()
+}
+
+/// Gets the owner proposed with `proposeOwner` that has not accepted the ownership yet.
+#[query(name = "getPendingOwner")]
+pub fn get_pending_owner() -> Option<Principal> {
+    roles::get_pending_owner()
+}
+
+/// Gets the roles granted to a principal.
+///
+/// # Returns
+///
+/// Returns the granted roles, the owner role is returned alone for the owner since it holds every role
+#[query(name = "getRoles")]
+pub fn get_roles(principal: Principal) -> Vec<Role> {
+    if principal == roles::get_owner() {
+        return vec![Role::Owner];
+    }
+
+    roles::get_granted_roles(principal).roles
+}
@@ -0,0 +1,18 @@
+use ic_cdk::query;
+
+use crate::settlement_management::settlement_details::{self, SettlementDetails};
+
+/// Gets the recurring funding and borrowing settlement details of a market.
+///
+/// # Parameters
+///
+/// * `market_index` (u64): The unique identifier of the market
+///
+/// # Returns
+///
+/// Returns `None` if the market has no settlement timer, otherwise the [`SettlementDetails`]
+/// with the interval, the last run time and the failures recorded
+#[query(name = "getMarketSettlementDetails")]
+pub fn get_market_settlement_details(market_index: u64) -> Option<SettlementDetails> {
+    settlement_details::get_settlement_details(market_index)
+}
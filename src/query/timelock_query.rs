@@ -0,0 +1,22 @@
+use ic_cdk::query;
+
+use crate::stable_memory::TIMELOCK_QUEUE;
+use crate::timelock::timelock_utils;
+use crate::timelock::timelocked_change::QueuedChange;
+
+/// Gets the risk parameter changes waiting for their timelock.
+///
+/// # Returns
+///
+/// Returns the change id and the queued change (with its earliest execution time) of every queued change
+#[query(name = "getTimelockedChanges")]
+pub fn get_timelocked_changes() -> Vec<(u64, QueuedChange)> {
+    TIMELOCK_QUEUE
+        .with_borrow(|reference| reference.iter().map(|entry| entry.into_pair()).collect())
+}
+
+/// Gets the timelock delay in seconds between queueing a risk parameter change and its earliest execution.
+#[query(name = "getTimelockDelay")]
+pub fn get_timelock_delay() -> u64 {
+    timelock_utils::get_timelock_delay()
+}
@@ -0,0 +1,25 @@
+use ic_cdk::query;
+
+use crate::stable_memory::TREASURY_SWEEPS;
+use crate::treasury::treasury_sweep::TreasurySweep;
+
+/// Gets the history of sweeps of the accumulated fees to the treasury account.
+///
+/// # Parameters
+///
+/// * `start` (u64): The sweep id to start from
+/// * `limit` (u64): The maximum number of sweeps returned
+///
+/// # Returns
+///
+/// Returns the sweep id and the sweep of every sweep from `start`, in order of execution
+#[query(name = "getTreasurySweeps")]
+pub fn get_treasury_sweeps(start: u64, limit: u64) -> Vec<(u64, TreasurySweep)> {
+    TREASURY_SWEEPS.with_borrow(|reference| {
+        reference
+            .range(start..)
+            .take(limit as usize)
+            .map(|entry| entry.into_pair())
+            .collect()
+    })
+}
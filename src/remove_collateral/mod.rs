@@ -0,0 +1,3 @@
+#[allow(clippy::module_inception)]
+pub mod remove_collateral;
+pub mod remove_collateral_params;
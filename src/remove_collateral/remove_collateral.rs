@@ -0,0 +1,126 @@
+use ic_cdk::{api::msg_caller, update};
+
+use crate::constants::CLOSE_POSITION_PRIORITY_INDEX;
+use crate::events::event_log::record_event;
+use crate::events::evnts_type::Events;
+use crate::house_settings::is_paused;
+use crate::market::functions::open_position_in_market::{
+    FailureReason, OpenPositioninMarketResult,
+};
+use crate::pricing_update_management::price_waiting_operation_trait::PriceWaitingOperation;
+use crate::pricing_update_management::price_waiting_operation_utils::put_price_waiting_operation;
+use crate::remove_collateral::remove_collateral_params::RemoveCollateralParams;
+use crate::stable_memory::MARKETS_LIST;
+use crate::user::balance_utils::update_user_balance;
+use crate::user::position_util::_put_user_position_detail;
+use crate::user::user_query::try_get_user_position_details;
+
+/// Removes collateral from an open position into the owner's balance.
+///
+/// The removed collateral is borrowed from the house as debt, so the position's size and pnl are unchanged
+/// while its leverage is increased. The position is checked at the current price against the market's
+/// max leverage and the liquidation threshold.
+///
+/// # Parameters
+///
+/// * `params` - [`RemoveCollateralParams`] containing:
+///   - `market_index` (u64): The unique identifier of the market containing the position
+///   - `owner` (Principal): The principal ID of the position owner
+///   - `position_id` (u64): The unique identifier of the position
+///   - `amount` (u128): The collateral to remove in quote asset (20-decimal precision)
+///   - `deadline` (Option<u64>): Optional deadline if the operation is queued
+///
+/// # Returns
+///
+/// Returns [`OpenPositioninMarketResult`] which can be:
+/// - `Settled { position_id, position }`: Collateral was removed, returns the updated position
+/// - `Waiting { id }`: Operation queued due to stale price data, will execute when price updates,
+///   `id` is the ticket for querying the operation status
+/// - `Failed { reason }`: The position does not exist or the removal would exceed the max leverage
+///   or make the position liquidatable, or the protocol is paused or the market is not active (`MarketNotActive`)
+#[update(name = "removeCollateral")]
+pub fn remove_collateral(params: RemoveCollateralParams) -> OpenPositioninMarketResult {
+    assert!(
+        msg_caller() == params.owner,
+        "Caller is not the owner of the position"
+    );
+
+    let result = _remove_collateral(&params);
+
+    if let OpenPositioninMarketResult::Waiting { id: _ } = result {
+        let ticket = put_price_waiting_operation(
+            params.market_index,
+            CLOSE_POSITION_PRIORITY_INDEX,
+            PriceWaitingOperation::from(params),
+        );
+
+        return OpenPositioninMarketResult::Waiting { id: Some(ticket) };
+    }
+
+    result
+}
+
+/// Internal implementation of the remove collateral functionality.
+///
+/// The function:
+/// 1. Retrieves the position and verifies it belongs to the specified market,
+///    and that the protocol is not paused and the market is active
+/// 2. Removes the collateral from the position in the market if the price is current, otherwise returns `Waiting`
+/// 3. Adds the removed collateral to the owner's balance and updates the position record on success
+pub fn _remove_collateral(params: &RemoveCollateralParams) -> OpenPositioninMarketResult {
+    let RemoveCollateralParams {
+        market_index,
+        owner,
+        position_id,
+        amount,
+        ..
+    } = *params;
+
+    let Some((position_market_index, position)) = try_get_user_position_details(owner, position_id)
+    else {
+        return OpenPositioninMarketResult::Failed {
+            reason: FailureReason::Other,
+        };
+    };
+
+    if position_market_index != market_index {
+        return OpenPositioninMarketResult::Failed {
+            reason: FailureReason::Other,
+        };
+    }
+
+    MARKETS_LIST.with_borrow_mut(|reference| {
+        let mut market = reference.get(market_index).expect("Market does not exist");
+
+        if is_paused() || !market.status.allows_opening() {
+            return OpenPositioninMarketResult::Failed {
+                reason: FailureReason::MarketNotActive,
+            };
+        }
+
+        let result = market.remove_collateral_from_position(position, amount);
+
+        if let OpenPositioninMarketResult::Settled { position, .. } = result {
+            update_user_balance(owner, amount, true);
+
+            _put_user_position_detail(owner, market_index, position_id, position);
+
+            reference.set(market_index, &market);
+
+            record_event(Events::RemoveCollateral {
+                owner,
+                market_index,
+                position_id,
+                amount,
+            });
+
+            return OpenPositioninMarketResult::Settled {
+                position_id: Some(position_id),
+                position,
+                position_fee: 0,
+            };
+        }
+
+        result
+    })
+}
@@ -0,0 +1,54 @@
+use candid::{CandidType, Principal};
+use serde::{Deserialize, Serialize};
+
+use crate::pricing_update_management::operation_status::OperationStatus;
+use crate::pricing_update_management::price_waiting_operation_trait::{
+    PriceWaitingOperation, PriceWaitingOperationTrait,
+};
+use crate::remove_collateral::remove_collateral::_remove_collateral;
+
+/// Parameters for removing collateral from an open position.
+#[derive(CandidType, Deserialize, Serialize, Copy, Clone)]
+pub struct RemoveCollateralParams {
+    /// The unique identifier of the market containing the position.
+    #[serde(rename = "marketIndex")]
+    pub market_index: u64,
+
+    /// The principal ID of the position owner.
+    /// **IMPORTANT**: This must match the message caller (`msg_caller()`) for security.
+    pub owner: Principal,
+
+    /// The unique identifier of the position.
+    #[serde(rename = "positionId")]
+    pub position_id: u64,
+
+    /// The collateral to remove in quote asset.
+    /// Uses 20-decimal precision.
+    pub amount: u128,
+
+    /// Optional deadline for the operation, in nanoseconds since the UNIX epoch.
+    /// If the operation is queued waiting for a price update and is not executed by this time,
+    /// it is failed.
+    /// Without a deadline, a queued operation fails if the next price update fails.
+    pub deadline: Option<u64>,
+}
+
+impl PriceWaitingOperationTrait for RemoveCollateralParams {
+    fn execute(&self) -> OperationStatus {
+        _remove_collateral(self).into()
+    }
+
+    fn deadline(&self) -> Option<u64> {
+        self.deadline
+    }
+
+    fn executor(&self) -> Option<Principal> {
+        Some(self.owner)
+    }
+}
+
+impl From<RemoveCollateralParams> for PriceWaitingOperation {
+    fn from(params: RemoveCollateralParams) -> Self {
+        PriceWaitingOperation::RemoveCollateral(params)
+    }
+}
@@ -1,2 +1,3 @@
+#[allow(clippy::module_inception)]
 pub mod remove_liquidity;
 pub mod remove_liquidity_params;
@@ -2,7 +2,8 @@ use ic_cdk::{api::msg_caller, update};
 
 use crate::{
     constants::REMOVE_LIQUIDITY_PRIORITY_INDEX,
-    house_settings::{get_execution_fee, update_execution_fees_accumulated},
+    events::{event_log::record_event, evnts_type::Events},
+    house_settings::{get_execution_fee, is_paused, update_execution_fees_accumulated},
     market::market_details::LiquidityOperationResult,
     pricing_update_management::{
         price_waiting_operation_trait::PriceWaitingOperation,
@@ -34,7 +35,8 @@ use crate::{
 ///
 /// Returns [`LiquidityOperationResult`] which can be:
 /// - `Settled { amount_out }`: Successfully removed liquidity, returns actual assets received
-/// - `Waiting`: Operation queued due to stale price data, will execute when price updates
+/// - `Waiting { id }`: Operation queued due to stale price data, will execute when price updates,
+///   `id` is the ticket for querying the operation status
 /// - `Failed`: Operation failed due to insufficient shares or invalid parameters
 ///
 /// # Security Notes
@@ -63,6 +65,7 @@ use crate::{
 ///     owner: msg_caller(),
 ///     amount_in: 1000000000000000000000, // 0.1 units of liquidity shares with 20 decimal places
 ///     min_amount_out: 950000000000000000000, // 0.095 units minimum assets with 5% slippage tolerance
+///     deadline: None, // fail if still waiting for a price update at this time
 /// };
 ///
 /// let result = remove_liquidity(params).await;
@@ -70,7 +73,7 @@ use crate::{
 ///     LiquidityOperationResult::Settled { amount_out } => {
 ///         // Successfully removed liquidity, received `amount_out` assets
 ///     },
-///     LiquidityOperationResult::Waiting => {
+///     LiquidityOperationResult::Waiting { id } => {
 ///         // Operation queued, will execute when price updates
 ///     },
 ///     LiquidityOperationResult::Failed => {
@@ -90,18 +93,16 @@ pub async fn remove_liquidity(params: RemoveLiquidityParams) -> LiquidityOperati
     let result = _remove_liquidity(&params);
 
     if let LiquidityOperationResult::Waiting { id: _ } = result {
-        let tx_id = put_price_waiting_operation(
+        let ticket = put_price_waiting_operation(
             params.market_index,
             REMOVE_LIQUIDITY_PRIORITY_INDEX,
             PriceWaitingOperation::from(params),
-        ) as u64;
+        );
 
-        return LiquidityOperationResult::Waiting {
-            id: Some((params.market_index, REMOVE_LIQUIDITY_PRIORITY_INDEX, tx_id)),
-        };
+        return LiquidityOperationResult::Waiting { id: Some(ticket) };
     }
 
-    return result;
+    result
 }
 
 /// Internal implementation of the remove liquidity functionality.
@@ -152,6 +153,10 @@ pub fn _remove_liquidity(params: &RemoveLiquidityParams) -> LiquidityOperationRe
     MARKETS_LIST.with_borrow_mut(|reference| {
         let mut market = reference.get(market_index).unwrap();
 
+        if is_paused() || !market.status.allows_closing() {
+            return LiquidityOperationResult::Failed("Market is paused".to_string());
+        }
+
         let result = market.remove_liquidity_from_market((*params).into());
 
         if let LiquidityOperationResult::Settled { amount_out } = result {
@@ -165,7 +170,15 @@ pub fn _remove_liquidity(params: &RemoveLiquidityParams) -> LiquidityOperationRe
 
             update_user_balance(owner, amount_out - execution_fee_gotten, true);
             reference.set(market_index, &market);
+
+            record_event(Events::RemovedLiquidity {
+                owner,
+                market_index,
+                shares: amount_in,
+                price: market.pricing_manager.price,
+                amount_out,
+            });
         }
-        return result;
+        result
     })
 }
@@ -1,19 +1,21 @@
 use candid::{CandidType, Principal};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{
     market::functions::remove_liquidity::RemoveLiquidityFromMarketParams,
-    pricing_update_management::price_waiting_operation_trait::{
-        PriceWaitingOperation, PriceWaitingOperationTrait,
+    pricing_update_management::{
+        operation_status::OperationStatus,
+        price_waiting_operation_trait::{PriceWaitingOperation, PriceWaitingOperationTrait},
     },
     remove_liquidity::remove_liquidity::_remove_liquidity,
+    user::balance_utils::update_user_market_liquidity_shares,
 };
 
 /// Parameters for removing liquidity from a market.
 ///
 /// This struct contains all the necessary information to remove liquidity shares from a
 /// specific market in the clearing house. The owner must be the message caller to ensure security.
-#[derive(CandidType, Deserialize, Copy, Clone)]
+#[derive(CandidType, Deserialize, Serialize, Copy, Clone)]
 pub struct RemoveLiquidityParams {
     /// The unique identifier of the target market to remove liquidity from.
     /// Must correspond to an existing market in the clearing house.
@@ -35,11 +37,34 @@ pub struct RemoveLiquidityParams {
     /// less than this amount, the transaction will fail.
     /// Should be calculated based on current market conditions and acceptable slippage.
     pub min_amount_out: u128,
+
+    /// Optional deadline for the operation, in nanoseconds since the UNIX epoch.
+    /// If the operation is queued waiting for a price update and is not executed by this time,
+    /// it is failed and its reserved amounts are released.
+    /// Without a deadline, a queued operation fails if the next price update fails.
+    pub deadline: Option<u64>,
 }
 
 impl PriceWaitingOperationTrait for RemoveLiquidityParams {
-    fn execute(&self) {
-        _remove_liquidity(self);
+    fn execute(&self) -> OperationStatus {
+        _remove_liquidity(self).into()
+    }
+
+    fn deadline(&self) -> Option<u64> {
+        self.deadline
+    }
+
+    fn executor(&self) -> Option<Principal> {
+        Some(self.owner)
+    }
+
+    /// reserves the liquidity shares
+    fn reserve(&self) {
+        update_user_market_liquidity_shares(self.owner, self.market_index, self.amount_in, false);
+    }
+
+    fn release(&self) {
+        update_user_market_liquidity_shares(self.owner, self.market_index, self.amount_in, true);
     }
 }
 
@@ -49,13 +74,13 @@ impl From<RemoveLiquidityParams> for PriceWaitingOperation {
     }
 }
 
-impl Into<RemoveLiquidityFromMarketParams> for RemoveLiquidityParams {
-    fn into(self) -> RemoveLiquidityFromMarketParams {
-        let Self {
+impl From<RemoveLiquidityParams> for RemoveLiquidityFromMarketParams {
+    fn from(val: RemoveLiquidityParams) -> Self {
+        let RemoveLiquidityParams {
             min_amount_out,
             amount_in,
             ..
-        } = self;
+        } = val;
         RemoveLiquidityFromMarketParams {
             min_amount_out,
             amount_in,
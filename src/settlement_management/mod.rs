@@ -0,0 +1,2 @@
+pub mod settlement_details;
+pub mod settlement_timer_utils;
@@ -0,0 +1,65 @@
+use std::borrow::Cow;
+
+use candid::CandidType;
+use ic_stable_structures::{Storable, storable::Bound};
+use serde::{Deserialize, Serialize};
+
+use crate::stable_memory::MARKETS_SETTLEMENT_DETAILS;
+
+/// Settlement Details
+///
+/// the recurring settlement of funding and borrowing fees of a market
+#[derive(CandidType, Deserialize, Serialize, Clone, Default)]
+pub struct SettlementDetails {
+    /// Interval between settlements in seconds, zero when the timer is disabled
+    #[serde(rename = "intervalInSecs")]
+    pub interval_in_secs: u64,
+    /// Last time a settlement was run
+    #[serde(rename = "lastRunTime")]
+    pub last_run_time: u64,
+    /// Number of settlements that failed
+    #[serde(rename = "failuresCount")]
+    pub failures_count: u64,
+    /// The most recent failure
+    #[serde(rename = "lastFailure")]
+    pub last_failure: Option<SettlementFailure>,
+}
+
+/// Settlement Failure
+#[derive(CandidType, Deserialize, Serialize, Clone)]
+pub struct SettlementFailure {
+    pub time: u64,
+    pub reason: String,
+}
+
+pub fn get_settlement_details(market_index: u64) -> Option<SettlementDetails> {
+    MARKETS_SETTLEMENT_DETAILS.with_borrow(|reference| reference.get(&market_index))
+}
+
+pub fn set_settlement_details(market_index: u64, details: SettlementDetails) {
+    MARKETS_SETTLEMENT_DETAILS.with_borrow_mut(|reference| {
+        reference.insert(market_index, details);
+    });
+}
+
+impl Storable for SettlementDetails {
+    fn to_bytes(&self) -> Cow<'_, [u8]> {
+        let serialized = bincode::serialize(self).expect("failed to serialize");
+        Cow::Owned(serialized)
+    }
+
+    /// Converts the element into an owned byte vector.
+    ///
+    /// This method consumes `self` and avoids cloning when possible.
+    fn into_bytes(self) -> Vec<u8> {
+        bincode::serialize(&self).expect("failed to serialize")
+    }
+
+    /// Converts bytes into an element.
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        bincode::deserialize(bytes.as_ref()).expect("failed to desearalize")
+    }
+
+    /// The size bounds of the type.
+    const BOUND: Bound = Bound::Unbounded;
+}
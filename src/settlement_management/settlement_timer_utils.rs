@@ -0,0 +1,160 @@
+use std::time::Duration;
+
+use candid::Principal;
+use ic_cdk::api::time;
+
+use crate::constants::DEFAULT_MARKET_SETTLEMENT_INTERVAL;
+use crate::events::event_log::record_event;
+use crate::events::evnts_type::Events;
+use crate::market::market_status::market_accepts;
+use crate::pricing_update_management::price_fetch::update_price;
+use crate::settlement_management::settlement_details::{
+    SettlementDetails, SettlementFailure, get_settlement_details, set_settlement_details,
+};
+use crate::stable_memory::{MARKET_ORDERS, MARKET_TIMER_MANAGER, MARKETS_LIST, POSITIONS_TRIGGERS};
+use crate::utils::_duration_in_seconds;
+
+/// Start Market Settlement Timer
+///
+/// sets the interval between settlements of a market and (re)starts its recurring timer,
+/// an interval of zero stops the timer
+pub fn start_market_settlement_timer(market_index: u64, interval_in_secs: u64) {
+    let details = get_settlement_details(market_index).unwrap_or_default();
+
+    set_settlement_details(
+        market_index,
+        SettlementDetails {
+            interval_in_secs,
+            ..details
+        },
+    );
+
+    _set_market_settlement_timer(market_index, interval_in_secs);
+}
+
+/// Rearm Market Settlement Timers
+///
+/// restarts the settlement timer of every market with its saved interval,
+/// markets without settlement details are started with the default interval
+/// @dev called after an upgrade since timers do not persist
+pub fn rearm_market_settlement_timers() {
+    let markets_count = MARKETS_LIST.with_borrow(|reference| reference.len());
+
+    for market_index in 0..markets_count {
+        match get_settlement_details(market_index) {
+            Some(SettlementDetails {
+                interval_in_secs, ..
+            }) => _set_market_settlement_timer(market_index, interval_in_secs),
+            None => start_market_settlement_timer(market_index, DEFAULT_MARKET_SETTLEMENT_INTERVAL),
+        }
+    }
+}
+
+/// Run Market Settlement
+///
+/// settles the funding payment and collects the borrowing fees of a market,
+/// the run time and any failure are recorded in the market's settlement details
+///
+/// the market's price is also refreshed if it is not current and the market has position triggers
+/// or limit orders, since those are only evaluated on price updates and would otherwise never be
+/// executed in a market without other activity
+pub fn run_market_settlement(market_index: u64) {
+    _run_market_settlement(market_index, time());
+
+    if _needs_price_refresh(market_index) {
+        ic_cdk::futures::spawn(async move {
+            update_price(market_index).await;
+        });
+    }
+}
+
+/// settles the market's fees up to the current time and records the run in its settlement details
+pub fn _run_market_settlement(market_index: u64, current_time: u64) {
+    let outcome = MARKETS_LIST.with_borrow_mut(|reference| {
+        let Some(mut market) = reference.get(market_index) else {
+            return Err("Market does not exist");
+        };
+
+        let duration_in_secs = |start_time| _duration_in_seconds(start_time, current_time);
+
+        if !market._accrue_funding_and_borrowing_fees_after_duration(duration_in_secs) {
+            return Err("Borrowing fees could not be collected");
+        }
+
+        reference.set(market_index, &market);
+
+        record_event(Events::SettleMarketFees {
+            market_index,
+            price: market.pricing_manager.price,
+        });
+
+        Ok(())
+    });
+
+    let mut details = get_settlement_details(market_index).unwrap_or_default();
+
+    details.last_run_time = current_time;
+
+    if let Err(reason) = outcome {
+        details.failures_count += 1;
+        details.last_failure = Some(SettlementFailure {
+            time: current_time,
+            reason: reason.to_string(),
+        });
+    }
+
+    set_settlement_details(market_index, details);
+}
+
+/// true if the market's price is not current, the market accepts closing positions
+/// and it has position triggers or limit orders waiting for a price update
+fn _needs_price_refresh(market_index: u64) -> bool {
+    if !market_accepts(market_index, false) {
+        return false;
+    }
+
+    let price_is_current = MARKETS_LIST.with_borrow(|reference| {
+        reference
+            .get(market_index)
+            .is_some_and(|market| market.pricing_manager.get_price().is_some())
+    });
+
+    if price_is_current {
+        return false;
+    }
+
+    let has_triggers = POSITIONS_TRIGGERS.with_borrow(|reference| {
+        reference
+            .range((market_index, Principal::management_canister(), 0)..)
+            .next()
+            .is_some_and(|entry| entry.key().0 == market_index)
+    });
+
+    let has_orders = MARKET_ORDERS.with_borrow(|reference| {
+        reference
+            .range((market_index, 0)..=(market_index, u64::MAX))
+            .next()
+            .is_some()
+    });
+
+    has_triggers || has_orders
+}
+
+fn _set_market_settlement_timer(market_index: u64, interval_in_secs: u64) {
+    let new_timer = (interval_in_secs != 0).then(|| {
+        ic_cdk_timers::set_timer_interval(Duration::from_secs(interval_in_secs), move || {
+            run_market_settlement(market_index);
+        })
+    });
+
+    MARKET_TIMER_MANAGER.with_borrow_mut(|reference| {
+        let previous_timer = match new_timer {
+            Some(timer_id) => reference.insert(market_index, timer_id),
+            None => reference.remove(&market_index),
+        };
+
+        if let Some(timer_id) = previous_timer {
+            ic_cdk_timers::clear_timer(timer_id);
+        }
+    });
+}
@@ -5,18 +5,38 @@ use candid::Principal;
 use ic_cdk_timers::TimerId;
 
 use crate::constants::{
-    _ADMIN_MEMORY_ID, _BALANCES_MEMORY_ID, _HOUSE_DETAILS_MEMORY_ID,
-    _MARKET_LIQUIDTY_SHARES_MEMORY_ID, _MARKET_SHARE_USER_BALANCES_MEMORY_ID, _MARKETS_MEMORY_ID,
-    _POSITIONS_MEMORY_ID,
+    _BALANCES_MEMORY_ID, _EVENTS_LOG_DATA_MEMORY_ID, _EVENTS_LOG_INDEX_MEMORY_ID,
+    _HOUSE_DETAILS_MEMORY_ID, _LEGACY_MARKETS_MEMORY_ID, _MARKET_LIQUIDTY_SHARES_MEMORY_ID,
+    _MARKET_ORDERS_MEMORY_ID, _MARKET_SHARE_USER_BALANCES_MEMORY_ID, _MARKETS_DELISTINGS_MEMORY_ID,
+    _MARKETS_MEMORY_ID, _MARKETS_SETTLEMENT_DETAILS_MEMORY_ID, _ORDERS_COUNTER_MEMORY_ID,
+    _ORDERS_MARKETS_MEMORY_ID, _ORDERS_STATUS_MEMORY_ID, _OWNER_MEMORY_ID,
+    _PENDING_OWNER_MEMORY_ID, _POSITIONS_MEMORY_ID, _POSITIONS_TRIGGERS_MEMORY_ID,
+    _PRICE_WAITING_OPERATIONS_COUNTER_MEMORY_ID, _PRICE_WAITING_OPERATIONS_KEYS_MEMORY_ID,
+    _PRICE_WAITING_OPERATIONS_MEMORY_ID, _PRICE_WAITING_OPERATIONS_STATUS_MEMORY_ID,
+    _ROLES_MEMORY_ID, _TIMELOCK_COUNTER_MEMORY_ID, _TIMELOCK_DELAY_MEMORY_ID,
+    _TIMELOCK_QUEUE_MEMORY_ID, _TREASURY_SWEEPS_MEMORY_ID, _TWAP_ORDERS_COUNTER_MEMORY_ID,
+    _TWAP_ORDERS_MEMORY_ID, _USERS_ORDERS_MEMORY_ID, DEFAULT_TIMELOCK_DELAY,
 };
 
+use crate::admin_roles::roles::GrantedRoles;
+use crate::delisting::delisting_details::DelistingDetails;
+use crate::events::evnts_type::EventRecord;
 use crate::house_settings::HouseDetails;
+use crate::market::legacy_market_details::LegacyMarketDetails;
 use crate::market::market_details::MarketDetails;
+use crate::order_management::order::Order;
+use crate::order_management::position_triggers::PositionTriggers;
+use crate::order_management::triggers_scan::TriggersScan;
+use crate::order_management::twap_order::TwapOrder;
 use crate::position::position_details::PositionDetails;
+use crate::pricing_update_management::operation_status::OperationStatus;
 use crate::pricing_update_management::price_waiting_operation_trait::PriceWaitingOperation;
+use crate::settlement_management::settlement_details::SettlementDetails;
+use crate::timelock::timelocked_change::QueuedChange;
+use crate::treasury::treasury_sweep::TreasurySweep;
 
 use ic_stable_structures::memory_manager::{MemoryManager, VirtualMemory};
-use ic_stable_structures::{DefaultMemoryImpl, StableBTreeMap, StableCell, StableVec};
+use ic_stable_structures::{DefaultMemoryImpl, StableBTreeMap, StableCell, StableLog, StableVec};
 
 type Memory = VirtualMemory<DefaultMemoryImpl>;
 
@@ -25,16 +45,32 @@ thread_local! {
 }
 
 thread_local! {
-    pub  static ADMIN:RefCell<StableCell<Principal,Memory>> = RefCell::new(StableCell::init(MEMORY_MANAGER.with_borrow(|tag|{
-        tag.get(_ADMIN_MEMORY_ID)
+    pub  static OWNER:RefCell<StableCell<Principal,Memory>> = RefCell::new(StableCell::init(MEMORY_MANAGER.with_borrow(|tag|{
+        tag.get(_OWNER_MEMORY_ID)
       }),Principal::anonymous()));
 
+    /// owner proposed with proposeOwner, until it accepts the ownership
+
+    pub static PENDING_OWNER:RefCell<StableCell<Option<Principal>,Memory>> = MEMORY_MANAGER.with_borrow(|tag|{
+      RefCell::new(StableCell::init(tag.get(_PENDING_OWNER_MEMORY_ID), None))});
+
+    /// Principal and the roles granted to it by the owner
+
+    pub static ROLES:RefCell<StableBTreeMap<Principal,GrantedRoles,Memory>> = MEMORY_MANAGER.with_borrow(|tag|{
+      RefCell::new(StableBTreeMap::init(tag.get(_ROLES_MEMORY_ID)))});
+
     pub static HOUSE_SETTINGS:RefCell<StableCell<HouseDetails,Memory>> = MEMORY_MANAGER.with_borrow(|tag|{
       RefCell::new(StableCell::init(tag.get(_HOUSE_DETAILS_MEMORY_ID), HouseDetails::default()))});
 
     pub static MARKETS_LIST:RefCell<StableVec<MarketDetails,Memory>> = MEMORY_MANAGER.with_borrow(|tag|{
       RefCell::new(StableVec::init(tag.get(_MARKETS_MEMORY_ID)))});
 
+    /// markets stored with the layout before the market status, fee factors and impact pool were added,
+    /// emptied into MARKETS_LIST in post_upgrade
+
+    pub static LEGACY_MARKETS_LIST:RefCell<StableVec<LegacyMarketDetails,Memory>> = MEMORY_MANAGER.with_borrow(|tag|{
+      RefCell::new(StableVec::init(tag.get(_LEGACY_MARKETS_MEMORY_ID)))});
+
     pub static USERS_BALANCES:RefCell<StableBTreeMap<Principal,u128,Memory>> =MEMORY_MANAGER.with_borrow(|tag|{
        RefCell::new(StableBTreeMap::init(tag.get(_BALANCES_MEMORY_ID)))});
 
@@ -47,13 +83,113 @@ thread_local! {
 
     /// User amd TimeStamp
 
+    #[allow(clippy::type_complexity)]
     pub static USERS_POSITIONS:RefCell<StableBTreeMap<(Principal,u64),(u64,PositionDetails),Memory>> = MEMORY_MANAGER.with_borrow(|tag|{
       RefCell::new(StableBTreeMap::init(tag.get(_POSITIONS_MEMORY_ID)))});
 
 
-    pub  static MARKET_PRICE_WAITING_OPERATION:RefCell<HashMap<u64,(TimerId,HashMap<u8,Vec< PriceWaitingOperation> >)>> = RefCell::new(HashMap::new());
+    /// Market Index, Priority Index and Operation ID
+
+    pub static MARKET_PRICE_WAITING_OPERATION:RefCell<StableBTreeMap<(u64,u8,u64),PriceWaitingOperation,Memory>> = MEMORY_MANAGER.with_borrow(|tag|{
+      RefCell::new(StableBTreeMap::init(tag.get(_PRICE_WAITING_OPERATIONS_MEMORY_ID)))});
+
+    pub static PRICE_WAITING_OPERATIONS_COUNTER:RefCell<StableCell<u64,Memory>> = MEMORY_MANAGER.with_borrow(|tag|{
+      RefCell::new(StableCell::init(tag.get(_PRICE_WAITING_OPERATIONS_COUNTER_MEMORY_ID), 0))});
+
+    /// Operation ID and its Market Index and Priority Index (index of the queued operations by operation id)
+
+    pub static PRICE_WAITING_OPERATIONS_KEYS:RefCell<StableBTreeMap<u64,(u64,u8),Memory>> = MEMORY_MANAGER.with_borrow(|tag|{
+      RefCell::new(StableBTreeMap::init(tag.get(_PRICE_WAITING_OPERATIONS_KEYS_MEMORY_ID)))});
+
+    /// Operation ID (ticket) and status
+
+    pub static PRICE_WAITING_OPERATIONS_STATUS:RefCell<StableBTreeMap<u64,OperationStatus,Memory>> = MEMORY_MANAGER.with_borrow(|tag|{
+      RefCell::new(StableBTreeMap::init(tag.get(_PRICE_WAITING_OPERATIONS_STATUS_MEMORY_ID)))});
+
+    /// timers are not persisted across upgrades and are re-armed in post_upgrade
+    pub static MARKET_PRICE_WAITING_OPERATION_TIMERS:RefCell<HashMap<u64,TimerId>> = RefCell::new(HashMap::new());
+
+    /// Market Index and settlement details
+
+    pub static MARKETS_SETTLEMENT_DETAILS:RefCell<StableBTreeMap<u64,SettlementDetails,Memory>> = MEMORY_MANAGER.with_borrow(|tag|{
+      RefCell::new(StableBTreeMap::init(tag.get(_MARKETS_SETTLEMENT_DETAILS_MEMORY_ID)))});
+
+    /// Market Index and Order ID
+
+    pub static MARKET_ORDERS:RefCell<StableBTreeMap<(u64,u64),Order,Memory>> = MEMORY_MANAGER.with_borrow(|tag|{
+      RefCell::new(StableBTreeMap::init(tag.get(_MARKET_ORDERS_MEMORY_ID)))});
+
+    pub static ORDERS_COUNTER:RefCell<StableCell<u64,Memory>> = MEMORY_MANAGER.with_borrow(|tag|{
+      RefCell::new(StableCell::init(tag.get(_ORDERS_COUNTER_MEMORY_ID), 0))});
+
+    /// Order ID and its Market Index (index of the order books by order id)
+
+    pub static ORDERS_MARKETS:RefCell<StableBTreeMap<u64,u64,Memory>> = MEMORY_MANAGER.with_borrow(|tag|{
+      RefCell::new(StableBTreeMap::init(tag.get(_ORDERS_MARKETS_MEMORY_ID)))});
+
+    /// User, Order ID and its Market Index (index of the order books by owner)
+
+    pub static USERS_ORDERS:RefCell<StableBTreeMap<(Principal,u64),u64,Memory>> = MEMORY_MANAGER.with_borrow(|tag|{
+      RefCell::new(StableBTreeMap::init(tag.get(_USERS_ORDERS_MEMORY_ID)))});
+
+    /// Order ID and status
+
+    pub static ORDERS_STATUS:RefCell<StableBTreeMap<u64,OperationStatus,Memory>> = MEMORY_MANAGER.with_borrow(|tag|{
+      RefCell::new(StableBTreeMap::init(tag.get(_ORDERS_STATUS_MEMORY_ID)))});
+
+    /// Market Index, User and Position ID
+
+    pub static POSITIONS_TRIGGERS:RefCell<StableBTreeMap<(u64,Principal,u64),PositionTriggers,Memory>> = MEMORY_MANAGER.with_borrow(|tag|{
+      RefCell::new(StableBTreeMap::init(tag.get(_POSITIONS_TRIGGERS_MEMORY_ID)))});
+
+    /// TWAP Order ID
+
+    pub static TWAP_ORDERS:RefCell<StableBTreeMap<u64,TwapOrder,Memory>> = MEMORY_MANAGER.with_borrow(|tag|{
+      RefCell::new(StableBTreeMap::init(tag.get(_TWAP_ORDERS_MEMORY_ID)))});
+
+    pub static TWAP_ORDERS_COUNTER:RefCell<StableCell<u64,Memory>> = MEMORY_MANAGER.with_borrow(|tag|{
+      RefCell::new(StableCell::init(tag.get(_TWAP_ORDERS_COUNTER_MEMORY_ID), 0))});
+
+    /// timers of the next child execution of active TWAP orders, re-armed in post_upgrade
+    pub static TWAP_ORDERS_TIMERS:RefCell<HashMap<u64,TimerId>> = RefCell::new(HashMap::new());
+
+    /// Sweep ID and sweep of the accumulated fees to the treasury account
+
+    pub static TREASURY_SWEEPS:RefCell<StableBTreeMap<u64,TreasurySweep,Memory>> = MEMORY_MANAGER.with_borrow(|tag|{
+      RefCell::new(StableBTreeMap::init(tag.get(_TREASURY_SWEEPS_MEMORY_ID)))});
+
+    /// Change ID and risk parameter change waiting for its timelock
+
+    pub static TIMELOCK_QUEUE:RefCell<StableBTreeMap<u64,QueuedChange,Memory>> = MEMORY_MANAGER.with_borrow(|tag|{
+      RefCell::new(StableBTreeMap::init(tag.get(_TIMELOCK_QUEUE_MEMORY_ID)))});
+
+    pub static TIMELOCK_COUNTER:RefCell<StableCell<u64,Memory>> = MEMORY_MANAGER.with_borrow(|tag|{
+      RefCell::new(StableCell::init(tag.get(_TIMELOCK_COUNTER_MEMORY_ID), 0))});
+
+    /// delay in seconds between queueing a risk parameter change and its earliest execution
+
+    pub static TIMELOCK_DELAY:RefCell<StableCell<u64,Memory>> = MEMORY_MANAGER.with_borrow(|tag|{
+      RefCell::new(StableCell::init(tag.get(_TIMELOCK_DELAY_MEMORY_ID), DEFAULT_TIMELOCK_DELAY))});
+
+    /// Market index and delisting of the market
+
+    pub static MARKETS_DELISTINGS:RefCell<StableBTreeMap<u64,DelistingDetails,Memory>> = MEMORY_MANAGER.with_borrow(|tag|{
+      RefCell::new(StableBTreeMap::init(tag.get(_MARKETS_DELISTINGS_MEMORY_ID)))});
+
+    /// append-only log of every state-changing action, the index of an event is its sequence number
+
+    pub static EVENTS_LOG:RefCell<StableLog<EventRecord,Memory,Memory>> = MEMORY_MANAGER.with_borrow(|tag|{
+      RefCell::new(StableLog::init(tag.get(_EVENTS_LOG_INDEX_MEMORY_ID), tag.get(_EVENTS_LOG_DATA_MEMORY_ID)))});
+
+    /// final settlement timers of markets being delisted, re-armed in post_upgrade
+    pub static MARKET_DELISTING_TIMERS:RefCell<HashMap<u64,TimerId>> = RefCell::new(HashMap::new());
+
+    /// Market Index and the scan of its order book started by a price update, run in batches
+
+    pub static MARKET_TRIGGERS_SCANS:RefCell<HashMap<u64,TriggersScan>> = RefCell::new(HashMap::new());
 
-    pub static MARKET_TIMER_MANAGER:RefCell<HashMap<u64,u64>> = RefCell::new(HashMap::new());
+    /// recurring settlement timers of markets, re-armed in post_upgrade
+    pub static MARKET_TIMER_MANAGER:RefCell<HashMap<u64,TimerId>> = RefCell::new(HashMap::new());
 
 
 }
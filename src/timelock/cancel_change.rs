@@ -0,0 +1,24 @@
+use ic_cdk::{api::msg_caller, update};
+
+use crate::timelock::timelock_utils::_cancel_queued_change;
+
+/// Cancels a queued risk parameter change before it is executed.
+///
+/// # Parameters
+///
+/// * `change_id` (u64): The change id returned when the change was queued
+///
+/// # Returns
+///
+/// Returns `bool` indicating success:
+/// - `true`: Change was removed from the queue
+/// - `false`: No change is queued with the change id (it was already executed or cancelled)
+///
+/// # Panics
+///
+/// - The caller does not hold the role the change was queued with
+///   (risk manager for risk parameters and owner for the timelock delay)
+#[update(name = "cancelTimelockedChange")]
+pub fn cancel_timelocked_change(change_id: u64) -> bool {
+    _cancel_queued_change(change_id, msg_caller())
+}
@@ -0,0 +1,26 @@
+use ic_cdk::{
+    api::{msg_caller, time},
+    update,
+};
+
+use crate::timelock::timelock_utils::_execute_queued_change;
+
+/// Executes a queued risk parameter change once its timelock delay has passed.
+///
+/// # Parameters
+///
+/// * `change_id` (u64): The change id returned when the change was queued
+///
+/// # Panics
+///
+/// - The change does not exist (it was already executed or cancelled)
+/// - The caller does not hold the role the change was queued with
+///   (risk manager for risk parameters and owner for the timelock delay)
+/// - The earliest execution time of the change has not been reached
+/// - The change was not executed within the grace period after its earliest execution time,
+///   it has to be cancelled and queued again
+/// - The change is no longer valid (e.g the config does not pass the market config bounds)
+#[update(name = "executeTimelockedChange")]
+pub fn execute_timelocked_change(change_id: u64) {
+    _execute_queued_change(change_id, msg_caller(), time());
+}
@@ -0,0 +1,4 @@
+pub mod cancel_change;
+pub mod execute_change;
+pub mod timelock_utils;
+pub mod timelocked_change;
@@ -0,0 +1,125 @@
+use candid::Principal;
+use ic_cdk::api::time;
+
+use crate::admin_roles::roles::has_role;
+use crate::constants::{_ONE_SECOND, TIMELOCK_GRACE_PERIOD};
+use crate::events::event_log::record_event;
+use crate::events::evnts_type::Events;
+use crate::stable_memory::{TIMELOCK_COUNTER, TIMELOCK_DELAY, TIMELOCK_QUEUE};
+use crate::timelock::timelocked_change::{QueuedChange, TimelockedChange};
+
+pub fn get_timelock_delay() -> u64 {
+    TIMELOCK_DELAY.with_borrow(|reference| *reference.get())
+}
+
+/// Queue Change
+///
+/// validates a change and queues it to be executed after the timelock delay
+/// with the role required to execute or cancel it
+///
+/// Returns the change id
+pub fn queue_change(change: TimelockedChange, proposer: Principal) -> u64 {
+    _queue_change(change, proposer, time())
+}
+
+pub fn _queue_change(change: TimelockedChange, proposer: Principal, queued_at: u64) -> u64 {
+    if let Err(reason) = change.validate() {
+        panic!("{}", reason);
+    }
+
+    let change_id = TIMELOCK_COUNTER.with_borrow_mut(|reference| {
+        let change_id = *reference.get();
+        reference.set(change_id + 1);
+        change_id
+    });
+
+    let eta = queued_at + get_timelock_delay() * _ONE_SECOND;
+
+    TIMELOCK_QUEUE.with_borrow_mut(|reference| {
+        reference.insert(
+            change_id,
+            QueuedChange {
+                change: change.clone(),
+                proposer,
+                role: change.required_role(),
+                queued_at,
+                eta,
+            },
+        );
+    });
+
+    record_event(Events::QueueChange {
+        change_id,
+        change,
+        eta,
+    });
+
+    change_id
+}
+
+pub fn get_queued_change(change_id: u64) -> Option<QueuedChange> {
+    TIMELOCK_QUEUE.with_borrow(|reference| reference.get(&change_id))
+}
+
+pub fn remove_queued_change(change_id: u64) -> Option<QueuedChange> {
+    TIMELOCK_QUEUE.with_borrow_mut(|reference| reference.remove(&change_id))
+}
+
+/// Execute Queued Change
+///
+/// executes a queued change between its earliest execution time and the end of the grace period after it
+/// @dev panics if the caller does not hold the role the change was queued with,
+/// the time is outside the execution window or the change is no longer valid
+pub fn _execute_queued_change(change_id: u64, caller: Principal, current_time: u64) {
+    let Some(queued_change) = get_queued_change(change_id) else {
+        panic!("Change does not exist");
+    };
+
+    assert!(
+        has_role(caller, queued_change.role),
+        "Caller does not hold the role of the change"
+    );
+
+    assert!(
+        current_time >= queued_change.eta,
+        "Timelock has not expired"
+    );
+    assert!(
+        current_time <= queued_change.eta + TIMELOCK_GRACE_PERIOD * _ONE_SECOND,
+        "Change is stale"
+    );
+
+    if let Err(reason) = queued_change.change.validate() {
+        panic!("{}", reason);
+    }
+
+    remove_queued_change(change_id);
+
+    queued_change.change.execute(current_time);
+
+    record_event(Events::ExecuteChange {
+        change_id,
+        change: queued_change.change,
+    });
+}
+
+/// Cancel Queued Change
+///
+/// Returns false if no change is queued with the change id
+/// @dev panics if the caller does not hold the role the change was queued with
+pub fn _cancel_queued_change(change_id: u64, caller: Principal) -> bool {
+    let Some(queued_change) = get_queued_change(change_id) else {
+        return false;
+    };
+
+    assert!(
+        has_role(caller, queued_change.role),
+        "Caller does not hold the role of the change"
+    );
+
+    remove_queued_change(change_id);
+
+    record_event(Events::CancelChange { change_id });
+
+    true
+}
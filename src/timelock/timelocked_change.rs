@@ -0,0 +1,138 @@
+use std::borrow::Cow;
+
+use candid::{CandidType, Principal};
+use ic_stable_structures::{Storable, storable::Bound};
+use serde::{Deserialize, Serialize};
+
+use crate::admin_roles::roles::Role;
+use crate::constants::{MAX_TIMELOCK_DELAY, MIN_TIMELOCK_DELAY};
+use crate::house_settings::set_execution_fee;
+use crate::market::market_config::MarketConfig;
+use crate::stable_memory::{MARKETS_LIST, TIMELOCK_DELAY};
+use crate::utils::_duration_in_seconds;
+
+/// Timelocked Change
+///
+/// a change of risk parameters that is only executed after the timelock delay
+#[derive(CandidType, Deserialize, Serialize, Clone)]
+pub enum TimelockedChange {
+    /// Sets every tunable parameter of a market (market state, funding, liquidity and pricing factors)
+    UpdateMarketConfig {
+        #[serde(rename = "marketIndex")]
+        market_index: u64,
+        config: Box<MarketConfig>,
+    },
+    /// Sets the execution fee charged on every operation
+    SetExecutionFee(u128),
+    /// Sets the timelock delay in seconds
+    SetTimelockDelay(u64),
+}
+
+/// Queued Change
+///
+/// a timelocked change waiting for its earliest execution time
+#[derive(CandidType, Deserialize, Serialize, Clone)]
+pub struct QueuedChange {
+    pub change: TimelockedChange,
+    pub proposer: Principal,
+    /// Role required to execute or cancel the change, the role required to queue it
+    pub role: Role,
+    #[serde(rename = "queuedAt")]
+    pub queued_at: u64,
+    /// Earliest execution time in nanoseconds
+    pub eta: u64,
+}
+
+impl TimelockedChange {
+    /// Required Role
+    ///
+    /// the role required to queue the change, stored with the queued change
+    /// and required again to execute or cancel it
+    pub fn required_role(&self) -> Role {
+        match self {
+            TimelockedChange::UpdateMarketConfig { .. } | TimelockedChange::SetExecutionFee(_) => {
+                Role::RiskManager
+            }
+            TimelockedChange::SetTimelockDelay(_) => Role::Owner,
+        }
+    }
+
+    /// Validate
+    ///
+    /// checks the change can be executed, when it is queued and again when it is executed
+    pub fn validate(&self) -> Result<(), String> {
+        match self {
+            TimelockedChange::UpdateMarketConfig {
+                market_index,
+                config,
+            } => {
+                let market_exists =
+                    MARKETS_LIST.with_borrow(|reference| *market_index < reference.len());
+
+                if !market_exists {
+                    return Err("Market does not exist".to_string());
+                }
+
+                config.validate()
+            }
+            TimelockedChange::SetExecutionFee(_) => Ok(()),
+            TimelockedChange::SetTimelockDelay(delay_in_secs) => {
+                if (MIN_TIMELOCK_DELAY..=MAX_TIMELOCK_DELAY).contains(delay_in_secs) {
+                    Ok(())
+                } else {
+                    Err("Timelock delay out of bounds".to_string())
+                }
+            }
+        }
+    }
+
+    pub fn execute(&self, current_time: u64) {
+        match self {
+            TimelockedChange::UpdateMarketConfig {
+                market_index,
+                config,
+            } => {
+                MARKETS_LIST.with_borrow_mut(|reference| {
+                    let mut market = reference.get(*market_index).expect("Market does not exist");
+
+                    // fees are accrued with the current parameters so the new parameters only apply from now
+                    market._accrue_funding_and_borrowing_fees_after_duration(|start_time| {
+                        _duration_in_seconds(start_time, current_time)
+                    });
+
+                    market.apply_config(**config);
+
+                    reference.set(*market_index, &market);
+                });
+            }
+            TimelockedChange::SetExecutionFee(execution_fee) => set_execution_fee(*execution_fee),
+            TimelockedChange::SetTimelockDelay(delay_in_secs) => {
+                TIMELOCK_DELAY.with_borrow_mut(|reference| {
+                    reference.set(*delay_in_secs);
+                });
+            }
+        }
+    }
+}
+
+impl Storable for QueuedChange {
+    fn to_bytes(&self) -> Cow<'_, [u8]> {
+        let serialized = bincode::serialize(self).expect("failed to serialize");
+        Cow::Owned(serialized)
+    }
+
+    /// Converts the element into an owned byte vector.
+    ///
+    /// This method consumes `self` and avoids cloning when possible.
+    fn into_bytes(self) -> Vec<u8> {
+        bincode::serialize(&self).expect("failed to serialize")
+    }
+
+    /// Converts bytes into an element.
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        bincode::deserialize(bytes.as_ref()).expect("failed to desearalize")
+    }
+
+    /// The size bounds of the type.
+    const BOUND: Bound = Bound::Unbounded;
+}
@@ -0,0 +1,3 @@
+pub mod set_treasury_account;
+pub mod sweep_treasury_fees;
+pub mod treasury_sweep;
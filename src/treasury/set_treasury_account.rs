@@ -0,0 +1,21 @@
+use ic_cdk::update;
+use icrc_ledger_types::icrc1::account::Account;
+
+use crate::admin_roles::owner_guard;
+use crate::events::event_log::record_event;
+use crate::events::evnts_type::Events;
+use crate::house_settings;
+
+/// Sets the account the accumulated execution and position fees are swept to.
+///
+/// # Parameters
+///
+/// * `treasury_account` (Account): The ICRC account of the treasury on the house asset ledger
+#[update(name = "setTreasuryAccount", guard = "owner_guard")]
+pub fn set_treasury_account(treasury_account: Account) {
+    house_settings::set_treasury_account(treasury_account);
+
+    record_event(Events::SetTreasuryAccount {
+        account: treasury_account,
+    });
+}
@@ -0,0 +1,88 @@
+use ic_cdk::{api::msg_caller, api::time, update};
+
+use crate::admin_roles::treasurer_guard;
+use crate::asset_management::asset_management::AssetLedger;
+use crate::events::event_log::record_event;
+use crate::events::evnts_type::Events;
+use crate::house_settings::{
+    get_execution_fees_accumulated, get_house_asset_ledger, get_position_fees_acccumulated,
+    get_treasury_account, update_execution_fees_accumulated, update_position_fees_acccumulated,
+};
+use crate::treasury::treasury_sweep::{TreasurySweep, put_treasury_sweep};
+
+/// Sweeps the accumulated execution and position fees to the treasury account.
+///
+/// The fees are sent through the house asset ledger and the accumulated fees are reduced
+/// before the transfer, so fees collected while the transfer is in flight are kept for the next sweep.
+/// Only the fees in whole ledger units are swept, the rest stays accumulated for the next sweep.
+///
+/// # Returns
+///
+/// Returns `bool` indicating success:
+/// - `true`: Fees were sent to the treasury account and the sweep was recorded in the history
+/// - `false`: Transfer failed (e.g the fees do not cover the ledger fee), the accumulated fees are restored
+///
+/// # Security Notes
+///
+/// - **Treasurer Only**: Only the treasurer can sweep the fees
+/// - **Treasury Account**: The treasury account must be set with `setTreasuryAccount`
+#[update(name = "sweepTreasuryFees", guard = "treasurer_guard")]
+pub async fn sweep_treasury_fees() -> bool {
+    let Some(account) = get_treasury_account() else {
+        panic!("Treasury account is not set");
+    };
+
+    let house_asset_ledger = get_house_asset_ledger();
+
+    let (execution_fees, position_fees) = _sweepable_fees(
+        &house_asset_ledger,
+        get_execution_fees_accumulated(),
+        get_position_fees_acccumulated(),
+    );
+
+    update_execution_fees_accumulated(execution_fees, false);
+    update_position_fees_acccumulated(position_fees, false);
+
+    let tx_result = house_asset_ledger
+        ._send_out_to_account(execution_fees + position_fees, account)
+        .await;
+
+    if tx_result {
+        let sweep_id = put_treasury_sweep(TreasurySweep {
+            caller: msg_caller(),
+            account,
+            execution_fees,
+            position_fees,
+            time: time(),
+        });
+
+        record_event(Events::SweepTreasuryFees {
+            sweep_id,
+            account,
+            execution_fees,
+            position_fees,
+        });
+    } else {
+        //refund
+        update_execution_fees_accumulated(execution_fees, true);
+        update_position_fees_acccumulated(position_fees, true);
+    }
+
+    tx_result
+}
+
+/// Sweepable Fees
+///
+/// the execution and position fees rounded down to whole ledger units of the house asset,
+/// the amounts sent by the ledger
+pub fn _sweepable_fees(
+    house_asset_ledger: &AssetLedger,
+    execution_fees: u128,
+    position_fees: u128,
+) -> (u128, u128) {
+    let round_down = |amount: u128| {
+        house_asset_ledger.from_ledger_amount(house_asset_ledger.to_ledger_amount(amount))
+    };
+
+    (round_down(execution_fees), round_down(position_fees))
+}
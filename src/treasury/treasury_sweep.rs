@@ -0,0 +1,61 @@
+use std::borrow::Cow;
+
+use candid::{CandidType, Principal};
+use ic_stable_structures::{Storable, storable::Bound};
+use icrc_ledger_types::icrc1::account::Account;
+use serde::{Deserialize, Serialize};
+
+use crate::stable_memory::TREASURY_SWEEPS;
+
+/// Treasury Sweep
+///
+/// a transfer of the accumulated execution and position fees to the treasury account
+#[derive(CandidType, Deserialize, Serialize, Clone)]
+pub struct TreasurySweep {
+    /// Principal that swept the fees
+    pub caller: Principal,
+    /// Account the fees were sent to
+    pub account: Account,
+    /// Execution fees swept (20-decimal precision)
+    #[serde(rename = "executionFees")]
+    pub execution_fees: u128,
+    /// Treasury's share of position fees swept (20-decimal precision)
+    #[serde(rename = "positionFees")]
+    pub position_fees: u128,
+    pub time: u64,
+}
+
+/// Put Treasury Sweep
+///
+/// appends a sweep to the treasury sweeps history
+///
+/// Returns the sweep id
+pub fn put_treasury_sweep(sweep: TreasurySweep) -> u64 {
+    TREASURY_SWEEPS.with_borrow_mut(|reference| {
+        let sweep_id = reference.len();
+        reference.insert(sweep_id, sweep);
+        sweep_id
+    })
+}
+
+impl Storable for TreasurySweep {
+    fn to_bytes(&self) -> Cow<'_, [u8]> {
+        let serialized = bincode::serialize(self).expect("failed to serialize");
+        Cow::Owned(serialized)
+    }
+
+    /// Converts the element into an owned byte vector.
+    ///
+    /// This method consumes `self` and avoids cloning when possible.
+    fn into_bytes(self) -> Vec<u8> {
+        bincode::serialize(&self).expect("failed to serialize")
+    }
+
+    /// Converts bytes into an element.
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        bincode::deserialize(bytes.as_ref()).expect("failed to desearalize")
+    }
+
+    /// The size bounds of the type.
+    const BOUND: Bound = Bound::Unbounded;
+}
@@ -1 +1,26 @@
+pub mod test_asset_ledger;
+pub mod test_cancel_pending_operation;
+pub mod test_close_position;
+pub mod test_collect_borrow_fees;
+pub mod test_delisting;
+pub mod test_event_log;
+pub mod test_funding;
+pub mod test_increase_position;
+pub mod test_legacy_market_migration;
+pub mod test_liquidate_position;
+pub mod test_market_settlement;
 pub mod test_market_state_config;
+pub mod test_market_status;
+pub mod test_operation_status;
+pub mod test_order_book;
+pub mod test_position_fees;
+pub mod test_position_triggers;
+pub mod test_price_impact;
+pub mod test_price_waiting_operation;
+pub mod test_price_waiting_queue;
+pub mod test_roles;
+pub mod test_timelock;
+pub mod test_treasury_sweep;
+pub mod test_twap_order;
+pub mod test_update_position_collateral;
+pub mod utils;
@@ -0,0 +1,87 @@
+use candid::Principal;
+
+use crate::asset_management::asset_management::{AssetLedger, AssetLedgerType};
+use crate::asset_management::functions::user_deposit_subaccount;
+use crate::math::math::FLOAT_PRECISION;
+
+/// legacy ICP ledger, 8 decimals and a fee of 10_000
+fn icp_ledger() -> AssetLedger {
+    AssetLedger {
+        ledger_type: AssetLedgerType::ICP,
+        asset_decimals: 8,
+        ledger_fee: 10_000,
+        ..Default::default()
+    }
+}
+
+/// ICRC-2 ledger of a token with 18 decimals and no fee
+fn icrc_ledger() -> AssetLedger {
+    AssetLedger {
+        ledger_type: AssetLedgerType::ICRC,
+        asset_decimals: 18,
+        ledger_fee: 0,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn amounts_are_converted_with_the_ledger_decimals() {
+    assert_eq!(icp_ledger().to_ledger_amount(FLOAT_PRECISION), 100_000_000);
+    assert_eq!(
+        icp_ledger().from_ledger_amount(100_000_000),
+        FLOAT_PRECISION
+    );
+
+    assert_eq!(
+        icrc_ledger().to_ledger_amount(3 * FLOAT_PRECISION),
+        3 * 10u128.pow(18)
+    );
+    assert_eq!(
+        icrc_ledger().from_ledger_amount(3 * 10u128.pow(18)),
+        3 * FLOAT_PRECISION
+    );
+}
+
+#[test]
+fn fractions_of_a_ledger_unit_are_rounded_down() {
+    let ledger_unit = FLOAT_PRECISION / 100_000_000;
+
+    assert_eq!(icp_ledger().to_ledger_amount(ledger_unit - 1), 0);
+    assert_eq!(icp_ledger().to_ledger_amount(3 * ledger_unit / 2), 1);
+}
+
+#[test]
+fn ledger_fee_is_taken_out_of_the_amount_sent() {
+    assert_eq!(
+        icp_ledger().ledger_amount_after_fee(FLOAT_PRECISION),
+        Some(100_000_000 - 10_000)
+    );
+    assert_eq!(
+        icrc_ledger().ledger_amount_after_fee(FLOAT_PRECISION),
+        Some(10u128.pow(18))
+    );
+}
+
+#[test]
+fn amount_not_covering_the_ledger_fee_is_not_sent() {
+    let fee = icp_ledger().from_ledger_amount(10_000);
+
+    assert_eq!(icp_ledger().ledger_amount_after_fee(fee), None);
+    assert_eq!(icp_ledger().ledger_amount_after_fee(fee / 2), None);
+    assert_eq!(icrc_ledger().ledger_amount_after_fee(0), None);
+}
+
+#[test]
+fn every_user_has_its_own_deposit_subaccount() {
+    let user = Principal::from_slice(&[1]);
+    let other_user = Principal::from_slice(&[2]);
+
+    assert_eq!(
+        user_deposit_subaccount(&user),
+        user_deposit_subaccount(&user)
+    );
+    assert_ne!(
+        user_deposit_subaccount(&user),
+        user_deposit_subaccount(&other_user)
+    );
+}
@@ -0,0 +1,98 @@
+use candid::Principal;
+
+use crate::admin_roles::collect_borrowing_fees::CollectBorrowFeesParams;
+use crate::cancel_pending_operation::cancel_pending_operation::_cancel_pending_operation;
+use crate::constants::{
+    COLLECT_BORROW_FEES_PRIORITY_INDEX, OPEN_POSITION_PRIORITY_INDEX,
+    REMOVE_LIQUIDITY_PRIORITY_INDEX,
+};
+use crate::math::math::FLOAT_PRECISION;
+use crate::open_position::open_position_params::OpenPositionParams;
+use crate::pricing_update_management::operation_status::{OperationStatus, get_operation_status};
+use crate::pricing_update_management::price_waiting_operation_trait::PriceWaitingOperation;
+use crate::pricing_update_management::price_waiting_operation_utils::{
+    _queue_price_waiting_operation, get_price_waiting_operation,
+};
+use crate::remove_liquidity::remove_liquidity_params::RemoveLiquidityParams;
+use crate::unit_tests::utils::open_position_params;
+use crate::user::balance_utils::{
+    get_user_balance, get_user_market_liquidity_shares, set_user_market_liquidity_shares,
+    update_user_balance,
+};
+
+fn user(id: u8) -> Principal {
+    Principal::from_slice(&[id])
+}
+
+/// open of 400 collateral by user(1) queued in market 0 with 1_000 of balance
+fn queue_open_position() -> u64 {
+    update_user_balance(user(1), 1_000 * FLOAT_PRECISION, true);
+
+    let operation = PriceWaitingOperation::OpenPosition(OpenPositionParams {
+        owner: user(1),
+        ..open_position_params(true, 400 * FLOAT_PRECISION, FLOAT_PRECISION)
+    });
+
+    _queue_price_waiting_operation(0, OPEN_POSITION_PRIORITY_INDEX, operation)
+}
+
+#[test]
+fn cancelled_operation_is_removed_and_its_collateral_released() {
+    let ticket = queue_open_position();
+    assert_eq!(get_user_balance(user(1)), 600 * FLOAT_PRECISION);
+
+    assert!(_cancel_pending_operation(ticket, user(1)));
+
+    assert!(get_price_waiting_operation(ticket).is_none());
+    assert_eq!(get_user_balance(user(1)), 1_000 * FLOAT_PRECISION);
+    assert_eq!(
+        get_operation_status(ticket),
+        Some(OperationStatus::Cancelled)
+    );
+
+    // the operation is not queued anymore
+    assert!(!_cancel_pending_operation(ticket, user(1)));
+    assert_eq!(get_user_balance(user(1)), 1_000 * FLOAT_PRECISION);
+}
+
+#[test]
+fn cancelled_liquidity_removal_releases_the_shares() {
+    set_user_market_liquidity_shares(user(1), 0, 500 * FLOAT_PRECISION);
+
+    let operation = PriceWaitingOperation::RemoveLiquidity(RemoveLiquidityParams {
+        market_index: 0,
+        owner: user(1),
+        amount_in: 200 * FLOAT_PRECISION,
+        min_amount_out: 0,
+        deadline: None,
+    });
+    let ticket = _queue_price_waiting_operation(0, REMOVE_LIQUIDITY_PRIORITY_INDEX, operation);
+    assert_eq!(
+        get_user_market_liquidity_shares(user(1), 0),
+        300 * FLOAT_PRECISION
+    );
+
+    assert!(_cancel_pending_operation(ticket, user(1)));
+    assert_eq!(
+        get_user_market_liquidity_shares(user(1), 0),
+        500 * FLOAT_PRECISION
+    );
+}
+
+#[test]
+#[should_panic(expected = "Caller is not the owner of the operation")]
+fn operation_can_not_be_cancelled_by_another_principal() {
+    let ticket = queue_open_position();
+
+    _cancel_pending_operation(ticket, user(2));
+}
+
+#[test]
+#[should_panic(expected = "Caller is not the owner of the operation")]
+fn operation_queued_by_the_house_can_not_be_cancelled() {
+    let operation =
+        PriceWaitingOperation::CollectBorrowFees(CollectBorrowFeesParams { market_index: 0 });
+    let ticket = _queue_price_waiting_operation(0, COLLECT_BORROW_FEES_PRIORITY_INDEX, operation);
+
+    _cancel_pending_operation(ticket, Principal::anonymous());
+}
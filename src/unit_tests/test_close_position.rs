@@ -0,0 +1,169 @@
+use crate::close_position::close_position_result::ClosePositionResult;
+use crate::math::math::FLOAT_PRECISION;
+use crate::position::position_details::PositionDetails;
+use crate::unit_tests::utils::{
+    INITIAL_LIQUIDITY, PRICE, initiate_market, long_position, open_position, open_position_params,
+};
+
+#[test]
+fn split_divides_the_position_proportionally_to_units() {
+    let position = PositionDetails {
+        pre_cummulative_funding_factor: -(FLOAT_PRECISION as i128) / 100,
+        pre_cummulative_borrowing_factor: FLOAT_PRECISION / 100,
+        ..long_position()
+    };
+
+    let (split_position, remaining_position) = position.split(25 * FLOAT_PRECISION);
+
+    assert_eq!(
+        split_position,
+        PositionDetails {
+            collateral: 250 * FLOAT_PRECISION,
+            debt: 2_250 * FLOAT_PRECISION,
+            units: 25 * FLOAT_PRECISION,
+            max_reserve: 2_500 * FLOAT_PRECISION,
+            ..position
+        }
+    );
+    assert_eq!(
+        remaining_position,
+        PositionDetails {
+            collateral: 750 * FLOAT_PRECISION,
+            debt: 6_750 * FLOAT_PRECISION,
+            units: 75 * FLOAT_PRECISION,
+            max_reserve: 7_500 * FLOAT_PRECISION,
+            ..position
+        }
+    );
+}
+
+#[test]
+fn split_rounding_is_kept_by_the_remaining_position() {
+    let position = PositionDetails {
+        units: 3 * FLOAT_PRECISION,
+        ..long_position()
+    };
+
+    let (split_position, remaining_position) = position.split(FLOAT_PRECISION);
+
+    assert_eq!(split_position.collateral, 1_000 * FLOAT_PRECISION / 3);
+    assert_eq!(
+        split_position.collateral + remaining_position.collateral,
+        position.collateral
+    );
+    assert_eq!(split_position.debt + remaining_position.debt, position.debt);
+    assert_eq!(
+        split_position.max_reserve + remaining_position.max_reserve,
+        position.max_reserve
+    );
+}
+
+#[test]
+fn full_close_returns_collateral_and_profit_and_repays_debt() {
+    let mut market = initiate_market();
+    let position = open_position(
+        &mut market,
+        open_position_params(true, 1_000 * FLOAT_PRECISION, 10 * FLOAT_PRECISION),
+        PRICE,
+    );
+
+    // at 110 the position makes 1_000
+    let result = market._close_position_with_price_option(position, 0, Some(110 * FLOAT_PRECISION));
+
+    assert_eq!(
+        result,
+        ClosePositionResult::Settled {
+            returns: 2_000 * FLOAT_PRECISION,
+            position_fee: 0,
+        }
+    );
+
+    let liquidity_state = market.liquidity_state;
+    assert_eq!(
+        liquidity_state.free_liquidity,
+        INITIAL_LIQUIDITY - 1_000 * FLOAT_PRECISION
+    );
+    assert_eq!(
+        liquidity_state.total_deposit,
+        INITIAL_LIQUIDITY - 1_000 * FLOAT_PRECISION
+    );
+    assert_eq!(liquidity_state.current_net_debt, 0);
+    assert_eq!(liquidity_state.current_longs_reserve, 0);
+    assert_eq!(market.bias_tracker.longs.traders_open_interest(), 0);
+}
+
+#[test]
+fn partial_closes_settle_the_same_as_a_full_close() {
+    let mut market = initiate_market();
+    market.bias_tracker.longs.base_borrowing_factor = FLOAT_PRECISION / 1_000_000;
+    let position = open_position(
+        &mut market,
+        open_position_params(true, 1_000 * FLOAT_PRECISION, 10 * FLOAT_PRECISION),
+        PRICE,
+    );
+
+    // 1 of borrowing fees accrued on the position
+    market._collect_borrowing_fees_after_duration(|_| 0);
+    market._collect_borrowing_fees_after_duration(|_| 1_000);
+
+    let mut fully_closed_market = market.clone();
+    let full_close_result = fully_closed_market._close_position_with_price_option(
+        position,
+        0,
+        Some(110 * FLOAT_PRECISION),
+    );
+
+    assert_eq!(
+        full_close_result,
+        ClosePositionResult::Settled {
+            returns: 1_999 * FLOAT_PRECISION,
+            position_fee: 0,
+        }
+    );
+
+    let (split_position, remaining_position) = position.split(25 * FLOAT_PRECISION);
+
+    // the closed quarter pays a quarter of the borrowing fees
+    let split_close_result =
+        market._close_position_with_price_option(split_position, 0, Some(110 * FLOAT_PRECISION));
+    assert_eq!(
+        split_close_result,
+        ClosePositionResult::Settled {
+            returns: 49_975 * FLOAT_PRECISION / 100,
+            position_fee: 0,
+        }
+    );
+
+    let remaining_close_result = market._close_position_with_price_option(
+        remaining_position,
+        0,
+        Some(110 * FLOAT_PRECISION),
+    );
+    assert_eq!(
+        remaining_close_result,
+        ClosePositionResult::Settled {
+            returns: 149_925 * FLOAT_PRECISION / 100,
+            position_fee: 0,
+        }
+    );
+
+    assert_eq!(market.liquidity_state, fully_closed_market.liquidity_state);
+    assert_eq!(market.bias_tracker, fully_closed_market.bias_tracker);
+}
+
+#[test]
+fn close_fails_beyond_the_acceptable_price() {
+    let mut market = initiate_market();
+    let position = open_position(
+        &mut market,
+        open_position_params(true, 1_000 * FLOAT_PRECISION, 10 * FLOAT_PRECISION),
+        PRICE,
+    );
+    let liquidity_state = market.liquidity_state;
+
+    // a long is closed by selling, the price is below the limit
+    let result = market._close_position_with_price_option(position, PRICE + 1, Some(PRICE));
+
+    assert_eq!(result, ClosePositionResult::Failed);
+    assert_eq!(market.liquidity_state, liquidity_state);
+}
@@ -0,0 +1,150 @@
+use crate::close_position::close_position_result::ClosePositionResult;
+use crate::constants::_ONE_SECOND;
+use crate::market::market_details::MarketDetails;
+use crate::math::math::FLOAT_PRECISION;
+use crate::position::position_details::PositionDetails;
+use crate::unit_tests::utils::{
+    INITIAL_LIQUIDITY, PRICE, initiate_market, open_position, open_position_params,
+};
+
+/// market with a 10_000 long reserve in a 100_000 pool and a base borrowing factor of 0.0001% per second for longs
+fn market_with_long_position() -> (MarketDetails, PositionDetails) {
+    let mut market = initiate_market();
+    market.bias_tracker.longs.base_borrowing_factor = FLOAT_PRECISION / 1_000_000;
+
+    let position = open_position(
+        &mut market,
+        open_position_params(true, 1_000 * FLOAT_PRECISION, 10 * FLOAT_PRECISION),
+        PRICE,
+    );
+
+    (market, position)
+}
+
+#[test]
+fn borrowing_factor_is_set_from_the_reserve_relative_to_pool_value() {
+    let (market, _) = market_with_long_position();
+
+    // 0.0001% * 10_000 / 100_000
+    assert_eq!(
+        market
+            .bias_tracker
+            .longs
+            .calculate_borrowing_factor_per_sec(INITIAL_LIQUIDITY, 10_000 * FLOAT_PRECISION),
+        FLOAT_PRECISION / 10_000_000
+    );
+}
+
+#[test]
+fn borrowing_fees_accrue_for_the_duration_since_last_collection() {
+    let (mut market, position) = market_with_long_position();
+
+    // the first collection only sets the borrowing factor per second
+    market._collect_borrowing_fees_after_duration(|_| 0);
+    assert_eq!(market.get_cummulative_borrowing_factor_since_epoch(true), 0);
+    assert_eq!(market.liquidity_state.current_borrow_fees_owed, 0);
+
+    market._collect_borrowing_fees_after_duration(|_| 1_000);
+
+    // 0.00001% per second for 1_000 seconds on 10_000 open interest
+    let cummulative_borrowing_factor = market.get_cummulative_borrowing_factor_since_epoch(true);
+    assert_eq!(cummulative_borrowing_factor, FLOAT_PRECISION / 10_000);
+    assert_eq!(
+        market.liquidity_state.current_borrow_fees_owed,
+        FLOAT_PRECISION
+    );
+    assert_eq!(
+        market.liquidity_state.last_time_since_borrow_fees_collected,
+        1_000 * _ONE_SECOND
+    );
+    assert_eq!(
+        position.get_net_borrowing_fee(cummulative_borrowing_factor),
+        FLOAT_PRECISION
+    );
+
+    // shorts have no reserve and pay nothing
+    assert_eq!(
+        market.get_cummulative_borrowing_factor_since_epoch(false),
+        0
+    );
+}
+
+#[test]
+fn borrowing_fees_are_paid_to_the_house_on_close() {
+    let (mut market, position) = market_with_long_position();
+    market._collect_borrowing_fees_after_duration(|_| 0);
+    market._collect_borrowing_fees_after_duration(|_| 1_000);
+
+    let result = market._close_position_with_price_option(position, 0, Some(PRICE));
+
+    assert_eq!(
+        result,
+        ClosePositionResult::Settled {
+            returns: 999 * FLOAT_PRECISION,
+            position_fee: 0,
+        }
+    );
+    assert_eq!(market.liquidity_state.current_borrow_fees_owed, 0);
+    assert_eq!(
+        market.liquidity_state.free_liquidity,
+        INITIAL_LIQUIDITY + FLOAT_PRECISION
+    );
+    assert_eq!(
+        market.liquidity_state.total_deposit,
+        INITIAL_LIQUIDITY + FLOAT_PRECISION
+    );
+}
+
+#[test]
+fn zero_pool_value_sets_no_borrowing_factor() {
+    let (mut market, _) = market_with_long_position();
+
+    assert_eq!(
+        market
+            .bias_tracker
+            .longs
+            .calculate_borrowing_factor_per_sec(0, 10_000 * FLOAT_PRECISION),
+        0
+    );
+
+    // bad debt above the pool's tokens makes the pool value zero
+    market.liquidity_state.current_house_bad_debt = 2 * INITIAL_LIQUIDITY;
+
+    market._collect_borrowing_fees_after_duration(|_| 0);
+    market._collect_borrowing_fees_after_duration(|_| 1_000);
+
+    assert_eq!(market.get_cummulative_borrowing_factor_since_epoch(true), 0);
+    assert_eq!(market.liquidity_state.current_borrow_fees_owed, 0);
+}
+
+#[test]
+fn closing_every_position_of_the_market_settles_the_borrow_fees_owed() {
+    let (mut market, first_position) = market_with_long_position();
+
+    // open interests that do not divide the payments evenly
+    let second_position = open_position(
+        &mut market,
+        open_position_params(true, 333 * FLOAT_PRECISION + 1_666_667, 3 * FLOAT_PRECISION),
+        PRICE,
+    );
+    let third_position = open_position(
+        &mut market,
+        open_position_params(true, 77 * FLOAT_PRECISION + 1, 7 * FLOAT_PRECISION),
+        PRICE,
+    );
+
+    market._collect_borrowing_fees_after_duration(|_| 0);
+    // each collection rounds its payment down, while positions pay on the sum of the factors
+    for _ in 0..100 {
+        market._collect_borrowing_fees_after_duration(|_| 1);
+    }
+
+    for position in [first_position, second_position, third_position] {
+        let result = market._close_position_with_price_option(position, 0, Some(PRICE));
+        assert!(matches!(result, ClosePositionResult::Settled { .. }));
+    }
+
+    assert_eq!(market.liquidity_state.current_borrow_fees_owed, 0);
+    assert_eq!(market.liquidity_state.current_net_debt, 0);
+    assert_eq!(market.liquidity_state.current_longs_reserve, 0);
+}
@@ -0,0 +1,111 @@
+use candid::Principal;
+
+use crate::constants::DELISTING_SETTLEMENT_BATCH;
+use crate::delisting::delisting_utils::_settle_delisted_positions;
+use crate::market::functions::remove_liquidity::RemoveLiquidityFromMarketParams;
+use crate::market::market_details::{LiquidityOperationResult, MarketDetails};
+use crate::math::math::FLOAT_PRECISION;
+use crate::stable_memory::MARKETS_LIST;
+use crate::unit_tests::utils::{
+    INITIAL_LIQUIDITY, PRICE, initiate_market, open_position, open_position_params,
+};
+use crate::user::balance_utils::get_user_balance;
+use crate::user::position_util::_put_user_position_detail;
+use crate::user::user_query::try_get_user_position_details;
+
+fn user(id: u8) -> Principal {
+    Principal::from_slice(&[id])
+}
+
+/// market 0 with `count` longs of 100 collateral at 2x opened at PRICE by user(1),
+/// and a long of user(2) in market 1
+fn markets_with_positions(count: u64) {
+    let mut market = initiate_market();
+    market.liquidity_state.total_liquidity_shares = INITIAL_LIQUIDITY;
+
+    for position_id in 0..count {
+        let position = open_position(
+            &mut market,
+            open_position_params(true, 100 * FLOAT_PRECISION, 2 * FLOAT_PRECISION),
+            PRICE,
+        );
+        _put_user_position_detail(user(1), 0, position_id, position);
+    }
+
+    let mut other_market = initiate_market();
+    let position = open_position(
+        &mut other_market,
+        open_position_params(true, 100 * FLOAT_PRECISION, 2 * FLOAT_PRECISION),
+        PRICE,
+    );
+    _put_user_position_detail(user(2), 1, 0, position);
+
+    MARKETS_LIST.with_borrow_mut(|reference| {
+        reference.push(&market);
+        reference.push(&other_market);
+    });
+}
+
+fn get_market(market_index: u64) -> MarketDetails {
+    MARKETS_LIST.with_borrow(|reference| reference.get(market_index).unwrap())
+}
+
+#[test]
+fn delisted_positions_are_settled_in_batches() {
+    let count = DELISTING_SETTLEMENT_BATCH as u64 + 1;
+    markets_with_positions(count);
+
+    let (settled, unsettled, next) = _settle_delisted_positions(0, PRICE, None);
+    assert_eq!((settled, unsettled), (DELISTING_SETTLEMENT_BATCH as u64, 0));
+    assert_eq!(next, Some((user(1), DELISTING_SETTLEMENT_BATCH as u64)));
+
+    // the position of the other market is scanned but not settled
+    let (settled, unsettled, next) = _settle_delisted_positions(0, PRICE, next);
+    assert_eq!((settled, unsettled, next), (1, 0, None));
+
+    assert!(try_get_user_position_details(user(1), count - 1).is_none());
+    assert!(try_get_user_position_details(user(2), 0).is_some());
+
+    // the collateral is returned without pnl at the opening price
+    assert_eq!(
+        get_user_balance(user(1)),
+        count as u128 * 100 * FLOAT_PRECISION
+    );
+
+    let market = get_market(0);
+    assert_eq!(market.liquidity_state.free_liquidity, INITIAL_LIQUIDITY);
+    assert_eq!(market.liquidity_state.total_deposit, INITIAL_LIQUIDITY);
+    assert_eq!(market.liquidity_state.current_net_debt, 0);
+    assert_eq!(market.liquidity_state.current_longs_reserve, 0);
+}
+
+#[test]
+fn settled_market_liquidity_is_redeemed_for_the_pool_value() {
+    markets_with_positions(2);
+
+    // the longs lose half their collateral at the final settlement price
+    let settlement_price = PRICE * 3 / 4;
+    let (settled, _, next) = _settle_delisted_positions(0, settlement_price, None);
+    assert_eq!((settled, next), (2, None));
+    assert_eq!(get_user_balance(user(1)), 100 * FLOAT_PRECISION);
+
+    let mut market = get_market(0);
+
+    // with no position left the pool value does not depend on the price
+    let result = market._remove_liquidity_from_market_with_price(
+        RemoveLiquidityFromMarketParams {
+            amount_in: INITIAL_LIQUIDITY,
+            min_amount_out: 0,
+        },
+        Some(PRICE),
+    );
+
+    assert_eq!(
+        result,
+        LiquidityOperationResult::Settled {
+            amount_out: INITIAL_LIQUIDITY + 100 * FLOAT_PRECISION
+        }
+    );
+    assert_eq!(market.liquidity_state.free_liquidity, 0);
+    assert_eq!(market.liquidity_state.total_deposit, 0);
+}
@@ -0,0 +1,146 @@
+use candid::Principal;
+
+use crate::admin_roles::grant_role::grant_role;
+use crate::admin_roles::revoke_role::revoke_role;
+use crate::admin_roles::roles::Role;
+use crate::constants::MAX_EVENTS_QUERY_LIMIT;
+use crate::events::event_log::record_event;
+use crate::events::evnts_type::{EventFilter, EventRecord, Events};
+use crate::query::events_query::{get_events, get_events_count};
+
+fn user(id: u8) -> Principal {
+    Principal::from_slice(&[id])
+}
+
+/// deposits of user(1) and user(2), a market 0 funding settlement and a withdrawal of user(1)
+fn record_events() {
+    record_event(Events::Deposit {
+        user: user(1),
+        amount: 100,
+    });
+    record_event(Events::Deposit {
+        user: user(2),
+        amount: 200,
+    });
+    record_event(Events::SettleFundingFees {
+        market_index: 0,
+        price: 1_000,
+    });
+    record_event(Events::Withdraw {
+        user: user(1),
+        amount: 50,
+    });
+}
+
+fn sequences(events: &[(u64, EventRecord)]) -> Vec<u64> {
+    events.iter().map(|(sequence, _)| *sequence).collect()
+}
+
+#[test]
+fn events_are_numbered_in_the_order_they_are_recorded() {
+    assert_eq!(record_event(Events::SetProtocolPaused { paused: true }), 0);
+    assert_eq!(record_event(Events::SetProtocolPaused { paused: false }), 1);
+    assert_eq!(get_events_count(), 2);
+
+    let events = get_events(0, 10, None);
+    assert_eq!(sequences(&events), vec![0, 1]);
+    assert!(matches!(
+        events[1].1.event,
+        Events::SetProtocolPaused { paused: false }
+    ));
+}
+
+#[test]
+fn events_are_paginated_from_the_start_sequence() {
+    record_events();
+
+    assert_eq!(sequences(&get_events(1, 2, None)), vec![1, 2]);
+    assert_eq!(sequences(&get_events(3, 10, None)), vec![3]);
+    assert!(get_events(4, 10, None).is_empty());
+}
+
+#[test]
+fn events_are_filtered_by_principal_and_market() {
+    record_events();
+
+    let by_user = EventFilter {
+        principal: Some(user(1)),
+        market_index: None,
+    };
+    assert_eq!(sequences(&get_events(0, 10, Some(by_user))), vec![0, 3]);
+
+    let by_market = EventFilter {
+        principal: None,
+        market_index: Some(0),
+    };
+    assert_eq!(sequences(&get_events(0, 10, Some(by_market))), vec![2]);
+
+    // both fields have to match
+    let by_user_and_market = EventFilter {
+        principal: Some(user(1)),
+        market_index: Some(0),
+    };
+    assert!(get_events(0, 10, Some(by_user_and_market)).is_empty());
+
+    // events recorded by unit tests are called by the anonymous principal
+    let by_caller = EventFilter {
+        principal: Some(Principal::anonymous()),
+        market_index: None,
+    };
+    assert_eq!(
+        sequences(&get_events(0, 10, Some(by_caller))),
+        vec![0, 1, 2, 3]
+    );
+}
+
+#[test]
+fn limit_applies_to_the_events_matching_the_filter() {
+    record_events();
+
+    let by_user = EventFilter {
+        principal: Some(user(1)),
+        market_index: None,
+    };
+    assert_eq!(sequences(&get_events(0, 1, Some(by_user.clone()))), vec![0]);
+    assert_eq!(sequences(&get_events(1, 1, Some(by_user))), vec![3]);
+}
+
+#[test]
+fn events_returned_are_capped_by_the_max_limit() {
+    for _ in 0..MAX_EVENTS_QUERY_LIMIT + 1 {
+        record_event(Events::SetProtocolPaused { paused: true });
+    }
+
+    assert_eq!(
+        get_events(0, u64::MAX, None).len() as u64,
+        MAX_EVENTS_QUERY_LIMIT
+    );
+}
+
+#[test]
+fn only_actions_changing_state_are_recorded() {
+    grant_role(user(1), Role::Keeper);
+    // the role is already granted
+    grant_role(user(1), Role::Keeper);
+    revoke_role(user(1), Role::Keeper);
+    // the role is already revoked
+    revoke_role(user(1), Role::Keeper);
+
+    let events = get_events(0, 10, None);
+    assert_eq!(events.len(), 2);
+    assert!(matches!(
+        events[0].1.event,
+        Events::GrantRole {
+            role: Role::Keeper,
+            ..
+        }
+    ));
+    assert!(matches!(
+        events[1].1.event,
+        Events::RevokeRole {
+            role: Role::Keeper,
+            ..
+        }
+    ));
+    assert_eq!(events[0].1.event.principal(), Some(user(1)));
+}
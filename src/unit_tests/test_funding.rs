@@ -0,0 +1,138 @@
+use crate::constants::_ONE_SECOND;
+use crate::market::components::funding_state::FundingState;
+use crate::math::math::FLOAT_PRECISION;
+use crate::unit_tests::utils::{PRICE, initiate_market, open_position, open_position_params};
+
+/// funding that changes by 0.00001% per second at full skew, up to 0.001% per second
+fn adaptive_funding_state() -> FundingState {
+    FundingState {
+        funding_increase_factor_ps: FLOAT_PRECISION / 10_000_000,
+        funding_decrease_factor_ps: FLOAT_PRECISION / 100_000_000,
+        max_funding_factor_ps: FLOAT_PRECISION / 100_000,
+        funding_exponent_factor: FLOAT_PRECISION,
+        threshold_stable_funding: FLOAT_PRECISION / 10,
+        threshold_decrease_funding: FLOAT_PRECISION / 100,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn funding_factor_increases_for_every_second_of_the_duration() {
+    let mut funding_state = adaptive_funding_state();
+
+    // longs are half of the open interest more than shorts
+    funding_state._update_funding_factor_ps(
+        1_000 * FLOAT_PRECISION as i128,
+        2_000 * FLOAT_PRECISION,
+        10,
+    );
+
+    // 50% of 0.00001% per second for 10 seconds
+    assert_eq!(
+        funding_state.current_funding_factor_ps,
+        FLOAT_PRECISION as i128 / 2_000_000
+    );
+}
+
+#[test]
+fn funding_factor_decreases_for_every_second_of_the_duration() {
+    let mut funding_state = FundingState {
+        current_funding_factor_ps: FLOAT_PRECISION as i128 / 1_000_000,
+        ..adaptive_funding_state()
+    };
+
+    // the skew is below the decrease threshold
+    funding_state._update_funding_factor_ps(FLOAT_PRECISION as i128, 2_000 * FLOAT_PRECISION, 10);
+
+    // 0.000001% per second for 10 seconds
+    assert_eq!(
+        funding_state.current_funding_factor_ps,
+        FLOAT_PRECISION as i128 / 1_000_000 - FLOAT_PRECISION as i128 / 10_000_000
+    );
+}
+
+#[test]
+fn funding_settlement_updates_the_factor_for_the_elapsed_duration() {
+    let mut market = initiate_market();
+    market.funding_state = adaptive_funding_state();
+
+    open_position(
+        &mut market,
+        open_position_params(true, 3_000 * FLOAT_PRECISION, FLOAT_PRECISION),
+        PRICE,
+    );
+    open_position(
+        &mut market,
+        open_position_params(false, 1_000 * FLOAT_PRECISION, FLOAT_PRECISION),
+        PRICE,
+    );
+
+    market._settle_funding_payment_after_duration(|_| 10);
+
+    // the same factor as for a skew of 50% of the open interest updated after 10 seconds
+    let mut funding_state = adaptive_funding_state();
+    funding_state._update_funding_factor_ps(
+        2_000 * FLOAT_PRECISION as i128,
+        4_000 * FLOAT_PRECISION,
+        10,
+    );
+
+    assert_eq!(
+        market.funding_state.current_funding_factor_ps,
+        funding_state.current_funding_factor_ps
+    );
+    assert_eq!(market.funding_state.last_time_updated, 10 * _ONE_SECOND);
+}
+
+#[test]
+fn funding_factor_increases_towards_the_new_skew_when_it_changes_direction() {
+    // shorts were paying longs
+    let mut funding_state = FundingState {
+        current_funding_factor_ps: -(FLOAT_PRECISION as i128) / 1_000_000,
+        ..adaptive_funding_state()
+    };
+
+    // longs are now 5% of the open interest more than shorts, between the decrease and stable thresholds
+    funding_state._update_funding_factor_ps(
+        100 * FLOAT_PRECISION as i128,
+        2_000 * FLOAT_PRECISION,
+        10,
+    );
+
+    // 5% of 0.00001% per second for 10 seconds towards longs paying
+    assert_eq!(
+        funding_state.current_funding_factor_ps,
+        -(FLOAT_PRECISION as i128) / 1_000_000 + FLOAT_PRECISION as i128 / 20_000_000
+    );
+}
+
+#[test]
+fn balanced_open_interest_pays_no_funding() {
+    let mut funding_state = FundingState {
+        current_funding_factor_ps: FLOAT_PRECISION as i128 / 1_000_000,
+        funding_increase_factor_ps: 0,
+        ..adaptive_funding_state()
+    };
+
+    funding_state._update_funding_factor_ps(0, 2_000 * FLOAT_PRECISION, 10);
+
+    assert_eq!(funding_state.current_funding_factor_ps, 0);
+}
+
+#[test]
+fn funding_time_moves_forward_without_open_interest_on_both_sides() {
+    let mut market = initiate_market();
+    market.funding_state = adaptive_funding_state();
+
+    open_position(
+        &mut market,
+        open_position_params(true, 1_000 * FLOAT_PRECISION, FLOAT_PRECISION),
+        PRICE,
+    );
+
+    market._settle_funding_payment_after_duration(|_| 5);
+
+    // no funding is paid for the duration and it is not paid again later
+    assert_eq!(market.funding_state.last_time_updated, 5 * _ONE_SECOND);
+    assert_eq!(market.get_cummulative_funding_factor_since_epoch(true), 0);
+}
@@ -0,0 +1,310 @@
+use candid::Principal;
+
+use crate::increase_position::increase_position_params::IncreasePositionParams;
+use crate::market::functions::open_position_in_market::{
+    FailureReason, OpenPositioninMarketResult,
+};
+use crate::market::market_details::MarketDetails;
+use crate::math::math::{FLOAT_PRECISION, to_precision};
+use crate::position::position_details::PositionDetails;
+use crate::unit_tests::utils::{PRICE, initiate_market, open_position, open_position_params};
+
+fn increase_position_params(collateral: u128, leverage_factor: u128) -> IncreasePositionParams {
+    IncreasePositionParams {
+        owner: Principal::anonymous(),
+        market_index: 0,
+        position_id: 0,
+        collateral,
+        leverage_factor,
+        acceptable_price_limit: u128::MAX,
+        reserve_factor: FLOAT_PRECISION,
+        deadline: None,
+    }
+}
+
+/// market with a long of 1_000 collateral at 10x opened at PRICE
+fn market_with_long_position() -> (MarketDetails, PositionDetails) {
+    let mut market = initiate_market();
+
+    let position = open_position(
+        &mut market,
+        open_position_params(true, 1_000 * FLOAT_PRECISION, 10 * FLOAT_PRECISION),
+        PRICE,
+    );
+
+    (market, position)
+}
+
+fn increased_position(result: OpenPositioninMarketResult) -> PositionDetails {
+    match result {
+        OpenPositioninMarketResult::Settled { position, .. } => position,
+        result => panic!("position not increased: {:?}", result),
+    }
+}
+
+#[test]
+fn increase_adds_collateral_debt_units_and_reserve() {
+    let (mut market, position) = market_with_long_position();
+
+    let result = market._increase_position_in_market_with_price(
+        position,
+        increase_position_params(1_000 * FLOAT_PRECISION, 10 * FLOAT_PRECISION),
+        Some(PRICE),
+    );
+
+    // 10x is the max leverage, the increased position is exactly at it
+    assert_eq!(
+        increased_position(result),
+        PositionDetails {
+            collateral: 2_000 * FLOAT_PRECISION,
+            debt: 18_000 * FLOAT_PRECISION,
+            units: 200 * FLOAT_PRECISION,
+            max_reserve: 20_000 * FLOAT_PRECISION,
+            ..position
+        }
+    );
+    assert_eq!(
+        market.liquidity_state.current_net_debt,
+        18_000 * FLOAT_PRECISION
+    );
+}
+
+#[test]
+fn increase_entry_price_is_the_weighted_average() {
+    let (mut market, position) = market_with_long_position();
+
+    let result = market._increase_position_in_market_with_price(
+        position,
+        increase_position_params(1_000 * FLOAT_PRECISION, 10 * FLOAT_PRECISION),
+        Some(110 * FLOAT_PRECISION),
+    );
+    let increased_position = increased_position(result);
+
+    assert_eq!(
+        increased_position.units,
+        100 * FLOAT_PRECISION + to_precision(10_000 * FLOAT_PRECISION, 110 * FLOAT_PRECISION)
+    );
+
+    // 20_000 of open interest for 100 units at 100 and 90.9 units at 110
+    let entry_price = to_precision(increased_position.open_interest(), increased_position.units);
+    assert!(entry_price > 10_476 * FLOAT_PRECISION / 100);
+    assert!(entry_price < 10_477 * FLOAT_PRECISION / 100);
+}
+
+/// accrues a borrowing fee of 0.01% on the long's 10_000 open interest
+fn accrue_borrowing_fee(market: &mut MarketDetails) {
+    market
+        .bias_tracker
+        .longs
+        .update_cumulative_borrowing_factor(0, FLOAT_PRECISION / 10_000_000);
+    let borrowing_fee = market
+        .bias_tracker
+        .longs
+        .update_cumulative_borrowing_factor(1_000, 0);
+    market.liquidity_state.current_borrow_fees_owed += borrowing_fee;
+}
+
+#[test]
+fn increase_settles_accrued_borrowing_fee() {
+    let (mut market, position) = market_with_long_position();
+    accrue_borrowing_fee(&mut market);
+
+    let cummulative_borrowing_factor = market.get_cummulative_borrowing_factor_since_epoch(true);
+    assert_eq!(
+        position.get_net_borrowing_fee(cummulative_borrowing_factor),
+        FLOAT_PRECISION
+    );
+    assert_eq!(
+        market.liquidity_state.current_borrow_fees_owed,
+        FLOAT_PRECISION
+    );
+    let liquidity_state = market.liquidity_state;
+
+    let result = market._increase_position_in_market_with_price(
+        position,
+        increase_position_params(1_000 * FLOAT_PRECISION, 10 * FLOAT_PRECISION),
+        Some(PRICE),
+    );
+    let increased_position = increased_position(result);
+
+    // the fee of 1 is paid from the added collateral, the other 999 are opened at 10x
+    assert_eq!(
+        increased_position,
+        PositionDetails {
+            collateral: 1_999 * FLOAT_PRECISION,
+            debt: 17_991 * FLOAT_PRECISION,
+            units: 19_990 * FLOAT_PRECISION / 100,
+            max_reserve: 19_990 * FLOAT_PRECISION,
+            pre_cummulative_borrowing_factor: cummulative_borrowing_factor,
+            ..position
+        }
+    );
+    assert_eq!(
+        increased_position.get_net_borrowing_fee(cummulative_borrowing_factor),
+        0
+    );
+
+    // the fee is paid to the free liquidity
+    assert_eq!(market.liquidity_state.current_borrow_fees_owed, 0);
+    assert_eq!(
+        market.liquidity_state.free_liquidity,
+        liquidity_state.free_liquidity - 18_981 * FLOAT_PRECISION + FLOAT_PRECISION
+    );
+    assert_eq!(
+        market.liquidity_state.total_deposit,
+        liquidity_state.total_deposit + 1_000 * FLOAT_PRECISION
+    );
+}
+
+#[test]
+fn increase_repays_bad_debt_with_the_borrowing_fee() {
+    let (mut market, position) = market_with_long_position();
+    accrue_borrowing_fee(&mut market);
+    market.liquidity_state.current_house_bad_debt = FLOAT_PRECISION / 2;
+    let liquidity_state = market.liquidity_state;
+
+    market._increase_position_in_market_with_price(
+        position,
+        increase_position_params(1_000 * FLOAT_PRECISION, 10 * FLOAT_PRECISION),
+        Some(PRICE),
+    );
+
+    assert_eq!(market.liquidity_state.current_house_bad_debt, 0);
+    assert_eq!(
+        market.liquidity_state.free_liquidity,
+        liquidity_state.free_liquidity - 18_981 * FLOAT_PRECISION + FLOAT_PRECISION / 2
+    );
+}
+
+#[test]
+fn increase_settles_accrued_funding_fee() {
+    let (mut market, position) = market_with_long_position();
+
+    // 0.01% funding paid on 10_000 open interest
+    market
+        .bias_tracker
+        .longs
+        .update_cumulative_funding_factor(-(FLOAT_PRECISION as i128) / 10_000);
+    let cummulative_funding_factor = market.get_cummulative_funding_factor_since_epoch(true);
+    assert_eq!(
+        position.get_net_funding_fee(cummulative_funding_factor),
+        -(FLOAT_PRECISION as i128)
+    );
+    let total_deposit = market.liquidity_state.total_deposit;
+
+    let result = market._increase_position_in_market_with_price(
+        position,
+        increase_position_params(1_000 * FLOAT_PRECISION, 10 * FLOAT_PRECISION),
+        Some(PRICE),
+    );
+    let increased_position = increased_position(result);
+
+    assert_eq!(increased_position.collateral, 1_999 * FLOAT_PRECISION);
+    assert_eq!(
+        increased_position.pre_cummulative_funding_factor,
+        cummulative_funding_factor
+    );
+    assert_eq!(
+        increased_position.get_net_funding_fee(cummulative_funding_factor),
+        0
+    );
+
+    // the funding paid is kept in the market for the shorts
+    assert_eq!(
+        market.liquidity_state.total_deposit,
+        total_deposit + 1_000 * FLOAT_PRECISION
+    );
+
+    // the longs owe no more funding, their open interest dynamic is back to their open interest
+    let (open_interest, open_interest_dynamic, ..) = market.bias_tracker.longs.bias_parameters();
+    assert_eq!(open_interest, 19_990 * FLOAT_PRECISION);
+    assert_eq!(open_interest_dynamic, open_interest as i128);
+}
+
+#[test]
+fn increase_fails_if_fees_exceed_added_collateral() {
+    let (mut market, position) = market_with_long_position();
+    accrue_borrowing_fee(&mut market);
+    let liquidity_state = market.liquidity_state;
+
+    let result = market._increase_position_in_market_with_price(
+        position,
+        increase_position_params(FLOAT_PRECISION, 10 * FLOAT_PRECISION),
+        Some(PRICE),
+    );
+
+    assert_eq!(
+        result,
+        OpenPositioninMarketResult::Failed {
+            reason: FailureReason::Other
+        }
+    );
+    assert_eq!(market.liquidity_state, liquidity_state);
+}
+
+#[test]
+fn increase_fails_if_leverage_exceeds_max() {
+    let (mut market, position) = market_with_long_position();
+    market.state.max_leverage_factor = 5 * FLOAT_PRECISION;
+    let liquidity_state = market.liquidity_state;
+
+    // 15_000 open interest on 2_000 collateral is above the lowered 5x
+    let result = market._increase_position_in_market_with_price(
+        position,
+        increase_position_params(1_000 * FLOAT_PRECISION, 5 * FLOAT_PRECISION),
+        Some(PRICE),
+    );
+
+    assert_eq!(
+        result,
+        OpenPositioninMarketResult::Failed {
+            reason: FailureReason::Other
+        }
+    );
+    assert_eq!(market.liquidity_state, liquidity_state);
+}
+
+#[test]
+fn increase_fails_if_fees_exceed_collateral() {
+    let (mut market, position) = market_with_long_position();
+
+    // 10% funding paid on 10_000 open interest uses up the 1_000 collateral
+    market
+        .bias_tracker
+        .longs
+        .update_cumulative_funding_factor(-(FLOAT_PRECISION as i128) / 10);
+
+    let result = market._increase_position_in_market_with_price(
+        position,
+        increase_position_params(1_000 * FLOAT_PRECISION, FLOAT_PRECISION),
+        Some(PRICE),
+    );
+
+    assert_eq!(
+        result,
+        OpenPositioninMarketResult::Failed {
+            reason: FailureReason::Other
+        }
+    );
+}
+
+#[test]
+fn increase_fails_beyond_the_acceptable_price() {
+    let (mut market, position) = market_with_long_position();
+
+    let result = market._increase_position_in_market_with_price(
+        position,
+        IncreasePositionParams {
+            acceptable_price_limit: PRICE - 1,
+            ..increase_position_params(1_000 * FLOAT_PRECISION, 10 * FLOAT_PRECISION)
+        },
+        Some(PRICE),
+    );
+
+    assert_eq!(
+        result,
+        OpenPositioninMarketResult::Failed {
+            reason: FailureReason::PriceLimitExceeded
+        }
+    );
+}
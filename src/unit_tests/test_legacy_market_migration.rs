@@ -0,0 +1,175 @@
+use std::borrow::Cow;
+
+use candid::Principal;
+use ic_stable_structures::memory_manager::{MemoryId, MemoryManager, VirtualMemory};
+use ic_stable_structures::{StableVec, Storable, VectorMemory};
+
+use crate::asset_management::asset_management::{AssetLedger, AssetLedgerType};
+use crate::house_settings::{HouseDetails, LegacyHouseDetails};
+use crate::market::components::pricing::PricingState;
+use crate::market::legacy_market_details::{
+    _migrate_legacy_markets, LegacyMarketDetails, LegacyMarketState, LegacyPricingState,
+};
+use crate::market::market_details::{MarketDetails, MarketState};
+use crate::market::market_status::MarketStatus;
+use crate::math::math::FLOAT_PRECISION;
+use crate::pricing_update_management::price_fetch::{AssetClass, AssetPricingDetails};
+
+type TestMemory = VirtualMemory<VectorMemory>;
+
+fn legacy_market(symbol: &str) -> LegacyMarketDetails {
+    LegacyMarketDetails {
+        index_asset_pricing_details: AssetPricingDetails {
+            symbol: symbol.to_string(),
+            class: AssetClass::Cryptocurrency,
+        },
+        pricing_manager: LegacyPricingState {
+            last_time_updated: 1_700_000_000_000_000_000,
+            price: 120 * FLOAT_PRECISION,
+            price_impact_exponent_factor: 2 * FLOAT_PRECISION,
+            positive_price_impact_factor: FLOAT_PRECISION / 1000,
+            negative_price_impact_factor: FLOAT_PRECISION / 500,
+        },
+        state: LegacyMarketState {
+            max_leverage_factor: 50 * FLOAT_PRECISION,
+            max_reserve_factor: FLOAT_PRECISION / 2,
+            liquidation_factor: FLOAT_PRECISION / 100,
+        },
+        ..LegacyMarketDetails::default()
+    }
+}
+
+/// the legacy and current markets lists on separate memories of one stable memory, as in the canister
+fn markets_memories() -> (TestMemory, TestMemory) {
+    let memory_manager = MemoryManager::init(VectorMemory::default());
+    (
+        memory_manager.get(MemoryId::new(3)),
+        memory_manager.get(MemoryId::new(29)),
+    )
+}
+
+#[test]
+fn legacy_markets_are_migrated_with_defaults_for_new_fields() {
+    let (legacy_memory, memory) = markets_memories();
+    let legacy_markets: StableVec<LegacyMarketDetails, TestMemory> = StableVec::init(legacy_memory);
+    legacy_markets.push(&legacy_market("BTC"));
+    legacy_markets.push(&legacy_market("ETH"));
+
+    let markets: StableVec<MarketDetails, TestMemory> = StableVec::init(memory);
+    _migrate_legacy_markets(&legacy_markets, &markets);
+
+    assert!(legacy_markets.is_empty());
+    assert_eq!(markets.len(), 2);
+
+    let btc_market = markets.get(0).unwrap();
+    assert_eq!(btc_market.index_asset_pricing_details.symbol, "BTC");
+    assert_eq!(
+        markets.get(1).unwrap().index_asset_pricing_details.symbol,
+        "ETH"
+    );
+    assert_eq!(
+        btc_market.pricing_manager,
+        PricingState {
+            last_time_updated: 1_700_000_000_000_000_000,
+            price: 120 * FLOAT_PRECISION,
+            price_impact_exponent_factor: 2 * FLOAT_PRECISION,
+            positive_price_impact_factor: FLOAT_PRECISION / 1000,
+            negative_price_impact_factor: FLOAT_PRECISION / 500,
+            impact_pool: 0,
+        }
+    );
+    assert_eq!(
+        btc_market.state,
+        MarketState {
+            max_leverage_factor: 50 * FLOAT_PRECISION,
+            max_reserve_factor: FLOAT_PRECISION / 2,
+            liquidation_factor: FLOAT_PRECISION / 100,
+            ..MarketState::default()
+        }
+    );
+    assert!(btc_market.status == MarketStatus::Active);
+}
+
+#[test]
+fn migration_runs_once() {
+    let (legacy_memory, memory) = markets_memories();
+    let legacy_markets: StableVec<LegacyMarketDetails, TestMemory> = StableVec::init(legacy_memory);
+    legacy_markets.push(&legacy_market("BTC"));
+
+    let markets: StableVec<MarketDetails, TestMemory> = StableVec::init(memory);
+    _migrate_legacy_markets(&legacy_markets, &markets);
+    // a later upgrade finds the legacy list empty
+    _migrate_legacy_markets(&legacy_markets, &markets);
+
+    assert_eq!(markets.len(), 1);
+}
+
+#[test]
+fn fresh_install_migrates_nothing() {
+    let (legacy_memory, memory) = markets_memories();
+    let legacy_markets: StableVec<LegacyMarketDetails, TestMemory> = StableVec::init(legacy_memory);
+    let markets: StableVec<MarketDetails, TestMemory> = StableVec::init(memory);
+
+    _migrate_legacy_markets(&legacy_markets, &markets);
+
+    assert!(markets.is_empty());
+}
+
+#[test]
+#[should_panic]
+fn current_layout_can_not_be_read_from_the_legacy_memory() {
+    let (legacy_memory, _) = markets_memories();
+    let legacy_markets: StableVec<LegacyMarketDetails, TestMemory> =
+        StableVec::init(legacy_memory.clone());
+    legacy_markets.push(&legacy_market("BTC"));
+
+    // the element bound is part of the StableVec layout
+    let _markets: StableVec<MarketDetails, TestMemory> = StableVec::init(legacy_memory);
+}
+
+#[test]
+fn legacy_house_details_are_decoded() {
+    let legacy_house_details = LegacyHouseDetails {
+        house_asset_ledger: AssetLedger {
+            ledger_id: Principal::anonymous(),
+            ledger_type: AssetLedgerType::ICRC,
+            asset_decimals: 8,
+            ledger_fee: 10_000,
+        },
+        house_asset_pricing_details: AssetPricingDetails {
+            symbol: "ICP".to_string(),
+            class: AssetClass::Cryptocurrency,
+        },
+        execution_fee: 20_000,
+        execution_fees_accumulated: 300_000,
+        position_fees_acccumulated: 4_000_000,
+    };
+    let bytes = bincode::serialize(&legacy_house_details).unwrap();
+
+    let house_details = HouseDetails::from_bytes(Cow::Owned(bytes));
+
+    assert_eq!(
+        house_details.house_asset_ledger,
+        legacy_house_details.house_asset_ledger
+    );
+    assert_eq!(house_details.house_asset_pricing_details.symbol, "ICP");
+    assert_eq!(house_details.execution_fee, 20_000);
+    assert_eq!(house_details.execution_fees_accumulated, 300_000);
+    assert_eq!(house_details.position_fees_acccumulated, 4_000_000);
+    assert!(house_details.treasury_account.is_none());
+    assert!(!house_details.paused);
+}
+
+#[test]
+fn current_house_details_are_decoded() {
+    let house_details = HouseDetails {
+        execution_fee: 20_000,
+        paused: true,
+        ..HouseDetails::default()
+    };
+
+    let decoded = HouseDetails::from_bytes(house_details.to_bytes());
+
+    assert_eq!(decoded.execution_fee, 20_000);
+    assert!(decoded.paused);
+}
@@ -0,0 +1,177 @@
+use crate::liquidate_position::liquidate_position_result::LiquidatePositionResult;
+use crate::math::math::FLOAT_PRECISION;
+use crate::position::position_details::PositionDetails;
+use crate::unit_tests::utils::{
+    INITIAL_LIQUIDITY, PRICE, initiate_market, long_position, open_position, open_position_params,
+};
+
+const LIQUIDATION_FACTOR: u128 = FLOAT_PRECISION / 10;
+
+fn short_position() -> PositionDetails {
+    PositionDetails {
+        long: false,
+        ..long_position()
+    }
+}
+
+#[test]
+fn long_is_liquidatable_at_the_liquidation_factor() {
+    let position = long_position();
+
+    // at 91 the loss of 900 leaves 100, 10% of the 1_000 collateral
+    assert!(position.is_liquidatable(91 * FLOAT_PRECISION, 0, 0, LIQUIDATION_FACTOR));
+    // at 91.01 the loss of 899 leaves 101
+    assert!(!position.is_liquidatable(9_101 * FLOAT_PRECISION / 100, 0, 0, LIQUIDATION_FACTOR));
+}
+
+#[test]
+fn short_is_liquidatable_at_the_liquidation_factor() {
+    let position = short_position();
+
+    assert!(position.is_liquidatable(109 * FLOAT_PRECISION, 0, 0, LIQUIDATION_FACTOR));
+    assert!(!position.is_liquidatable(10_899 * FLOAT_PRECISION / 100, 0, 0, LIQUIDATION_FACTOR));
+}
+
+#[test]
+fn accrued_fees_reduce_the_collateral_checked() {
+    let position = long_position();
+    let price = 92 * FLOAT_PRECISION;
+
+    // without fees the loss of 800 leaves 200, above 100
+    assert!(!position.is_liquidatable(price, 0, 0, LIQUIDATION_FACTOR));
+
+    // 2% borrowing on 10_000 open interest leaves 800 collateral, the loss of 800 leaves 0, below 80
+    assert!(position.is_liquidatable(price, 0, FLOAT_PRECISION / 50, LIQUIDATION_FACTOR));
+
+    // 1% funding received adds 100 collateral
+    assert!(!position.is_liquidatable(
+        91 * FLOAT_PRECISION,
+        FLOAT_PRECISION as i128 / 100,
+        0,
+        LIQUIDATION_FACTOR
+    ));
+}
+
+#[test]
+fn position_with_fees_exceeding_collateral_is_liquidatable_in_profit() {
+    let position = long_position();
+
+    // 10% borrowing on 10_000 open interest uses up the 1_000 collateral
+    assert!(position.is_liquidatable(
+        110 * FLOAT_PRECISION,
+        0,
+        FLOAT_PRECISION / 10,
+        LIQUIDATION_FACTOR
+    ));
+}
+
+#[test]
+fn healthy_position_is_not_liquidated() {
+    let mut market = initiate_market();
+    let position = open_position(
+        &mut market,
+        open_position_params(true, 1_000 * FLOAT_PRECISION, 10 * FLOAT_PRECISION),
+        PRICE,
+    );
+    let liquidity_state = market.liquidity_state;
+
+    let result = market._liquidate_position_with_price(position, Some(PRICE));
+
+    assert_eq!(
+        result,
+        LiquidatePositionResult::Failed("Position is not liquidatable".to_string())
+    );
+    assert_eq!(market.liquidity_state, liquidity_state);
+}
+
+#[test]
+fn liquidation_waits_for_a_price() {
+    let mut market = initiate_market();
+    let position = open_position(
+        &mut market,
+        open_position_params(true, 1_000 * FLOAT_PRECISION, 10 * FLOAT_PRECISION),
+        PRICE,
+    );
+
+    let result = market._liquidate_position_with_price(position, None);
+
+    assert_eq!(result, LiquidatePositionResult::Waiting { id: None });
+}
+
+#[test]
+fn liquidation_pays_reward_then_fee_then_owner() {
+    let mut market = initiate_market();
+    // 0.5% reward and 0.25% fee on 10_000 open interest
+    market.state.liquidation_reward_factor = FLOAT_PRECISION / 200;
+    market.state.liquidation_fee_factor = FLOAT_PRECISION / 400;
+
+    let position = open_position(
+        &mut market,
+        open_position_params(true, 1_000 * FLOAT_PRECISION, 10 * FLOAT_PRECISION),
+        PRICE,
+    );
+    assert_eq!(position, long_position());
+
+    // closing at 91 returns 100
+    let result = market._liquidate_position_with_price(position, Some(91 * FLOAT_PRECISION));
+
+    assert_eq!(
+        result,
+        LiquidatePositionResult::Settled {
+            liquidator_reward: 50 * FLOAT_PRECISION,
+            liquidation_fee: 25 * FLOAT_PRECISION,
+            returns: 25 * FLOAT_PRECISION,
+        }
+    );
+
+    // the house keeps the 900 loss and the 25 fee, the debt and reserve are returned
+    let liquidity_state = market.liquidity_state;
+    assert_eq!(
+        liquidity_state.free_liquidity,
+        INITIAL_LIQUIDITY + 925 * FLOAT_PRECISION
+    );
+    assert_eq!(
+        liquidity_state.total_deposit,
+        INITIAL_LIQUIDITY + 925 * FLOAT_PRECISION
+    );
+    assert_eq!(liquidity_state.current_net_debt, 0);
+    assert_eq!(liquidity_state.current_longs_reserve, 0);
+    assert_eq!(liquidity_state.current_house_bad_debt, 0);
+}
+
+#[test]
+fn underwater_liquidation_pays_the_reward_from_free_liquidity() {
+    let mut market = initiate_market();
+    market.state.liquidation_reward_factor = FLOAT_PRECISION / 200;
+    market.state.liquidation_fee_factor = FLOAT_PRECISION / 400;
+
+    let position = open_position(
+        &mut market,
+        open_position_params(true, 1_000 * FLOAT_PRECISION, 10 * FLOAT_PRECISION),
+        PRICE,
+    );
+
+    // at 85 the loss of 1_500 exceeds the collateral, nothing is returned
+    let result = market._liquidate_position_with_price(position, Some(85 * FLOAT_PRECISION));
+
+    assert_eq!(
+        result,
+        LiquidatePositionResult::Settled {
+            liquidator_reward: 50 * FLOAT_PRECISION,
+            liquidation_fee: 0,
+            returns: 0,
+        }
+    );
+
+    // the house keeps the 1_000 collateral less the 50 reward
+    let liquidity_state = market.liquidity_state;
+    assert_eq!(
+        liquidity_state.free_liquidity,
+        INITIAL_LIQUIDITY + 950 * FLOAT_PRECISION
+    );
+    assert_eq!(
+        liquidity_state.total_deposit,
+        INITIAL_LIQUIDITY + 950 * FLOAT_PRECISION
+    );
+    assert_eq!(liquidity_state.current_house_bad_debt, 0);
+}
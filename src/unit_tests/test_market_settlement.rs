@@ -0,0 +1,72 @@
+use crate::constants::_ONE_SECOND;
+use crate::events::evnts_type::Events;
+use crate::math::math::FLOAT_PRECISION;
+use crate::query::events_query::get_events;
+use crate::settlement_management::settlement_details::get_settlement_details;
+use crate::settlement_management::settlement_timer_utils::_run_market_settlement;
+use crate::stable_memory::MARKETS_LIST;
+use crate::unit_tests::utils::{PRICE, initiate_market, open_position, open_position_params};
+
+/// market 0 with a long and a short opened at time 0
+fn market_with_positions() {
+    let mut market = initiate_market();
+
+    open_position(
+        &mut market,
+        open_position_params(true, 3_000 * FLOAT_PRECISION, 2 * FLOAT_PRECISION),
+        PRICE,
+    );
+    open_position(
+        &mut market,
+        open_position_params(false, 1_000 * FLOAT_PRECISION, 2 * FLOAT_PRECISION),
+        PRICE,
+    );
+
+    MARKETS_LIST.with_borrow_mut(|reference| {
+        reference.push(&market);
+    });
+}
+
+#[test]
+fn settlement_accrues_funding_and_borrowing_fees_up_to_the_run_time() {
+    market_with_positions();
+
+    // the last half second is settled on the next run
+    let run_time = 3_600 * _ONE_SECOND + _ONE_SECOND / 2;
+    _run_market_settlement(0, run_time);
+
+    let market = MARKETS_LIST.with_borrow(|reference| reference.get(0).unwrap());
+    assert_eq!(market.funding_state.last_time_updated, 3_600 * _ONE_SECOND);
+    assert_eq!(
+        market.liquidity_state.last_time_since_borrow_fees_collected,
+        3_600 * _ONE_SECOND
+    );
+
+    let details = get_settlement_details(0).unwrap();
+    assert_eq!(details.last_run_time, run_time);
+    assert_eq!(details.failures_count, 0);
+    assert!(details.last_failure.is_none());
+
+    let events = get_events(0, 10, None);
+    assert!(matches!(
+        events.last().unwrap().1.event,
+        Events::SettleMarketFees {
+            market_index: 0,
+            price: _
+        }
+    ));
+}
+
+#[test]
+fn failed_settlement_is_recorded_with_its_reason() {
+    _run_market_settlement(0, 10);
+    _run_market_settlement(0, 20);
+
+    let details = get_settlement_details(0).unwrap();
+    assert_eq!(details.last_run_time, 20);
+    assert_eq!(details.failures_count, 2);
+
+    let failure = details.last_failure.unwrap();
+    assert_eq!(failure.time, 20);
+    assert_eq!(failure.reason, "Market does not exist");
+}
@@ -1 +1,160 @@
+use candid::Principal;
 
+use crate::constants::{_ONE_SECOND, DEFAULT_TIMELOCK_DELAY};
+use crate::market::market_config::MarketConfig;
+use crate::math::math::FLOAT_PRECISION;
+use crate::stable_memory::MARKETS_LIST;
+use crate::timelock::timelock_utils::{_execute_queued_change, _queue_change};
+use crate::timelock::timelocked_change::TimelockedChange;
+use crate::unit_tests::utils::initiate_market;
+
+/// config of the test market with funding, borrowing and price impact set
+fn config() -> MarketConfig {
+    MarketConfig {
+        liquidation_reward_factor: FLOAT_PRECISION / 100,
+        liquidation_fee_factor: FLOAT_PRECISION / 100,
+        open_fee_factor: FLOAT_PRECISION / 1_000,
+        close_fee_factor: FLOAT_PRECISION / 1_000,
+        treasury_fee_factor: FLOAT_PRECISION / 2,
+        min_funding_factor_ps: FLOAT_PRECISION / 1_000_000_000,
+        max_funding_factor_ps: FLOAT_PRECISION / 100_000,
+        threshold_decrease_funding: FLOAT_PRECISION / 100,
+        threshold_stable_funding: FLOAT_PRECISION / 10,
+        funding_increase_factor_ps: FLOAT_PRECISION / 10_000_000,
+        funding_decrease_factor_ps: FLOAT_PRECISION / 100_000_000,
+        longs_base_borrowing_factor: FLOAT_PRECISION / 1_000_000_000,
+        shorts_base_borrowing_factor: FLOAT_PRECISION / 1_000_000_000,
+        positive_price_impact_factor: FLOAT_PRECISION / 1_000_000,
+        negative_price_impact_factor: FLOAT_PRECISION / 500_000,
+        ..initiate_market().config()
+    }
+}
+
+fn rejection(config: MarketConfig) -> String {
+    config.validate().unwrap_err()
+}
+
+#[test]
+fn config_is_applied_to_every_component_of_the_market() {
+    let mut market = initiate_market();
+
+    market.apply_config(config());
+
+    assert_eq!(market.config(), config());
+    assert_eq!(
+        market.liquidity_state.liquidation_factor,
+        FLOAT_PRECISION / 10
+    );
+    assert_eq!(
+        market.funding_state.threshold_stable_funding,
+        FLOAT_PRECISION / 10
+    );
+}
+
+#[test]
+fn config_within_bounds_is_valid() {
+    assert_eq!(config().validate(), Ok(()));
+}
+
+#[test]
+fn config_out_of_bounds_is_rejected() {
+    assert_eq!(
+        rejection(MarketConfig {
+            max_leverage_factor: FLOAT_PRECISION,
+            ..config()
+        }),
+        "Max leverage factor must be above 1x"
+    );
+    assert_eq!(
+        rejection(MarketConfig {
+            longs_max_reserve_factor: FLOAT_PRECISION + 1,
+            ..config()
+        }),
+        "Max reserve factors of biases must be at most 100%"
+    );
+    assert_eq!(
+        rejection(MarketConfig {
+            liquidation_reward_factor: FLOAT_PRECISION / 2,
+            liquidation_fee_factor: FLOAT_PRECISION / 2,
+            ..config()
+        }),
+        "Liquidation reward and fee factors must be below 100%"
+    );
+}
+
+#[test]
+fn inconsistent_config_is_rejected() {
+    assert_eq!(
+        rejection(MarketConfig {
+            min_funding_factor_ps: FLOAT_PRECISION / 10_000,
+            ..config()
+        }),
+        "Min funding factor must be at most max funding factor"
+    );
+    assert_eq!(
+        rejection(MarketConfig {
+            threshold_decrease_funding: FLOAT_PRECISION / 5,
+            ..config()
+        }),
+        "Decrease funding threshold must be at most stable funding threshold"
+    );
+    assert_eq!(
+        rejection(MarketConfig {
+            positive_price_impact_factor: FLOAT_PRECISION / 100_000,
+            ..config()
+        }),
+        "Positive price impact factor must be at most negative price impact factor"
+    );
+}
+
+#[test]
+fn queued_config_update_is_applied_once_executed() {
+    MARKETS_LIST.with_borrow_mut(|reference| {
+        reference.push(&initiate_market());
+    });
+
+    let change = TimelockedChange::UpdateMarketConfig {
+        market_index: 0,
+        config: Box::new(config()),
+    };
+    let change_id = _queue_change(change, Principal::anonymous(), 0);
+
+    let execution_time = DEFAULT_TIMELOCK_DELAY * _ONE_SECOND;
+    _execute_queued_change(change_id, Principal::anonymous(), execution_time);
+
+    let market = MARKETS_LIST.with_borrow(|reference| reference.get(0).unwrap());
+    assert_eq!(market.config(), config());
+
+    // fees are accrued with the previous config up to the execution
+    assert_eq!(market.funding_state.last_time_updated, execution_time);
+}
+
+#[test]
+#[should_panic(expected = "Max leverage factor must be above 1x")]
+fn invalid_config_update_can_not_be_queued() {
+    MARKETS_LIST.with_borrow_mut(|reference| {
+        reference.push(&initiate_market());
+    });
+
+    let config = MarketConfig {
+        max_leverage_factor: 0,
+        ..config()
+    };
+    let change = TimelockedChange::UpdateMarketConfig {
+        market_index: 0,
+        config: Box::new(config),
+    };
+
+    _queue_change(change, Principal::anonymous(), 0);
+}
+
+#[test]
+#[should_panic(expected = "Market does not exist")]
+fn config_update_of_a_missing_market_can_not_be_queued() {
+    let change = TimelockedChange::UpdateMarketConfig {
+        market_index: 0,
+        config: Box::new(config()),
+    };
+
+    _queue_change(change, Principal::anonymous(), 0);
+}
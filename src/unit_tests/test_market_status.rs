@@ -0,0 +1,181 @@
+use candid::Principal;
+
+use crate::add_liquidity::add_liquidity::_add_liquidity;
+use crate::add_liquidity::add_liquidity_params::AddLiquidityParams;
+use crate::admin_roles::pause_protocol::{pause_protocol, unpause_protocol};
+use crate::admin_roles::roles::{Role, grant_role};
+use crate::admin_roles::set_market_status::_set_market_status;
+use crate::close_position::close_position::_close_position;
+use crate::close_position::close_position_params::ClosePositionParams;
+use crate::close_position::close_position_result::ClosePositionResult;
+use crate::market::functions::open_position_in_market::{
+    FailureReason, OpenPositioninMarketResult,
+};
+use crate::market::market_details::{LiquidityOperationResult, MarketDetails};
+use crate::market::market_status::{MarketStatus, market_accepts};
+use crate::math::math::FLOAT_PRECISION;
+use crate::open_position::open_position::_open_position;
+use crate::open_position::open_position_params::OpenPositionParams;
+use crate::remove_liquidity::remove_liquidity::_remove_liquidity;
+use crate::remove_liquidity::remove_liquidity_params::RemoveLiquidityParams;
+use crate::stable_memory::MARKETS_LIST;
+use crate::unit_tests::utils::{initiate_market, long_position, open_position_params};
+use crate::user::balance_utils::{set_user_market_liquidity_shares, update_user_balance};
+use crate::user::position_util::_put_user_position_detail;
+use crate::user::user_query::try_get_user_position_details;
+
+fn user(id: u8) -> Principal {
+    Principal::from_slice(&[id])
+}
+
+/// market 0 with the status, a position 0 and liquidity shares held by user(1) in it
+fn market_with_status(status: MarketStatus) {
+    MARKETS_LIST.with_borrow_mut(|reference| {
+        reference.push(&MarketDetails {
+            status,
+            ..initiate_market()
+        });
+    });
+
+    update_user_balance(user(1), 10_000 * FLOAT_PRECISION, true);
+    set_user_market_liquidity_shares(user(1), 0, 1_000 * FLOAT_PRECISION);
+    _put_user_position_detail(user(1), 0, 0, long_position());
+}
+
+fn get_status(market_index: u64) -> MarketStatus {
+    MARKETS_LIST.with_borrow(|reference| reference.get(market_index).unwrap().status)
+}
+
+fn open_in_market() -> OpenPositioninMarketResult {
+    _open_position(&OpenPositionParams {
+        owner: user(1),
+        ..open_position_params(true, 1_000 * FLOAT_PRECISION, FLOAT_PRECISION)
+    })
+}
+
+fn add_liquidity_to_market() -> LiquidityOperationResult {
+    _add_liquidity(&AddLiquidityParams {
+        depositor: user(1),
+        market_index: 0,
+        amount: 1_000 * FLOAT_PRECISION,
+        min_amount_out: 0,
+        deadline: None,
+    })
+}
+
+fn close_in_market() -> ClosePositionResult {
+    _close_position(&ClosePositionParams {
+        market_index: 0,
+        owner: user(1),
+        position_id: 0,
+        acceptable_price_limit: 0,
+        size_delta: None,
+        deadline: None,
+    })
+}
+
+fn remove_liquidity_from_market() -> LiquidityOperationResult {
+    _remove_liquidity(&RemoveLiquidityParams {
+        owner: user(1),
+        market_index: 0,
+        amount_in: 1_000 * FLOAT_PRECISION,
+        min_amount_out: 0,
+        deadline: None,
+    })
+}
+
+#[test]
+fn statuses_allow_less_activity_as_they_get_more_restrictive() {
+    assert!(MarketStatus::Active.allows_opening());
+    assert!(MarketStatus::Active.allows_closing());
+
+    assert!(!MarketStatus::CloseOnly.allows_opening());
+    assert!(MarketStatus::CloseOnly.allows_closing());
+
+    for status in [MarketStatus::Paused, MarketStatus::Delisted] {
+        assert!(!status.allows_opening());
+        assert!(!status.allows_closing());
+    }
+}
+
+#[test]
+fn paused_protocol_rejects_every_market() {
+    market_with_status(MarketStatus::Active);
+    assert!(market_accepts(0, true));
+    assert!(market_accepts(0, false));
+
+    pause_protocol();
+    assert!(!market_accepts(0, true));
+    assert!(!market_accepts(0, false));
+    assert_eq!(
+        open_in_market(),
+        OpenPositioninMarketResult::Failed {
+            reason: FailureReason::MarketNotActive
+        }
+    );
+    assert_eq!(close_in_market(), ClosePositionResult::Failed);
+
+    unpause_protocol();
+    assert!(market_accepts(0, true));
+
+    // a market that does not exist accepts nothing
+    assert!(!market_accepts(1, false));
+}
+
+#[test]
+fn close_only_market_rejects_opening_and_adding_liquidity() {
+    market_with_status(MarketStatus::CloseOnly);
+
+    assert!(!market_accepts(0, true));
+    assert!(market_accepts(0, false));
+
+    assert_eq!(
+        open_in_market(),
+        OpenPositioninMarketResult::Failed {
+            reason: FailureReason::MarketNotActive
+        }
+    );
+    assert_eq!(
+        add_liquidity_to_market(),
+        LiquidityOperationResult::Failed("Market is not active".to_string())
+    );
+}
+
+#[test]
+fn paused_market_rejects_closing_and_removing_liquidity() {
+    market_with_status(MarketStatus::Paused);
+
+    assert_eq!(close_in_market(), ClosePositionResult::Failed);
+    assert!(try_get_user_position_details(user(1), 0).is_some());
+
+    assert_eq!(
+        remove_liquidity_from_market(),
+        LiquidityOperationResult::Failed("Market is paused".to_string())
+    );
+}
+
+#[test]
+fn pauser_can_only_make_a_market_more_restrictive() {
+    market_with_status(MarketStatus::Active);
+    grant_role(user(2), Role::Pauser);
+
+    _set_market_status(0, MarketStatus::Paused, user(2));
+    assert_eq!(get_status(0), MarketStatus::Paused);
+
+    let result =
+        std::panic::catch_unwind(|| _set_market_status(0, MarketStatus::CloseOnly, user(2)));
+    assert!(result.is_err());
+    assert_eq!(get_status(0), MarketStatus::Paused);
+
+    grant_role(user(3), Role::RiskManager);
+    _set_market_status(0, MarketStatus::Active, user(3));
+    assert_eq!(get_status(0), MarketStatus::Active);
+}
+
+#[test]
+#[should_panic(expected = "Market is delisted")]
+fn market_can_not_be_set_to_delisted() {
+    market_with_status(MarketStatus::Active);
+
+    _set_market_status(0, MarketStatus::Delisted, user(1));
+}
@@ -0,0 +1,150 @@
+use candid::Principal;
+use ic_stable_structures::{DefaultMemoryImpl, StableBTreeMap};
+
+use crate::add_liquidity::add_liquidity_params::AddLiquidityParams;
+use crate::close_position::close_position_params::ClosePositionParams;
+use crate::close_position::close_position_result::ClosePositionResult;
+use crate::constants::{
+    ADD_LIQUIDITY_PRIORITY_INDEX, CLOSE_POSITION_PRIORITY_INDEX, MAX_RETAINED_STATUSES,
+    OPEN_POSITION_PRIORITY_INDEX,
+};
+use crate::market::functions::open_position_in_market::{
+    FailureReason, OpenPositioninMarketResult,
+};
+use crate::market::market_details::LiquidityOperationResult;
+use crate::math::math::FLOAT_PRECISION;
+use crate::open_position::open_position_params::OpenPositionParams;
+use crate::pricing_update_management::operation_status::{
+    OperationOutcome, OperationStatus, get_operation_status, prune_statuses,
+};
+use crate::pricing_update_management::price_waiting_operation_trait::PriceWaitingOperation;
+use crate::pricing_update_management::price_waiting_operation_utils::{
+    _queue_price_waiting_operation, get_price_waiting_operation,
+};
+use crate::unit_tests::utils::{long_position, open_position_params};
+use crate::user::balance_utils::{get_user_balance, update_user_balance};
+
+fn user(id: u8) -> Principal {
+    Principal::from_slice(&[id])
+}
+
+/// open of 400 collateral by user(1) in the market
+fn open_operation(market_index: u64) -> PriceWaitingOperation {
+    PriceWaitingOperation::OpenPosition(OpenPositionParams {
+        owner: user(1),
+        market_index,
+        ..open_position_params(true, 400 * FLOAT_PRECISION, FLOAT_PRECISION)
+    })
+}
+
+#[test]
+fn queued_operations_get_unique_tickets_across_markets_and_priorities() {
+    update_user_balance(user(1), 1_000 * FLOAT_PRECISION, true);
+
+    let add_liquidity = PriceWaitingOperation::AddLiquidity(AddLiquidityParams {
+        market_index: 0,
+        depositor: user(1),
+        amount: 100 * FLOAT_PRECISION,
+        min_amount_out: 0,
+        deadline: None,
+    });
+    let close = PriceWaitingOperation::ClosePosition(ClosePositionParams {
+        market_index: 0,
+        owner: user(1),
+        position_id: 0,
+        acceptable_price_limit: 0,
+        size_delta: None,
+        deadline: None,
+    });
+
+    let tickets = [
+        _queue_price_waiting_operation(0, ADD_LIQUIDITY_PRIORITY_INDEX, add_liquidity),
+        _queue_price_waiting_operation(1, OPEN_POSITION_PRIORITY_INDEX, open_operation(1)),
+        _queue_price_waiting_operation(0, CLOSE_POSITION_PRIORITY_INDEX, close),
+    ];
+    assert_eq!(tickets, [0, 1, 2]);
+
+    for ticket in tickets {
+        assert_eq!(get_operation_status(ticket), Some(OperationStatus::Pending));
+    }
+
+    let (key, _) = get_price_waiting_operation(1).unwrap();
+    assert_eq!(key, (1, OPEN_POSITION_PRIORITY_INDEX, 1));
+}
+
+#[test]
+fn queued_operation_reserves_its_amounts() {
+    update_user_balance(user(1), 1_000 * FLOAT_PRECISION, true);
+
+    _queue_price_waiting_operation(0, OPEN_POSITION_PRIORITY_INDEX, open_operation(0));
+
+    assert_eq!(get_user_balance(user(1)), 600 * FLOAT_PRECISION);
+}
+
+#[test]
+fn unknown_ticket_has_no_status() {
+    assert_eq!(get_operation_status(0), None);
+    assert!(get_price_waiting_operation(0).is_none());
+}
+
+#[test]
+fn operation_results_are_reported_as_statuses() {
+    let position = long_position();
+
+    let opened = OpenPositioninMarketResult::Settled {
+        position_id: Some(3),
+        position,
+        position_fee: 0,
+    };
+    assert_eq!(
+        OperationStatus::from(opened),
+        OperationStatus::Settled(OperationOutcome::Position {
+            position_id: 3,
+            position
+        })
+    );
+
+    let failed = OpenPositioninMarketResult::Failed {
+        reason: FailureReason::PriceLimitExceeded,
+    };
+    assert_eq!(
+        OperationStatus::from(failed),
+        OperationStatus::Failed("Price limit exceeded".to_string())
+    );
+
+    let closed = ClosePositionResult::Settled {
+        returns: 5,
+        position_fee: 1,
+    };
+    assert_eq!(
+        OperationStatus::from(closed),
+        OperationStatus::Settled(OperationOutcome::Amount(5))
+    );
+
+    let waiting = LiquidityOperationResult::Waiting { id: None };
+    assert_eq!(OperationStatus::from(waiting), OperationStatus::Pending);
+}
+
+#[test]
+fn final_statuses_of_old_operations_are_pruned_in_batches() {
+    let mut statuses: StableBTreeMap<u64, OperationStatus, _> =
+        StableBTreeMap::init(DefaultMemoryImpl::default());
+
+    for id in 0..12 {
+        let status = if id == 1 {
+            OperationStatus::Pending
+        } else {
+            OperationStatus::Cancelled
+        };
+        statuses.insert(id, status);
+    }
+
+    // every id is among the latest ones
+    prune_statuses(&mut statuses, MAX_RETAINED_STATUSES);
+    assert_eq!(statuses.len(), 12);
+
+    // ids 0 to 11 are old, the first 10 are checked and the pending one is kept
+    prune_statuses(&mut statuses, MAX_RETAINED_STATUSES + 12);
+    let ids: Vec<u64> = statuses.keys().collect();
+    assert_eq!(ids, vec![1, 10, 11]);
+}
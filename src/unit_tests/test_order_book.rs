@@ -0,0 +1,97 @@
+use candid::Principal;
+
+use crate::close_position::close_position_params::ClosePositionParams;
+use crate::constants::TRIGGERS_EXECUTION_BATCH;
+use crate::order_management::order::{Order, OrderType};
+use crate::order_management::order_utils::{
+    _execute_triggered_orders, get_order_status, get_user_orders, put_order, remove_order,
+};
+use crate::pricing_update_management::operation_status::OperationStatus;
+use crate::unit_tests::utils::PRICE;
+
+/// limit close order of a long position, triggered at or above the trigger price
+fn limit_close_order(
+    owner: Principal,
+    market_index: u64,
+    position_id: u64,
+    deadline: Option<u64>,
+) -> Order {
+    Order {
+        order_type: OrderType::LimitClose(ClosePositionParams {
+            market_index,
+            owner,
+            position_id,
+            acceptable_price_limit: 0,
+            size_delta: None,
+            deadline,
+        }),
+        trigger_price: 2 * PRICE,
+        long: true,
+        created_at: 0,
+    }
+}
+
+fn user(id: u8) -> Principal {
+    Principal::from_slice(&[id])
+}
+
+#[test]
+fn orders_are_indexed_by_owner() {
+    let first_order_id = put_order(limit_close_order(user(1), 0, 1, None));
+    let second_order_id = put_order(limit_close_order(user(1), 1, 2, None));
+    put_order(limit_close_order(user(2), 0, 3, None));
+
+    let order_ids: Vec<u64> = get_user_orders(user(1))
+        .into_iter()
+        .map(|(order_id, _)| order_id)
+        .collect();
+    assert_eq!(order_ids, vec![first_order_id, second_order_id]);
+
+    remove_order((1, second_order_id));
+
+    assert_eq!(get_user_orders(user(1)).len(), 1);
+    assert_eq!(get_user_orders(user(2)).len(), 1);
+}
+
+#[test]
+fn order_book_is_checked_in_batches() {
+    for position_id in 0..TRIGGERS_EXECUTION_BATCH as u64 + 10 {
+        put_order(limit_close_order(user(1), 0, position_id, None));
+    }
+    put_order(limit_close_order(user(1), 1, 0, None));
+
+    // none of the orders is triggered at PRICE
+    let next = _execute_triggered_orders(0, PRICE, None, 0);
+    assert_eq!(next, Some((0, TRIGGERS_EXECUTION_BATCH as u64)));
+
+    // the orders of the other market are not checked
+    assert_eq!(_execute_triggered_orders(0, PRICE, next, 0), None);
+    assert_eq!(get_user_orders(user(1)).len(), TRIGGERS_EXECUTION_BATCH + 11);
+}
+
+#[test]
+fn expired_orders_are_failed_when_checked() {
+    let order_id = put_order(limit_close_order(user(1), 0, 1, Some(10)));
+
+    assert_eq!(_execute_triggered_orders(0, PRICE, None, 10), None);
+    assert_eq!(get_order_status(order_id), Some(OperationStatus::Pending));
+
+    _execute_triggered_orders(0, PRICE, None, 11);
+
+    assert_eq!(
+        get_order_status(order_id),
+        Some(OperationStatus::Failed("Deadline exceeded".to_string()))
+    );
+    assert!(get_user_orders(user(1)).is_empty());
+}
+
+#[test]
+fn triggered_orders_are_kept_while_the_market_does_not_accept_them() {
+    // the market does not exist, so it accepts neither opening nor closing
+    let order_id = put_order(limit_close_order(user(1), 0, 1, None));
+
+    _execute_triggered_orders(0, 3 * PRICE, None, 0);
+
+    assert_eq!(get_order_status(order_id), Some(OperationStatus::Pending));
+    assert_eq!(get_user_orders(user(1)).len(), 1);
+}
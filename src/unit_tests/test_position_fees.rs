@@ -0,0 +1,88 @@
+use crate::market::market_details::MarketDetails;
+use crate::math::math::FLOAT_PRECISION;
+use crate::unit_tests::utils::{INITIAL_LIQUIDITY, initiate_market};
+
+const OPEN_INTEREST: u128 = 10_000 * FLOAT_PRECISION;
+
+/// market charging 0.1% to open and 0.2% to close, 30% of position fees go to the treasury
+fn market_with_position_fees() -> MarketDetails {
+    let mut market = initiate_market();
+    market.state.open_fee_factor = FLOAT_PRECISION / 1_000;
+    market.state.close_fee_factor = FLOAT_PRECISION / 500;
+    market.state.treasury_fee_factor = 3 * FLOAT_PRECISION / 10;
+    market
+}
+
+#[test]
+fn position_fee_is_charged_on_open_interest() {
+    let market = market_with_position_fees();
+
+    assert_eq!(
+        market.get_position_fee(OPEN_INTEREST, true),
+        10 * FLOAT_PRECISION
+    );
+    assert_eq!(
+        market.get_position_fee(OPEN_INTEREST, false),
+        20 * FLOAT_PRECISION
+    );
+    assert_eq!(initiate_market().get_position_fee(OPEN_INTEREST, true), 0);
+}
+
+#[test]
+fn position_fee_is_split_between_treasury_and_liquidity_providers() {
+    let mut market = market_with_position_fees();
+
+    let treasury_fee = market.collect_position_fee(10 * FLOAT_PRECISION);
+
+    assert_eq!(treasury_fee, 3 * FLOAT_PRECISION);
+    assert_eq!(
+        market.liquidity_state.free_liquidity,
+        INITIAL_LIQUIDITY + 7 * FLOAT_PRECISION
+    );
+    assert_eq!(
+        market.liquidity_state.total_deposit,
+        INITIAL_LIQUIDITY + 7 * FLOAT_PRECISION
+    );
+}
+
+#[test]
+fn liquidity_providers_share_repays_bad_debt_first() {
+    let mut market = market_with_position_fees();
+    market.liquidity_state.current_house_bad_debt = 5 * FLOAT_PRECISION;
+
+    let treasury_fee = market.collect_position_fee(10 * FLOAT_PRECISION);
+
+    assert_eq!(treasury_fee, 3 * FLOAT_PRECISION);
+    assert_eq!(market.liquidity_state.current_house_bad_debt, 0);
+    assert_eq!(
+        market.liquidity_state.free_liquidity,
+        INITIAL_LIQUIDITY + 2 * FLOAT_PRECISION
+    );
+    assert_eq!(
+        market.liquidity_state.total_deposit,
+        INITIAL_LIQUIDITY + 7 * FLOAT_PRECISION
+    );
+}
+
+#[test]
+fn rounding_of_the_treasury_share_goes_to_liquidity_providers() {
+    let mut market = market_with_position_fees();
+
+    // 30% of 3 is 0.9, rounded down to 0
+    let treasury_fee = market.collect_position_fee(3);
+
+    assert_eq!(treasury_fee, 0);
+    assert_eq!(market.liquidity_state.free_liquidity, INITIAL_LIQUIDITY + 3);
+}
+
+#[test]
+fn whole_position_fee_goes_to_the_treasury_at_100_percent() {
+    let mut market = market_with_position_fees();
+    market.state.treasury_fee_factor = FLOAT_PRECISION;
+    let liquidity_state = market.liquidity_state;
+
+    let treasury_fee = market.collect_position_fee(10 * FLOAT_PRECISION);
+
+    assert_eq!(treasury_fee, 10 * FLOAT_PRECISION);
+    assert_eq!(market.liquidity_state, liquidity_state);
+}
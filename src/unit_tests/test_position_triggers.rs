@@ -0,0 +1,159 @@
+use candid::Principal;
+
+use crate::constants::TRIGGERS_EXECUTION_BATCH;
+use crate::math::math::FLOAT_PRECISION;
+use crate::order_management::position_triggers::{
+    PositionTriggers, TrailingDistance, TrailingStop, execute_position_triggers,
+    get_position_triggers, put_position_triggers,
+};
+use crate::unit_tests::utils::PRICE;
+
+fn user(id: u8) -> Principal {
+    Principal::from_slice(&[id])
+}
+
+/// triggers with a stop-loss 10% below PRICE and a take-profit 10% above it for longs
+/// (and the other way around for shorts)
+fn triggers(long: bool) -> PositionTriggers {
+    let (stop_loss, take_profit) = if long {
+        (PRICE * 9 / 10, PRICE * 11 / 10)
+    } else {
+        (PRICE * 11 / 10, PRICE * 9 / 10)
+    };
+
+    PositionTriggers {
+        long,
+        stop_loss: Some(stop_loss),
+        take_profit: Some(take_profit),
+        trailing_stop: None,
+    }
+}
+
+#[test]
+fn long_triggers_at_or_beyond_their_prices() {
+    let triggers = triggers(true);
+
+    assert!(!triggers.stop_loss_is_triggered(PRICE));
+    assert!(triggers.stop_loss_is_triggered(PRICE * 9 / 10));
+    assert!(triggers.stop_loss_is_triggered(PRICE * 8 / 10));
+
+    assert!(!triggers.take_profit_is_triggered(PRICE));
+    assert!(triggers.take_profit_is_triggered(PRICE * 11 / 10));
+    assert!(triggers.take_profit_is_triggered(PRICE * 12 / 10));
+}
+
+#[test]
+fn short_triggers_at_or_beyond_their_prices() {
+    let triggers = triggers(false);
+
+    assert!(!triggers.stop_loss_is_triggered(PRICE));
+    assert!(triggers.stop_loss_is_triggered(PRICE * 11 / 10));
+
+    assert!(!triggers.take_profit_is_triggered(PRICE));
+    assert!(triggers.take_profit_is_triggered(PRICE * 9 / 10));
+}
+
+#[test]
+fn stop_loss_must_be_on_the_losing_side_of_the_take_profit() {
+    assert!(triggers(true).is_valid());
+    assert!(triggers(false).is_valid());
+
+    let inverted = PositionTriggers {
+        long: false,
+        ..triggers(true)
+    };
+    assert!(!inverted.is_valid());
+
+    let stop_loss_only = PositionTriggers {
+        take_profit: None,
+        ..inverted
+    };
+    assert!(stop_loss_only.is_valid());
+}
+
+#[test]
+fn position_triggers_are_checked_in_batches() {
+    for position_id in 0..TRIGGERS_EXECUTION_BATCH as u64 + 5 {
+        put_position_triggers(0, user(1), position_id, triggers(true));
+    }
+    put_position_triggers(1, user(1), 0, triggers(true));
+
+    // none of the triggers is reached at PRICE
+    let next = execute_position_triggers(0, PRICE, None);
+    assert_eq!(next, Some((0, user(1), TRIGGERS_EXECUTION_BATCH as u64)));
+
+    // the triggers of the other market are not checked
+    assert_eq!(execute_position_triggers(0, PRICE, next), None);
+}
+
+#[test]
+fn triggered_positions_are_kept_while_the_market_does_not_accept_closing() {
+    // the market does not exist, so it does not accept closing
+    put_position_triggers(0, user(1), 0, triggers(true));
+
+    assert_eq!(execute_position_triggers(0, PRICE / 2, None), None);
+    assert!(get_position_triggers(0, user(1), 0).is_some());
+}
+
+/// trailing stop 5% away from a water mark at PRICE
+fn trailing_stop() -> TrailingStop {
+    TrailingStop {
+        distance: TrailingDistance::Percentage(5 * FLOAT_PRECISION / 100),
+        water_mark: PRICE,
+    }
+}
+
+#[test]
+fn trailing_level_is_the_distance_behind_the_water_mark() {
+    assert_eq!(trailing_stop().trailing_level(true), PRICE * 95 / 100);
+    assert_eq!(trailing_stop().trailing_level(false), PRICE * 105 / 100);
+
+    let absolute = TrailingStop {
+        distance: TrailingDistance::Absolute(PRICE * 2),
+        ..trailing_stop()
+    };
+    assert_eq!(absolute.trailing_level(true), 0);
+}
+
+#[test]
+fn water_mark_only_moves_to_better_prices() {
+    let mut long_stop = trailing_stop();
+    assert!(!long_stop.update_water_mark(PRICE / 2, true));
+    assert!(!long_stop.update_water_mark(PRICE, true));
+    assert_eq!(long_stop.water_mark, PRICE);
+    assert!(long_stop.update_water_mark(2 * PRICE, true));
+    assert_eq!(long_stop.water_mark, 2 * PRICE);
+
+    let mut short_stop = trailing_stop();
+    assert!(!short_stop.update_water_mark(2 * PRICE, false));
+    assert!(short_stop.update_water_mark(PRICE / 2, false));
+    assert_eq!(short_stop.water_mark, PRICE / 2);
+}
+
+#[test]
+fn trailing_stop_follows_the_price_and_triggers_behind_it() {
+    put_position_triggers(
+        0,
+        user(1),
+        0,
+        PositionTriggers {
+            long: true,
+            stop_loss: None,
+            take_profit: None,
+            trailing_stop: Some(trailing_stop()),
+        },
+    );
+
+    execute_position_triggers(0, 2 * PRICE, None);
+
+    let triggers = get_position_triggers(0, user(1), 0).unwrap();
+    assert_eq!(triggers.trailing_stop.unwrap().water_mark, 2 * PRICE);
+    assert!(!triggers.trailing_stop_is_triggered(2 * PRICE * 96 / 100));
+    assert!(triggers.trailing_stop_is_triggered(2 * PRICE * 95 / 100));
+
+    // a worse price does not move the water mark
+    execute_position_triggers(0, 2 * PRICE * 96 / 100, None);
+
+    let triggers = get_position_triggers(0, user(1), 0).unwrap();
+    assert_eq!(triggers.trailing_stop.unwrap().water_mark, 2 * PRICE);
+}
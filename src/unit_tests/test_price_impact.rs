@@ -0,0 +1,164 @@
+use crate::market::functions::open_position_in_market::{
+    FailureReason, OpenPositioninMarketResult,
+};
+use crate::market::market_details::MarketDetails;
+use crate::math::math::{FLOAT_PRECISION, to_precision};
+use crate::open_position::open_position_params::OpenPositionParams;
+use crate::unit_tests::utils::{PRICE, initiate_market, open_position, open_position_params};
+
+const OPEN_INTEREST: u128 = 10_000 * FLOAT_PRECISION;
+
+/// market with linear price impact of 0.1% of the change in the open interest imbalance
+fn market_with_price_impact() -> MarketDetails {
+    let mut market = initiate_market();
+    market.pricing_manager.positive_price_impact_factor = FLOAT_PRECISION / 1_000;
+    market.pricing_manager.negative_price_impact_factor = FLOAT_PRECISION / 1_000;
+    market
+}
+
+/// market skewed by a long of 10_000 open interest that paid 10 of price impact into the impact pool
+fn skewed_market() -> MarketDetails {
+    let mut market = market_with_price_impact();
+    open_position(
+        &mut market,
+        open_position_params(true, 1_000 * FLOAT_PRECISION, 10 * FLOAT_PRECISION),
+        PRICE,
+    );
+    assert_eq!(market.pricing_manager.impact_pool, 10 * FLOAT_PRECISION);
+    market
+}
+
+#[test]
+fn trade_increasing_the_imbalance_pays_negative_impact() {
+    let mut market = market_with_price_impact();
+
+    assert_eq!(
+        market.calculate_price_impact(OPEN_INTEREST, true, true),
+        -10 * FLOAT_PRECISION as i128
+    );
+
+    // the long buys at 100.1
+    let position = open_position(
+        &mut market,
+        open_position_params(true, 1_000 * FLOAT_PRECISION, 10 * FLOAT_PRECISION),
+        PRICE,
+    );
+
+    assert_eq!(
+        position.units,
+        to_precision(OPEN_INTEREST, 1_001 * FLOAT_PRECISION / 10)
+    );
+    assert_eq!(market.pricing_manager.impact_pool, 10 * FLOAT_PRECISION);
+}
+
+#[test]
+fn negative_impact_can_exceed_the_acceptable_price() {
+    let mut market = market_with_price_impact();
+
+    let result = market._open_position_in_market_with_price(
+        OpenPositionParams {
+            acceptable_price_limit: PRICE,
+            ..open_position_params(true, 1_000 * FLOAT_PRECISION, 10 * FLOAT_PRECISION)
+        },
+        Some(PRICE),
+    );
+
+    assert_eq!(
+        result,
+        OpenPositioninMarketResult::Failed {
+            reason: FailureReason::PriceLimitExceeded
+        }
+    );
+    assert_eq!(market.pricing_manager.impact_pool, 0);
+}
+
+#[test]
+fn trade_reducing_the_imbalance_is_paid_from_the_impact_pool() {
+    let mut market = skewed_market();
+
+    // a short of 4_000 reduces the imbalance from 10_000 to 6_000
+    assert_eq!(
+        market.get_execution_price(
+            4_000 * FLOAT_PRECISION,
+            4_000 * FLOAT_PRECISION,
+            false,
+            true,
+            PRICE
+        ),
+        (1_001 * FLOAT_PRECISION / 10, 4 * FLOAT_PRECISION as i128)
+    );
+
+    open_position(
+        &mut market,
+        open_position_params(false, 400 * FLOAT_PRECISION, 10 * FLOAT_PRECISION),
+        PRICE,
+    );
+
+    assert_eq!(market.pricing_manager.impact_pool, 6 * FLOAT_PRECISION);
+}
+
+#[test]
+fn positive_impact_is_capped_by_the_impact_pool() {
+    let mut market = skewed_market();
+    market.pricing_manager.positive_price_impact_factor = FLOAT_PRECISION / 100;
+
+    // the impact of 40 is capped at the 10 in the impact pool
+    assert_eq!(
+        market.calculate_price_impact(4_000 * FLOAT_PRECISION, false, true),
+        40 * FLOAT_PRECISION as i128
+    );
+    assert_eq!(
+        market.get_execution_price(
+            4_000 * FLOAT_PRECISION,
+            4_000 * FLOAT_PRECISION,
+            false,
+            true,
+            PRICE
+        ),
+        (10_025 * FLOAT_PRECISION / 100, 10 * FLOAT_PRECISION as i128)
+    );
+
+    open_position(
+        &mut market,
+        open_position_params(false, 400 * FLOAT_PRECISION, 10 * FLOAT_PRECISION),
+        PRICE,
+    );
+
+    assert_eq!(market.pricing_manager.impact_pool, 0);
+}
+
+#[test]
+fn trade_crossing_the_imbalance_nets_both_sides() {
+    let mut market = skewed_market();
+
+    // a short of 15_000 moves the imbalance from 10_000 long to 5_000 short,
+    // 10 of positive impact for the long side and 5 of negative impact for the short side
+    assert_eq!(
+        market.calculate_price_impact(15_000 * FLOAT_PRECISION, false, true),
+        5 * FLOAT_PRECISION as i128
+    );
+
+    market.pricing_manager.negative_price_impact_factor = 3 * FLOAT_PRECISION / 1_000;
+
+    // 10 of positive impact and 15 of negative impact
+    assert_eq!(
+        market.calculate_price_impact(15_000 * FLOAT_PRECISION, false, true),
+        -5 * FLOAT_PRECISION as i128
+    );
+}
+
+#[test]
+fn closing_the_skewed_side_gets_positive_impact() {
+    let market = skewed_market();
+
+    assert_eq!(
+        market.calculate_price_impact(OPEN_INTEREST, true, false),
+        10 * FLOAT_PRECISION as i128
+    );
+
+    // the long sells at 100.1, the 10 paid on opening is paid back
+    assert_eq!(
+        market.get_execution_price(OPEN_INTEREST, OPEN_INTEREST, true, false, PRICE),
+        (1_001 * FLOAT_PRECISION / 10, 10 * FLOAT_PRECISION as i128)
+    );
+}
@@ -0,0 +1,66 @@
+use std::borrow::Cow;
+
+use candid::Principal;
+use ic_stable_structures::Storable;
+
+use crate::close_position::close_position_params::ClosePositionParams;
+use crate::pricing_update_management::price_waiting_operation_trait::{
+    PRICE_WAITING_OPERATION_LAYOUT_VERSION, PriceWaitingOperation,
+};
+
+fn close_position_operation() -> PriceWaitingOperation {
+    PriceWaitingOperation::ClosePosition(ClosePositionParams {
+        market_index: 2,
+        owner: Principal::from_slice(&[1]),
+        position_id: 3,
+        acceptable_price_limit: 4,
+        size_delta: Some(5),
+        deadline: Some(6),
+    })
+}
+
+#[test]
+fn operations_are_stored_after_their_layout_version() {
+    let bytes = close_position_operation().into_bytes();
+
+    assert_eq!(bytes[0], PRICE_WAITING_OPERATION_LAYOUT_VERSION);
+
+    let decoded = PriceWaitingOperation::from_bytes(Cow::Borrowed(&bytes));
+    assert_eq!(decoded.into_bytes(), bytes);
+}
+
+/// fails if the params of an operation change without a new layout version
+#[test]
+fn stored_layout_is_pinned_to_its_version() {
+    assert_eq!(PRICE_WAITING_OPERATION_LAYOUT_VERSION, 1);
+
+    let bytes = close_position_operation().into_bytes();
+
+    // version, variant, market index, owner, position id, acceptable price limit, size delta and deadline
+    assert_eq!(
+        hex(&bytes),
+        concat!(
+            "01",
+            "01000000",
+            "0200000000000000",
+            "010000000000000001",
+            "0300000000000000",
+            "04000000000000000000000000000000",
+            "0105000000000000000000000000000000",
+            "010600000000000000",
+        )
+    );
+}
+
+#[test]
+#[should_panic(expected = "unknown price waiting operation layout version 0")]
+fn operations_with_an_unknown_layout_version_are_not_decoded() {
+    let mut bytes = close_position_operation().into_bytes();
+    bytes[0] = 0;
+
+    PriceWaitingOperation::from_bytes(Cow::Owned(bytes));
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
@@ -0,0 +1,118 @@
+use candid::Principal;
+
+use crate::add_liquidity::add_liquidity_params::AddLiquidityParams;
+use crate::close_position::close_position_params::ClosePositionParams;
+use crate::constants::{
+    ADD_LIQUIDITY_PRIORITY_INDEX, CLOSE_POSITION_PRIORITY_INDEX, OPEN_POSITION_PRIORITY_INDEX,
+};
+use crate::math::math::FLOAT_PRECISION;
+use crate::open_position::open_position_params::OpenPositionParams;
+use crate::pricing_update_management::operation_status::{OperationStatus, get_operation_status};
+use crate::pricing_update_management::price_waiting_operation_trait::PriceWaitingOperation;
+use crate::pricing_update_management::price_waiting_operation_utils::{
+    _execute_price_waiting_operations, _queue_price_waiting_operation,
+    _take_price_waiting_operations, get_price_waiting_operation,
+};
+use crate::unit_tests::utils::open_position_params;
+use crate::user::balance_utils::{get_user_balance, update_user_balance};
+
+fn user(id: u8) -> Principal {
+    Principal::from_slice(&[id])
+}
+
+/// open of 100 collateral by user(1) in the market
+fn open_operation(market_index: u64, deadline: Option<u64>) -> PriceWaitingOperation {
+    PriceWaitingOperation::OpenPosition(OpenPositionParams {
+        owner: user(1),
+        market_index,
+        deadline,
+        ..open_position_params(true, 100 * FLOAT_PRECISION, FLOAT_PRECISION)
+    })
+}
+
+/// close of position 0 of user(1), which does not exist, in market 0
+fn close_operation(deadline: Option<u64>) -> PriceWaitingOperation {
+    PriceWaitingOperation::ClosePosition(ClosePositionParams {
+        market_index: 0,
+        owner: user(1),
+        position_id: 0,
+        acceptable_price_limit: 0,
+        size_delta: None,
+        deadline,
+    })
+}
+
+fn operation_ids(operations: &[((u64, u8, u64), PriceWaitingOperation)]) -> Vec<u64> {
+    operations
+        .iter()
+        .map(|((_, _, operation_id), _)| *operation_id)
+        .collect()
+}
+
+#[test]
+fn operations_are_taken_by_priority_and_then_in_queue_order() {
+    update_user_balance(user(1), 1_000 * FLOAT_PRECISION, true);
+
+    let add_liquidity = PriceWaitingOperation::AddLiquidity(AddLiquidityParams {
+        market_index: 0,
+        depositor: user(1),
+        amount: 100 * FLOAT_PRECISION,
+        min_amount_out: 0,
+        deadline: None,
+    });
+
+    _queue_price_waiting_operation(0, OPEN_POSITION_PRIORITY_INDEX, open_operation(0, None));
+    _queue_price_waiting_operation(0, ADD_LIQUIDITY_PRIORITY_INDEX, add_liquidity);
+    _queue_price_waiting_operation(0, OPEN_POSITION_PRIORITY_INDEX, open_operation(0, None));
+    _queue_price_waiting_operation(0, CLOSE_POSITION_PRIORITY_INDEX, close_operation(None));
+    _queue_price_waiting_operation(1, OPEN_POSITION_PRIORITY_INDEX, open_operation(1, None));
+
+    // the first operation of every priority is kept
+    let operations = _take_price_waiting_operations(0);
+    assert_eq!(operation_ids(&operations), vec![1, 3, 0, 2]);
+
+    // taken operations leave the queue, the operations of other markets stay
+    assert!(_take_price_waiting_operations(0).is_empty());
+    assert!(get_price_waiting_operation(0).is_none());
+    assert!(get_price_waiting_operation(4).is_some());
+}
+
+#[test]
+fn expired_operation_is_failed_and_its_collateral_released() {
+    update_user_balance(user(1), 1_000 * FLOAT_PRECISION, true);
+
+    let ticket = _queue_price_waiting_operation(
+        0,
+        OPEN_POSITION_PRIORITY_INDEX,
+        open_operation(0, Some(100)),
+    );
+    assert_eq!(get_user_balance(user(1)), 900 * FLOAT_PRECISION);
+
+    let retry_delay = _execute_price_waiting_operations(_take_price_waiting_operations(0), 101);
+
+    assert_eq!(retry_delay, None);
+    assert_eq!(
+        get_operation_status(ticket),
+        Some(OperationStatus::Failed("Deadline exceeded".to_string()))
+    );
+    assert_eq!(get_user_balance(user(1)), 1_000 * FLOAT_PRECISION);
+}
+
+#[test]
+fn operation_is_executed_until_its_deadline() {
+    let ticket = _queue_price_waiting_operation(
+        0,
+        CLOSE_POSITION_PRIORITY_INDEX,
+        close_operation(Some(100)),
+    );
+
+    _execute_price_waiting_operations(_take_price_waiting_operations(0), 100);
+
+    // executed, the position does not exist
+    assert_eq!(
+        get_operation_status(ticket),
+        Some(OperationStatus::Failed(
+            "Position could not be closed".to_string()
+        ))
+    );
+}
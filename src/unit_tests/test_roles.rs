@@ -0,0 +1,77 @@
+use candid::Principal;
+
+use crate::admin_roles::grant_role::grant_role;
+use crate::admin_roles::roles::{
+    Role, get_granted_roles, get_owner, get_pending_owner, has_role, revoke_role,
+};
+use crate::admin_roles::transfer_ownership::{_accept_ownership, propose_owner};
+
+fn user(id: u8) -> Principal {
+    Principal::from_slice(&[id])
+}
+
+#[test]
+fn owner_holds_every_role() {
+    for role in [
+        Role::Owner,
+        Role::RiskManager,
+        Role::Keeper,
+        Role::Pauser,
+        Role::Treasurer,
+    ] {
+        assert!(has_role(get_owner(), role));
+        assert!(!has_role(user(1), role));
+    }
+}
+
+#[test]
+fn granted_role_is_held_until_it_is_revoked() {
+    assert!(grant_role(user(1), Role::Keeper));
+    assert!(!grant_role(user(1), Role::Keeper));
+
+    assert!(has_role(user(1), Role::Keeper));
+    assert!(!has_role(user(1), Role::Pauser));
+
+    assert!(revoke_role(user(1), Role::Keeper));
+    assert!(!revoke_role(user(1), Role::Keeper));
+
+    assert!(!has_role(user(1), Role::Keeper));
+    assert!(get_granted_roles(user(1)).roles.is_empty());
+}
+
+#[test]
+fn revoking_a_role_keeps_the_other_roles() {
+    grant_role(user(1), Role::Keeper);
+    grant_role(user(1), Role::Pauser);
+
+    revoke_role(user(1), Role::Keeper);
+
+    assert!(!has_role(user(1), Role::Keeper));
+    assert!(has_role(user(1), Role::Pauser));
+}
+
+#[test]
+#[should_panic(expected = "Owner role can not be granted")]
+fn owner_role_can_not_be_granted() {
+    grant_role(user(1), Role::Owner);
+}
+
+#[test]
+fn ownership_is_transferred_once_the_proposed_owner_accepts() {
+    let previous_owner = get_owner();
+
+    propose_owner(user(1));
+    assert_eq!(get_pending_owner(), Some(user(1)));
+    assert_eq!(get_owner(), previous_owner);
+
+    let result = std::panic::catch_unwind(|| _accept_ownership(user(2)));
+    assert!(result.is_err());
+    assert_eq!(get_owner(), previous_owner);
+
+    _accept_ownership(user(1));
+
+    assert_eq!(get_owner(), user(1));
+    assert_eq!(get_pending_owner(), None);
+    assert!(has_role(user(1), Role::Owner));
+    assert!(!has_role(previous_owner, Role::Owner));
+}
@@ -0,0 +1,100 @@
+use candid::Principal;
+
+use crate::admin_roles::roles::{Role, grant_role};
+use crate::constants::{_ONE_SECOND, DEFAULT_TIMELOCK_DELAY, TIMELOCK_GRACE_PERIOD};
+use crate::house_settings::get_execution_fee;
+use crate::timelock::timelock_utils::{
+    _cancel_queued_change, _execute_queued_change, _queue_change, get_queued_change,
+};
+use crate::timelock::timelocked_change::TimelockedChange;
+
+/// earliest execution time of a change queued at time 0
+const ETA: u64 = DEFAULT_TIMELOCK_DELAY * _ONE_SECOND;
+
+fn user(id: u8) -> Principal {
+    Principal::from_slice(&[id])
+}
+
+/// execution fee change queued at time 0 by a risk manager
+fn queue_execution_fee_change() -> u64 {
+    grant_role(user(1), Role::RiskManager);
+
+    _queue_change(TimelockedChange::SetExecutionFee(5), user(1), 0)
+}
+
+#[test]
+fn queued_change_stores_the_role_it_requires() {
+    let change_id = queue_execution_fee_change();
+
+    let queued_change = get_queued_change(change_id).unwrap();
+    assert_eq!(queued_change.role, Role::RiskManager);
+    assert_eq!(queued_change.eta, ETA);
+
+    let change_id = _queue_change(TimelockedChange::SetTimelockDelay(3_600), user(1), 0);
+    assert_eq!(get_queued_change(change_id).unwrap().role, Role::Owner);
+}
+
+#[test]
+#[should_panic(expected = "Timelock has not expired")]
+fn change_can_not_be_executed_before_its_eta() {
+    let change_id = queue_execution_fee_change();
+
+    _execute_queued_change(change_id, user(1), ETA - 1);
+}
+
+#[test]
+#[should_panic(expected = "Change is stale")]
+fn change_can_not_be_executed_after_the_grace_period() {
+    let change_id = queue_execution_fee_change();
+
+    _execute_queued_change(
+        change_id,
+        user(1),
+        ETA + TIMELOCK_GRACE_PERIOD * _ONE_SECOND + 1,
+    );
+}
+
+#[test]
+fn change_is_executed_within_the_grace_period() {
+    let change_id = queue_execution_fee_change();
+
+    _execute_queued_change(
+        change_id,
+        user(1),
+        ETA + TIMELOCK_GRACE_PERIOD * _ONE_SECOND,
+    );
+
+    assert_eq!(get_execution_fee(), 5);
+    assert!(get_queued_change(change_id).is_none());
+}
+
+#[test]
+#[should_panic(expected = "Caller does not hold the role of the change")]
+fn change_can_not_be_executed_without_its_role() {
+    let change_id = queue_execution_fee_change();
+    grant_role(user(2), Role::Keeper);
+
+    _execute_queued_change(change_id, user(2), ETA);
+}
+
+#[test]
+#[should_panic(expected = "Caller does not hold the role of the change")]
+fn owner_change_can_not_be_executed_by_a_risk_manager() {
+    grant_role(user(1), Role::RiskManager);
+    let change_id = _queue_change(TimelockedChange::SetTimelockDelay(3_600), user(1), 0);
+
+    _execute_queued_change(change_id, user(1), ETA);
+}
+
+#[test]
+fn change_is_cancelled_by_its_role_only() {
+    let change_id = queue_execution_fee_change();
+
+    let result = std::panic::catch_unwind(|| _cancel_queued_change(change_id, user(2)));
+    assert!(result.is_err());
+    assert!(get_queued_change(change_id).is_some());
+
+    assert!(_cancel_queued_change(change_id, user(1)));
+    assert!(get_queued_change(change_id).is_none());
+    assert!(!_cancel_queued_change(change_id, user(1)));
+}
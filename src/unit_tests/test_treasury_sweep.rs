@@ -0,0 +1,39 @@
+use crate::asset_management::asset_management::AssetLedger;
+use crate::math::math::FLOAT_PRECISION;
+use crate::treasury::sweep_treasury_fees::_sweepable_fees;
+
+/// ledger of an asset with 8 decimals
+fn ledger() -> AssetLedger {
+    AssetLedger {
+        asset_decimals: 8,
+        ledger_fee: 10_000,
+        ..Default::default()
+    }
+}
+
+/// one unit of the asset on the ledger, with 20-decimal precision
+const LEDGER_UNIT: u128 = FLOAT_PRECISION / 100_000_000;
+
+#[test]
+fn fees_are_swept_in_whole_ledger_units() {
+    let execution_fees = 3 * FLOAT_PRECISION + LEDGER_UNIT / 2;
+    let position_fees = 5 * LEDGER_UNIT + 1;
+
+    let (swept_execution_fees, swept_position_fees) =
+        _sweepable_fees(&ledger(), execution_fees, position_fees);
+
+    assert_eq!(swept_execution_fees, 3 * FLOAT_PRECISION);
+    assert_eq!(swept_position_fees, 5 * LEDGER_UNIT);
+
+    // the remainders stay accumulated for the next sweep
+    assert_eq!(execution_fees - swept_execution_fees, LEDGER_UNIT / 2);
+    assert_eq!(position_fees - swept_position_fees, 1);
+}
+
+#[test]
+fn fees_below_a_ledger_unit_are_not_swept() {
+    assert_eq!(
+        _sweepable_fees(&ledger(), LEDGER_UNIT - 1, LEDGER_UNIT - 1),
+        (0, 0)
+    );
+}
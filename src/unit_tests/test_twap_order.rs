@@ -0,0 +1,159 @@
+use candid::Principal;
+
+use crate::close_position::close_position_params::ClosePositionParams;
+use crate::math::math::FLOAT_PRECISION;
+use crate::open_position::open_position_params::OpenPositionParams;
+use crate::order_management::twap_order::{TwapOrder, TwapOrderStatus, TwapOrderType};
+use crate::pricing_update_management::price_waiting_operation_trait::PriceWaitingOperation;
+use crate::unit_tests::utils::{long_position, open_position_params};
+use crate::user::position_util::_put_user_position_detail;
+
+fn user(id: u8) -> Principal {
+    Principal::from_slice(&[id])
+}
+
+fn twap_order(order_type: TwapOrderType, children: u64) -> TwapOrder {
+    TwapOrder {
+        order_type,
+        children,
+        interval_in_secs: 60,
+        executed_children: 0,
+        filled_size: 0,
+        position_id: None,
+        failed_children: Vec::new(),
+        next_execution_time: 0,
+        status: TwapOrderStatus::Active,
+        created_at: 0,
+    }
+}
+
+/// order opening a long of 1_000 collateral plus a remainder of 2 at 2x for user(1) in market 0
+fn open_order(children: u64) -> TwapOrder {
+    let params = OpenPositionParams {
+        owner: user(1),
+        deadline: Some(1),
+        ..open_position_params(true, 1_000 * FLOAT_PRECISION + 2, 2 * FLOAT_PRECISION)
+    };
+
+    twap_order(TwapOrderType::Open(params), children)
+}
+
+/// order closing position 0 of user(1) in market 0
+fn close_order(children: u64) -> TwapOrder {
+    let params = ClosePositionParams {
+        market_index: 0,
+        owner: user(1),
+        position_id: 0,
+        acceptable_price_limit: 0,
+        size_delta: None,
+        deadline: Some(1),
+    };
+
+    twap_order(TwapOrderType::Close(params), children)
+}
+
+#[test]
+fn open_order_collateral_is_split_equally_and_the_last_child_takes_the_remainder() {
+    let order = open_order(4);
+
+    for index in 0..3 {
+        assert_eq!(order.child_collateral(index), 250 * FLOAT_PRECISION);
+    }
+    assert_eq!(order.child_collateral(3), 250 * FLOAT_PRECISION + 2);
+
+    assert_eq!(order.reserved_collateral(), 1_000 * FLOAT_PRECISION + 2);
+}
+
+#[test]
+fn reserved_collateral_is_the_collateral_of_the_children_not_executed() {
+    let mut order = open_order(4);
+
+    order.executed_children = 3;
+    assert_eq!(order.reserved_collateral(), 250 * FLOAT_PRECISION + 2);
+
+    order.executed_children = 4;
+    assert_eq!(order.reserved_collateral(), 0);
+}
+
+#[test]
+fn first_open_child_opens_a_position_and_the_next_children_increase_it() {
+    let mut order = open_order(4);
+
+    let Some((PriceWaitingOperation::OpenPosition(params), size)) = order.next_child_operation()
+    else {
+        panic!("first child does not open a position");
+    };
+    assert_eq!(size, 250 * FLOAT_PRECISION);
+    assert_eq!(params.collateral, 250 * FLOAT_PRECISION);
+    assert_eq!(params.leverage_factor, 2 * FLOAT_PRECISION);
+    // the deadline of the order is not passed to its children
+    assert_eq!(params.deadline, None);
+
+    _put_user_position_detail(user(1), 0, 7, long_position());
+    order.executed_children = 1;
+    order.position_id = Some(7);
+
+    let Some((PriceWaitingOperation::IncreasePosition(params), size)) =
+        order.next_child_operation()
+    else {
+        panic!("next child does not increase the position");
+    };
+    assert_eq!(size, 250 * FLOAT_PRECISION);
+    assert_eq!(params.position_id, 7);
+    assert_eq!(params.collateral, 250 * FLOAT_PRECISION);
+}
+
+#[test]
+fn open_child_opens_a_new_position_if_the_position_was_closed() {
+    let mut order = open_order(4);
+    order.executed_children = 1;
+    order.position_id = Some(7);
+
+    assert!(matches!(
+        order.next_child_operation(),
+        Some((PriceWaitingOperation::OpenPosition(_), _))
+    ));
+
+    // a position with the same id in another market is not increased
+    _put_user_position_detail(user(1), 1, 7, long_position());
+
+    assert!(matches!(
+        order.next_child_operation(),
+        Some((PriceWaitingOperation::OpenPosition(_), _))
+    ));
+}
+
+#[test]
+fn close_children_close_an_equal_part_of_the_remaining_units() {
+    let position = long_position();
+    _put_user_position_detail(user(1), 0, 0, position);
+
+    let mut order = close_order(4);
+
+    let Some((PriceWaitingOperation::ClosePosition(params), size)) = order.next_child_operation()
+    else {
+        panic!("child does not close the position");
+    };
+    assert_eq!(params.size_delta, Some(position.units / 4));
+    assert_eq!(size, position.units / 4);
+    assert_eq!(params.deadline, None);
+
+    // the last child closes whatever is left
+    order.executed_children = 3;
+
+    let Some((PriceWaitingOperation::ClosePosition(params), size)) = order.next_child_operation()
+    else {
+        panic!("child does not close the position");
+    };
+    assert_eq!(params.size_delta, None);
+    assert_eq!(size, position.units);
+}
+
+#[test]
+fn close_child_has_no_operation_once_the_position_is_gone() {
+    assert!(close_order(4).next_child_operation().is_none());
+
+    // the position is in another market than the order
+    _put_user_position_detail(user(1), 1, 0, long_position());
+    assert!(close_order(4).next_child_operation().is_none());
+}
@@ -0,0 +1,244 @@
+use crate::market::functions::open_position_in_market::{
+    FailureReason, OpenPositioninMarketResult,
+};
+use crate::market::market_details::MarketDetails;
+use crate::math::math::FLOAT_PRECISION;
+use crate::position::position_details::PositionDetails;
+use crate::unit_tests::utils::{
+    INITIAL_LIQUIDITY, PRICE, initiate_market, open_position, open_position_params,
+};
+
+const FAILED: OpenPositioninMarketResult = OpenPositioninMarketResult::Failed {
+    reason: FailureReason::Other,
+};
+
+/// market with a long of 1_000 collateral at the leverage opened at PRICE
+fn market_with_long_position(leverage_factor: u128) -> (MarketDetails, PositionDetails) {
+    let mut market = initiate_market();
+
+    let position = open_position(
+        &mut market,
+        open_position_params(true, 1_000 * FLOAT_PRECISION, leverage_factor),
+        PRICE,
+    );
+
+    (market, position)
+}
+
+fn updated_position(result: OpenPositioninMarketResult) -> PositionDetails {
+    match result {
+        OpenPositioninMarketResult::Settled { position, .. } => position,
+        result => panic!("collateral not updated: {:?}", result),
+    }
+}
+
+#[test]
+fn collateral_can_be_removed_up_to_max_leverage() {
+    let (mut market, position) = market_with_long_position(5 * FLOAT_PRECISION);
+    let free_liquidity = market.liquidity_state.free_liquidity;
+
+    // 5_000 open interest on 500 collateral is exactly the 10x max leverage
+    let result = market._remove_collateral_from_position_with_price(
+        position,
+        500 * FLOAT_PRECISION,
+        Some(PRICE),
+    );
+
+    assert_eq!(
+        updated_position(result),
+        PositionDetails {
+            collateral: 500 * FLOAT_PRECISION,
+            debt: 4_500 * FLOAT_PRECISION,
+            ..position
+        }
+    );
+    assert_eq!(
+        market.liquidity_state.free_liquidity,
+        free_liquidity - 500 * FLOAT_PRECISION
+    );
+    assert_eq!(
+        market.liquidity_state.current_net_debt,
+        4_500 * FLOAT_PRECISION
+    );
+    assert_eq!(
+        market.liquidity_state.total_deposit,
+        INITIAL_LIQUIDITY + 500 * FLOAT_PRECISION
+    );
+}
+
+#[test]
+fn collateral_removal_above_max_leverage_fails() {
+    let (mut market, position) = market_with_long_position(5 * FLOAT_PRECISION);
+    let liquidity_state = market.liquidity_state;
+
+    let result = market._remove_collateral_from_position_with_price(
+        position,
+        501 * FLOAT_PRECISION,
+        Some(PRICE),
+    );
+
+    assert_eq!(result, FAILED);
+    assert_eq!(market.liquidity_state, liquidity_state);
+}
+
+#[test]
+fn collateral_removal_making_the_position_liquidatable_fails() {
+    let (mut market, position) = market_with_long_position(5 * FLOAT_PRECISION);
+    let price = 91 * FLOAT_PRECISION;
+
+    // the loss of 450 leaves 50 of 500 collateral, at the 10% liquidation factor
+    let result = market._remove_collateral_from_position_with_price(
+        position,
+        500 * FLOAT_PRECISION,
+        Some(price),
+    );
+    assert_eq!(result, FAILED);
+
+    // the loss of 450 leaves 150 of 600 collateral
+    let result = market._remove_collateral_from_position_with_price(
+        position,
+        400 * FLOAT_PRECISION,
+        Some(price),
+    );
+    assert_eq!(updated_position(result).collateral, 600 * FLOAT_PRECISION);
+}
+
+#[test]
+fn removing_no_or_all_collateral_fails() {
+    let (mut market, position) = market_with_long_position(5 * FLOAT_PRECISION);
+
+    assert_eq!(
+        market._remove_collateral_from_position_with_price(position, 0, Some(PRICE)),
+        FAILED
+    );
+    assert_eq!(
+        market._remove_collateral_from_position_with_price(
+            position,
+            position.collateral,
+            Some(PRICE)
+        ),
+        FAILED
+    );
+}
+
+#[test]
+fn collateral_removal_waits_for_a_price() {
+    let (mut market, position) = market_with_long_position(5 * FLOAT_PRECISION);
+
+    assert_eq!(
+        market._remove_collateral_from_position_with_price(position, 100 * FLOAT_PRECISION, None),
+        OpenPositioninMarketResult::Waiting { id: None }
+    );
+}
+
+#[test]
+fn added_collateral_repays_debt_without_a_price() {
+    let (mut market, position) = market_with_long_position(5 * FLOAT_PRECISION);
+    let free_liquidity = market.liquidity_state.free_liquidity;
+
+    // only the 4_000 of debt can be repaid without a price
+    assert_eq!(
+        market._add_collateral_to_position_with_price(position, 4_001 * FLOAT_PRECISION, None),
+        OpenPositioninMarketResult::Waiting { id: None }
+    );
+    assert_eq!(market.liquidity_state.free_liquidity, free_liquidity);
+
+    let result =
+        market._add_collateral_to_position_with_price(position, 4_000 * FLOAT_PRECISION, None);
+
+    assert_eq!(
+        updated_position(result),
+        PositionDetails {
+            collateral: 5_000 * FLOAT_PRECISION,
+            debt: 0,
+            ..position
+        }
+    );
+    assert_eq!(
+        market.liquidity_state.free_liquidity,
+        free_liquidity + 4_000 * FLOAT_PRECISION
+    );
+    assert_eq!(market.liquidity_state.current_net_debt, 0);
+}
+
+#[test]
+fn collateral_beyond_the_debt_is_added_at_1x_leverage() {
+    let (mut market, position) = market_with_long_position(5 * FLOAT_PRECISION);
+    let total_deposit = market.liquidity_state.total_deposit;
+
+    // 4_000 repays the debt and the 1_000 left buys 10 units at PRICE
+    let result = market._add_collateral_to_position_with_price(
+        position,
+        5_000 * FLOAT_PRECISION,
+        Some(PRICE),
+    );
+
+    let updated_position = updated_position(result);
+    assert_eq!(
+        updated_position,
+        PositionDetails {
+            collateral: 6_000 * FLOAT_PRECISION,
+            debt: 0,
+            units: 60 * FLOAT_PRECISION,
+            max_reserve: updated_position.max_reserve,
+            ..position
+        }
+    );
+    assert_eq!(updated_position.get_pnl(PRICE), 0);
+    assert_eq!(market.liquidity_state.current_net_debt, 0);
+    assert_eq!(
+        market.liquidity_state.total_deposit,
+        total_deposit + 5_000 * FLOAT_PRECISION
+    );
+}
+
+#[test]
+fn collateral_added_to_a_1x_position_is_added_at_1x_leverage() {
+    let (mut market, position) = market_with_long_position(FLOAT_PRECISION);
+
+    assert_eq!(position.debt, 0);
+    assert_eq!(position.units, 10 * FLOAT_PRECISION);
+
+    let result =
+        market._add_collateral_to_position_with_price(position, 100 * FLOAT_PRECISION, Some(PRICE));
+
+    assert_eq!(
+        updated_position(result),
+        PositionDetails {
+            collateral: 1_100 * FLOAT_PRECISION,
+            units: 11 * FLOAT_PRECISION,
+            max_reserve: position.max_reserve * 11 / 10,
+            ..position
+        }
+    );
+}
+
+#[test]
+fn adding_no_collateral_fails() {
+    let (mut market, position) = market_with_long_position(5 * FLOAT_PRECISION);
+
+    assert_eq!(
+        market._add_collateral_to_position_with_price(position, 0, Some(PRICE)),
+        FAILED
+    );
+}
+
+#[test]
+fn collateral_removed_from_a_1x_position_is_borrowed() {
+    let (mut market, position) = market_with_long_position(FLOAT_PRECISION);
+
+    let result = market._remove_collateral_from_position_with_price(
+        position,
+        100 * FLOAT_PRECISION,
+        Some(PRICE),
+    );
+
+    assert_eq!(
+        updated_position(result),
+        PositionDetails {
+            collateral: 900 * FLOAT_PRECISION,
+            debt: 100 * FLOAT_PRECISION,
+            ..position
+        }
+    );
+}
@@ -0,0 +1,86 @@
+use candid::Principal;
+
+use crate::market::functions::open_position_in_market::OpenPositioninMarketResult;
+use crate::market::market_config::MarketConfig;
+use crate::market::market_details::MarketDetails;
+use crate::math::math::FLOAT_PRECISION;
+use crate::open_position::open_position_params::OpenPositionParams;
+use crate::position::position_details::PositionDetails;
+
+/// liquidity deposited into the market by liquidity providers
+pub const INITIAL_LIQUIDITY: u128 = 100_000 * FLOAT_PRECISION;
+
+/// oracle price of the index asset
+pub const PRICE: u128 = 100 * FLOAT_PRECISION;
+
+/// Initiate Market
+///
+/// market with 10x max leverage, a 10% liquidation factor, no fees, no price impact
+/// and INITIAL_LIQUIDITY of free liquidity
+pub fn initiate_market() -> MarketDetails {
+    let mut market = MarketDetails::default();
+
+    market.apply_config(MarketConfig {
+        max_leverage_factor: 10 * FLOAT_PRECISION,
+        max_reserve_factor: FLOAT_PRECISION,
+        liquidation_factor: FLOAT_PRECISION / 10,
+        funding_exponent_factor: FLOAT_PRECISION,
+        longs_max_reserve_factor: FLOAT_PRECISION / 2,
+        longs_borrowing_exponent_factor: FLOAT_PRECISION,
+        shorts_max_reserve_factor: FLOAT_PRECISION / 2,
+        shorts_borrowing_exponent_factor: FLOAT_PRECISION,
+        price_impact_exponent_factor: FLOAT_PRECISION,
+        ..MarketConfig::default()
+    });
+
+    market.liquidity_state.free_liquidity = INITIAL_LIQUIDITY;
+    market.liquidity_state.total_deposit = INITIAL_LIQUIDITY;
+
+    market
+}
+
+/// params for opening a position with a reserve factor of 100% and no price limit
+pub fn open_position_params(
+    long: bool,
+    collateral: u128,
+    leverage_factor: u128,
+) -> OpenPositionParams {
+    OpenPositionParams {
+        owner: Principal::anonymous(),
+        long,
+        market_index: 0,
+        collateral,
+        leverage_factor,
+        acceptable_price_limit: if long { u128::MAX } else { 0 },
+        reserve_factor: FLOAT_PRECISION,
+        deadline: None,
+    }
+}
+
+/// Long Position
+///
+/// 1_000 collateral at 10x bought at PRICE, 100 units and a max reserve equal to its open interest
+pub fn long_position() -> PositionDetails {
+    PositionDetails {
+        owner: Principal::anonymous(),
+        collateral: 1_000 * FLOAT_PRECISION,
+        debt: 9_000 * FLOAT_PRECISION,
+        long: true,
+        units: 100 * FLOAT_PRECISION,
+        max_reserve: 10_000 * FLOAT_PRECISION,
+        pre_cummulative_funding_factor: 0,
+        pre_cummulative_borrowing_factor: 0,
+    }
+}
+
+/// opens a position in the market at the price, panics if it is not settled
+pub fn open_position(
+    market: &mut MarketDetails,
+    params: OpenPositionParams,
+    price: u128,
+) -> PositionDetails {
+    match market._open_position_in_market_with_price(params, Some(price)) {
+        OpenPositioninMarketResult::Settled { position, .. } => position,
+        result => panic!("position not opened: {:?}", result),
+    }
+}
@@ -1,6 +1,11 @@
 use candid::Principal;
+use ic_cdk::api::time;
 
-use crate::{position::position_details::PositionDetails, stable_memory::USERS_POSITIONS};
+use crate::{
+    order_management::order_utils::cancel_position_orders,
+    order_management::position_triggers::remove_position_triggers,
+    position::position_details::PositionDetails, stable_memory::USERS_POSITIONS,
+};
 
 #[derive(Debug, Clone)]
 pub struct QueryGetUserPositionState {
@@ -24,10 +29,34 @@ pub fn _put_user_position_detail(
     });
 }
 
-pub fn remove_user_position_detail(user: Principal, position_id: u64) {
-    USERS_POSITIONS.with_borrow_mut(|reference| {
-        reference.remove(&(user, position_id));
+/// Next User Position ID
+///
+/// position IDs are the time of opening, bumped until unique for the user
+/// since operations executed in the same batch share the same time
+pub fn _next_user_position_id(user: Principal) -> u64 {
+    let mut position_id = time();
+
+    USERS_POSITIONS.with_borrow(|reference| {
+        while reference.contains_key(&(user, position_id)) {
+            position_id += 1;
+        }
     });
+
+    position_id
+}
+
+/// Remove User Position Detail
+///
+/// removes a position, its stop-loss and take-profit triggers and cancels its limit close orders
+pub fn remove_user_position_detail(user: Principal, position_id: u64) {
+    let removed =
+        USERS_POSITIONS.with_borrow_mut(|reference| reference.remove(&(user, position_id)));
+
+    if let Some((market_index, _)) = removed {
+        remove_position_triggers(market_index, user, position_id);
+
+        cancel_position_orders(user, position_id);
+    }
 }
 
 pub fn try_get_user_position_details(
@@ -3,11 +3,15 @@ use crate::constants::{_ONE_SECOND, ONE_HOUR_NANOSECONDS};
 pub fn duration_in_hours(start_time: u64) -> u64 {
     let duration_in_nano_secs = ic_cdk::api::time() - start_time;
 
-    return duration_in_nano_secs / ONE_HOUR_NANOSECONDS;
+    duration_in_nano_secs / ONE_HOUR_NANOSECONDS
 }
 
 pub fn duration_in_seconds(start_time: u64) -> u64 {
-    let duration_in_nano_secs = ic_cdk::api::time() - start_time;
+    _duration_in_seconds(start_time, ic_cdk::api::time())
+}
+
+pub fn _duration_in_seconds(start_time: u64, current_time: u64) -> u64 {
+    let duration_in_nano_secs = current_time - start_time;
 
-    return duration_in_nano_secs / _ONE_SECOND;
+    duration_in_nano_secs / _ONE_SECOND
 }
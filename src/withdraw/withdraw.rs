@@ -1,8 +1,9 @@
 use ic_cdk::{api::msg_caller, update};
 
 use crate::{
-    house_settings::get_house_asset_ledger,
-    user::balance_utils::{get_user_balance, set_user_balance},
+    events::{event_log::record_event, evnts_type::Events},
+    house_settings::{get_house_asset_ledger, is_paused},
+    user::balance_utils::{get_user_balance, set_user_balance, update_user_balance},
     withdraw::withdraw_params::WithdrawParams,
 };
 
@@ -38,6 +39,8 @@ use crate::{
 /// 4. Sends the withdrawal transaction to the house asset ledger
 /// 5. If ledger transaction fails, refunds the user's balance
 ///
+/// The ledger transfer fee is taken out of the amount sent to the user
+///
 /// # Example Usage
 ///
 /// ```rust
@@ -54,18 +57,25 @@ use crate::{
 /// ```
 #[update(name = "withdrawFromAccount")]
 pub async fn withdraw_from_account(params: WithdrawParams) -> bool {
+    assert!(!is_paused(), "Protocol is paused");
+
     let user = msg_caller();
 
     let house_asset_ledger = get_house_asset_ledger();
 
     let user_balance = get_user_balance(user);
 
-    assert!(user_balance > params.amount, "Insufficient balance");
+    assert!(user_balance >= params.amount, "Insufficient balance");
     set_user_balance(user, user_balance - params.amount);
     let tx_result = house_asset_ledger._send_out(params.amount, user).await;
-    if tx_result == false {
+    if !tx_result {
         //refund
-        set_user_balance(user, user_balance + params.amount);
+        update_user_balance(user, params.amount, true);
+    } else {
+        record_event(Events::Withdraw {
+            user,
+            amount: params.amount,
+        });
     }
 
     tx_result